@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+
+use aoc_common::parse::{parse_at, signed, tokens};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
+pub fn calculate_frequency(changes: &[i32]) -> i32 {
+    changes.iter().sum()
+}
+
+pub fn find_first_repeated_frequency(changes: &[i32]) -> Option<i32> {
+    if changes.is_empty() {
+        return None;
+    }
+
+    let mut frequency_cache = HashSet::with_capacity(changes.len());
+    let mut frequency = 0;
+
+    for change in changes.iter().cycle() {
+        if !frequency_cache.insert(frequency) {
+            return Some(frequency);
+        }
+
+        frequency += change;
+    }
+
+    None
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i32>;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i32>> {
+        let mut changes = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            for (column, change) in tokens(line) {
+                changes.push(parse_at(signed(), change, idx + 1, column)?);
+            }
+        }
+
+        Ok(changes)
+    }
+
+    fn part_one(&self, input: &Vec<i32>) -> AocResult<String> {
+        Ok(calculate_frequency(input).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i32>) -> AocResult<String> {
+        find_first_repeated_frequency(input)
+            .map(|frequency| frequency.to_string())
+            .ok_or_else(|| AocError::no_solution("No frequency is reached twice."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_frequency() {
+        assert_eq!(find_first_repeated_frequency(&[1, -1]), Some(0));
+        assert_eq!(find_first_repeated_frequency(&[3, 3, 4, -2, -4]), Some(10));
+        assert_eq!(find_first_repeated_frequency(&[-6, 3, 8, 5, -6]), Some(5));
+        assert_eq!(find_first_repeated_frequency(&[7, 7, -2, -7, -4]), Some(14));
+    }
+}
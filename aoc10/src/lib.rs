@@ -1,63 +1,22 @@
 #[macro_use] extern crate lazy_static;
-use aoc_common::{GenericResult, GenericError};
+use aoc_common::math::Point;
+use aoc_common::ocr;
+use aoc_common::parse::{parse_at, signed};
+use aoc_common::solution::Solution;
+use aoc_common::{AocResult, AocError};
 use regex::Regex;
 use std::collections::{HashSet, HashMap};
-use std::collections::vec_deque::VecDeque;
+use aoc_collections::UnionFind;
 use std::cmp::Ordering;
 
-#[derive(Debug, PartialOrd, PartialEq, Copy, Clone, Eq, Hash)]
-pub struct Vector {
-    x: isize,
-    y: isize,
-}
-
-impl Vector {
-    pub fn new(x: isize, y: isize) -> Vector {
-        Vector {
-            x,
-            y,
-        }
-    }
-}
-
-impl std::ops::Sub for Vector {
-    type Output = Vector;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Vector::new(self.x - rhs.x, self.y - rhs.y)
-    }
-}
-
-impl std::ops::SubAssign for Vector {
-    fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-    }
-}
-
-impl std::ops::Add for Vector {
-    type Output = Vector;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Vector::new(self.x + rhs.x, self.y + rhs.y)
-    }
-}
-
-impl std::ops::AddAssign for Vector {
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-
 #[derive(Debug, PartialOrd, PartialEq, Clone)]
 pub struct PointWithVelocity {
-    position: Vector,
-    velocity: Vector,
+    position: Point,
+    velocity: Point,
 }
 
 impl PointWithVelocity {
-    pub fn new(position: Vector, velocity: Vector) -> PointWithVelocity {
+    pub fn new(position: Point, velocity: Point) -> PointWithVelocity {
         PointWithVelocity {
             position,
             velocity,
@@ -65,51 +24,50 @@ impl PointWithVelocity {
     }
 }
 
-pub fn parse_points_with_velocity(input: &Vec<String>) -> GenericResult<Vec<PointWithVelocity>> {
-    input.iter().map(|input| parse_point_with_velocity(input)).collect()
+pub fn parse_points_with_velocity(input: &Vec<String>) -> AocResult<Vec<PointWithVelocity>> {
+    input.iter().enumerate().map(|(idx, input)| parse_point_with_velocity(input, idx + 1)).collect()
 }
 
-fn parse_point_with_velocity(input: &str) -> GenericResult<PointWithVelocity> {
+fn parse_point_with_velocity(input: &str, line: usize) -> AocResult<PointWithVelocity> {
     lazy_static!{
         static ref regex: Regex = Regex::new(r"position=<(.*),(.*)> velocity=<(.*),(.*)>").unwrap();
     }
 
     regex
         .captures(input)
-        .ok_or(GenericError::new("Could not parse point with velocity.").into())
+        .ok_or(AocError::parse("Could not parse point with velocity."))
         .and_then(|captures| {
-            let x_pos = captures.get(1).ok_or(GenericError::new("Could not find x position.").into()).and_then(|m| parse_isize(m.as_str().trim()))?;
-            let y_pos = captures.get(2).ok_or(GenericError::new("Could not find x position.").into()).and_then(|m| parse_isize(m.as_str().trim()))?;
-            let x_velocity = captures.get(3).ok_or(GenericError::new("Could not find x position.").into()).and_then(|m| parse_isize(m.as_str().trim()))?;
-            let y_velocity = captures.get(4).ok_or(GenericError::new("Could not find x position.").into()).and_then(|m| parse_isize(m.as_str().trim()))?;
+            let x_pos = captures.get(1).ok_or(AocError::parse("Could not find x position.")).and_then(|m| parse_isize(input, m, line))?;
+            let y_pos = captures.get(2).ok_or(AocError::parse("Could not find x position.")).and_then(|m| parse_isize(input, m, line))?;
+            let x_velocity = captures.get(3).ok_or(AocError::parse("Could not find x position.")).and_then(|m| parse_isize(input, m, line))?;
+            let y_velocity = captures.get(4).ok_or(AocError::parse("Could not find x position.")).and_then(|m| parse_isize(input, m, line))?;
 
             Ok(PointWithVelocity::new(
-                Vector::new(x_pos, y_pos),
-                Vector::new(x_velocity, y_velocity),
+                Point(x_pos, y_pos),
+                Point(x_velocity, y_velocity),
             ))
         })
 }
 
-fn parse_isize(input: &str) -> GenericResult<isize> {
-    input.parse::<isize>().map_err(|e| e.into())
+fn parse_isize(input: &str, m: regex::Match, line: usize) -> AocResult<isize> {
+    let value = m.as_str();
+    let start = m.start() + value.len() - value.trim_start().len();
+
+    Ok(parse_at(signed(), value.trim(), line, input[..start].chars().count() + 1)?)
 }
 
 pub struct PointMap {
     points_with_velocity: Vec<PointWithVelocity>,
-    size: Vector,
+    size: Point,
 }
 
 impl PointMap {
     const EMPTY_CHAR: char = '.';
     const POINT_CHAR: char = '#';
-    const ORIGIN: Vector = Vector{ x: 0, y: 0};
 
     pub fn new(points_with_velocity: Vec<PointWithVelocity>) -> PointMap {
-        let points = points_with_velocity.iter().map(|p| p.position).collect();
-        let (min_x, max_x) = PointMap::find_min_max(&points, |p| p.x);
-        let (min_y, max_y) = PointMap::find_min_max(&points, |p| p.y);
-        let min_vector = Vector::new(min_x, min_y);
-        let size = Vector::new(max_x - min_x + 1, max_y - min_y + 1);
+        let (min_vector, max_vector) = PointMap::bounds(points_with_velocity.iter().map(|p| p.position));
+        let size = max_vector - min_vector + Point(1, 1);
 
         let normalized_points_with_velocity = points_with_velocity.into_iter().map(|points_with_velocity| {
             PointWithVelocity::new(
@@ -124,53 +82,45 @@ impl PointMap {
         }
     }
 
-    fn find_min_max(points_with_velocity: &Vec<Vector>, field_selector: fn(&Vector) -> isize) -> (isize, isize) {
-        let min = points_with_velocity.iter().map(field_selector).min().unwrap_or(0);
-        let max = points_with_velocity.iter().map(field_selector).max().unwrap_or(0);
-
-        (min, max)
+    fn bounds<I: IntoIterator<Item = Point>>(points: I) -> (Point, Point) {
+        Point::bounding_box(points).unwrap_or((Point::ORIGIN, Point::ORIGIN))
     }
 
     pub fn display(&self) {
-        let mut points: Vec<Vector> = self.points_iter().collect::<HashSet<Vector>>().into_iter().collect();
+        let mut points: Vec<Point> = self.points_iter().collect::<HashSet<Point>>().into_iter().collect();
 
         points.sort_by(|a, b| {
-            if a.y == b.y {
-                a.x.cmp(&b.x)
+            if a.1 == b.1 {
+                a.0.cmp(&b.0)
             } else {
-                a.y.cmp(&b.y)
+                a.1.cmp(&b.1)
             }
         });
 
-        dbg!(&points);
-
-        let (min_x, max_x) = PointMap::find_min_max(&points, |p| p.x);
-        let (min_y, max_y) = PointMap::find_min_max(&points, |p| p.y);
+        let (origin, max) = PointMap::bounds(points.iter().cloned());
+        let size = max - origin + Point(1, 1);
 
-        let size = dbg!(Vector::new(max_x - min_x + 1, max_y - min_y + 1));
-        let origin = dbg!(Vector::new(min_x, min_y));
-
-        let mut current_position = Vector::new(0, 0);
+        let mut current_position = Point(0, 0);
 
         for point in points.iter() {
             let normalized_point = *point - origin;
-            while current_position.y < normalized_point.y {
-                for _ in current_position.x..size.x {
+            while current_position.1 < normalized_point.1 {
+                for _ in current_position.0..size.0 {
                     print!("{}", PointMap::EMPTY_CHAR);
                 }
                 println!();
-                current_position.y += 1;
-                current_position.x = 0;
+                current_position.1 += 1;
+                current_position.0 = 0;
                 current_position;
             }
 
-            while current_position.x < normalized_point.x {
+            while current_position.0 < normalized_point.0 {
                 print!("{}", PointMap::EMPTY_CHAR);
-                current_position.x += 1;
+                current_position.0 += 1;
             }
 
             print!("{}", PointMap::POINT_CHAR);
-            current_position.x += 1;
+            current_position.0 += 1;
         }
     }
 
@@ -183,13 +133,13 @@ impl PointMap {
         self.points_with_velocity = self.points_with_velocity.iter()
             .filter(|point_with_velocity| {
                 let position = point_with_velocity.position;
-                PointMap::ORIGIN <= position && position < self.size
+                Point::ORIGIN <= position && position < self.size
             })
             .map(|p| p.clone())
             .collect();
     }
 
-    pub fn points_iter<'a>(&'a self) -> impl Iterator<Item = Vector> + 'a {
+    pub fn points_iter<'a>(&'a self) -> impl Iterator<Item = Point> + 'a {
         self.points_with_velocity.iter().map(|point_with_velocity| point_with_velocity.position)
     }
 
@@ -197,94 +147,141 @@ impl PointMap {
         self.points_with_velocity.len()
     }
 
-    pub fn size(&self) -> Vector {
+    pub fn size(&self) -> Point {
         self.size
     }
 }
 
+/// Moves the points until their bounding box stops shrinking. Returns the number of seconds
+/// this took together with the positions at that point in time.
+pub fn find_message(points_with_velocity: &[PointWithVelocity]) -> (usize, Vec<Point>) {
+    let mut positions: Vec<Point> = points_with_velocity.iter().map(|p| p.position).collect();
+    let mut area = bounding_area(&positions);
+    let mut seconds = 0;
+
+    loop {
+        let next_positions: Vec<Point> = positions
+            .iter()
+            .zip(points_with_velocity.iter())
+            .map(|(&position, point)| position + point.velocity)
+            .collect();
+        let next_area = bounding_area(&next_positions);
+
+        if next_area >= area {
+            return (seconds, positions);
+        }
+
+        positions = next_positions;
+        area = next_area;
+        seconds += 1;
+    }
+}
+
+fn bounding_area(points: &Vec<Point>) -> i128 {
+    let (min, max) = PointMap::bounds(points.iter().cloned());
+    let Point(width, height) = max - min + Point(1, 1);
+
+    width as i128 * height as i128
+}
+
+pub fn render_points(points: &Vec<Point>) -> String {
+    let (Point(min_x, min_y), Point(max_x, max_y)) = PointMap::bounds(points.iter().cloned());
+    let points: HashSet<&Point> = points.iter().collect();
+
+    (min_y..=max_y)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| {
+                    if points.contains(&Point(x, y)) {
+                        PointMap::POINT_CHAR
+                    } else {
+                        PointMap::EMPTY_CHAR
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub struct InitialComponents {
-    components: Vec<Vector>,
-    edges: HashMap<Vector, Vec<Vector>>,
-    size: Vector,
+    components: Vec<Point>,
+    size: Point,
 }
 
 pub struct ConnectedComponents {
-    connected_components: HashMap<usize, usize>,
+    union_find: UnionFind,
 }
 
 impl InitialComponents {
-    pub fn new(components: Vec<Vector>, size: Vector) -> InitialComponents {
-        let mut edges : HashMap<Vector, Vec<Vector>>= components.iter().cloned().map(|vector| (vector, vec![])).collect();
-
-        for point in components.iter() {
-            InitialComponents::check_edge_exists(&mut edges, point, Vector::new(point.x + 1, point.y));
-            InitialComponents::check_edge_exists(&mut edges, point, Vector::new(point.x - 1, point.y));
-            InitialComponents::check_edge_exists(&mut edges, point, Vector::new(point.x, point.y + 1));
-            InitialComponents::check_edge_exists(&mut edges, point, Vector::new(point.x, point.y - 1));
-        }
-
+    pub fn new(components: Vec<Point>, size: Point) -> InitialComponents {
         InitialComponents {
             components,
-            edges,
             size,
         }
     }
 
-    fn check_edge_exists(edges: &mut HashMap<Vector, Vec<Vector>>, point: &Vector, xp: Vector) -> () {
-        if edges.contains_key(&xp) {
-            edges.entry(*point).and_modify(|edges| edges.push(xp));
-        }
-    }
+    pub fn calculate_connected_components(self) -> ConnectedComponents {
+        let indices: HashMap<Point, usize> = self.components.iter().enumerate().map(|(index, &point)| (point, index)).collect();
+        let mut union_find = UnionFind::new(self.components.len());
 
-    pub fn calculate_connected_components(mut self) -> ConnectedComponents {
-        let mut queue = self.components.iter().cloned().collect::<VecDeque<Vector>>();
-        let mut map: HashMap<Vector, usize> = self.components.iter().enumerate().map(|(index, point)| (point.clone(), index)).collect();
+        for (index, point) in self.components.iter().enumerate() {
+            for neighbour in point.neighbours() {
+                if let Some(&neighbour_index) = indices.get(&neighbour) {
+                    union_find.union(index, neighbour_index);
+                }
+            }
+        }
 
-        while let Some(point) = queue.pop_front() {
-            let neighbours = self.edges.get(&point).unwrap();
+        ConnectedComponents::new(union_find)
+    }
+}
 
-            let min_neighbour = InitialComponents::find_min_neighbour(&map, neighbours);
+impl ConnectedComponents {
+    pub fn new(union_find: UnionFind) -> ConnectedComponents {
+        ConnectedComponents {
+            union_find,
+        }
+    }
 
-            if let Some(neighbour) = min_neighbour {
-                map.entry(point).and_modify(|value| {
-                    if neighbour < *value {
-                        *value = neighbour;
+    pub fn number_connected_components(&self) -> usize {
+        self.union_find.number_components()
+    }
+}
 
-                        for neighbour in neighbours.iter() {
-                            queue.push_back(neighbour.clone());
-                        }
-                    }
-                });
-            }
-        }
+pub struct Puzzle;
 
-        let mut connected_components : HashMap<usize, usize>= HashMap::new();
+impl Solution for Puzzle {
+    type Input = Vec<PointWithVelocity>;
 
-        for &connected_component in map.values() {
-            *connected_components.entry(connected_component).or_insert(0) += 1;
-        }
+    fn year(&self) -> u16 {
+        2018
+    }
 
-        ConnectedComponents::new(connected_components)
+    fn day(&self) -> u8 {
+        10
     }
 
-    fn find_min_neighbour(map: &HashMap<Vector, usize>, neighbours: &Vec<Vector>) -> Option<usize> {
-        neighbours
-            .iter()
-            .map(|neighbour| map.get(neighbour).cloned())
-            .flatten()
-            .min()
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<PointWithVelocity>> {
+        parse_points_with_velocity(
+            &lines
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .cloned()
+                .collect(),
+        )
     }
-}
 
-impl ConnectedComponents {
-    pub fn new(connected_components: HashMap<usize, usize>) -> ConnectedComponents {
-        ConnectedComponents {
-            connected_components,
-        }
+    fn part_one(&self, input: &Vec<PointWithVelocity>) -> AocResult<String> {
+        let (_, positions) = find_message(input);
+
+        ocr::recognize_points(positions)
     }
 
-    pub fn number_connected_components(&self) -> usize {
-        self.connected_components.len()
+    fn part_two(&self, input: &Vec<PointWithVelocity>) -> AocResult<String> {
+        let (seconds, _) = find_message(input);
+
+        Ok(seconds.to_string())
     }
 }
 
@@ -295,10 +292,28 @@ mod tests {
     #[test]
     fn test_parsing() {
         let expected_point_with_velocity = PointWithVelocity::new(
-            Vector::new(30432, -9912),
-            Vector::new(-3, 1),
+            Point(30432, -9912),
+            Point(-3, 1),
         );
 
-        assert_eq!(parse_point_with_velocity("position=< 30432,  -9912> velocity=<-3,  1>").unwrap(), expected_point_with_velocity)
+        assert_eq!(parse_point_with_velocity("position=< 30432,  -9912> velocity=<-3,  1>", 1).unwrap(), expected_point_with_velocity)
+    }
+
+    #[test]
+    fn test_find_message() {
+        let lines = aoc_common::read_raw_file_content("test_input.txt").unwrap();
+        let points_with_velocity = parse_points_with_velocity(&lines).unwrap();
+        let (seconds, positions) = find_message(&points_with_velocity);
+
+        assert_eq!(seconds, 3);
+        assert_eq!(render_points(&positions).lines().next(), Some("#...#..###"));
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let points = vec![Point(0, 0), Point(1, 0), Point(1, 1), Point(3, 1), Point(0, 2)];
+        let connected_components = InitialComponents::new(points, Point(4, 3)).calculate_connected_components();
+
+        assert_eq!(connected_components.number_connected_components(), 3);
     }
 }
@@ -1,7 +1,7 @@
-use aoc11::{FuelGrid, MaxPower};
+use aoc11::FuelGrid;
 
 fn main() {
     let grid = FuelGrid::new(300, 300, 9221);
-    let result = (1..=300).map(|size| grid.max_power_square(size, size)).max_by(|&MaxPower(_, _, _, a_power), &MaxPower(_, _, _, b_power)| a_power.cmp(&b_power)).unwrap();
+    let result = grid.max_power_any_square();
     println!("{:?}", result);
-}
\ No newline at end of file
+}
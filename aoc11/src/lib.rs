@@ -1,5 +1,9 @@
+use aoc_common::parse::{parse_at, signed};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
 pub struct FuelGrid {
-    grid: Vec<Vec<isize>>,
+    summed_area: Vec<Vec<isize>>,
     size: (usize, usize),
 }
 
@@ -21,8 +25,10 @@ impl FuelGrid {
             }
         }
 
+        let summed_area = FuelGrid::summed_area_table(&grid, width, height);
+
         FuelGrid {
-            grid,
+            summed_area,
             size: (width, height),
         }
     }
@@ -40,15 +46,12 @@ impl FuelGrid {
         let mut max_x = 0;
         let mut max_y = 0;
         let (width, height) = self.size;
+        let summed_area = &self.summed_area;
 
         for y in 0..(height - h + 1) {
             for x in 0..(width - w + 1) {
-                let mut power = 0;
-                for p_y in 0..h {
-                    for p_x in 0..w {
-                        power += self.grid[y + p_y][x + p_x];
-                    }
-                }
+                let power = summed_area[y + h][x + w] - summed_area[y][x + w] - summed_area[y + h][x]
+                    + summed_area[y][x];
 
                 if power > max_power {
                     max_power = power;
@@ -60,6 +63,66 @@ impl FuelGrid {
 
         MaxPower(max_x + 1, max_y + 1, Size(w, h), max_power)
     }
+
+    pub fn max_power_any_square(&self) -> MaxPower {
+        let (width, height) = self.size;
+
+        (1..=std::cmp::min(width, height))
+            .map(|size| self.max_power_square(size, size))
+            .max_by(|&MaxPower(_, _, _, a_power), &MaxPower(_, _, _, b_power)| a_power.cmp(&b_power))
+            .unwrap()
+    }
+
+    fn summed_area_table(grid: &[Vec<isize>], width: usize, height: usize) -> Vec<Vec<isize>> {
+        let mut summed_area = vec![vec![0; width + 1]; height + 1];
+
+        for y in 0..height {
+            for x in 0..width {
+                summed_area[y + 1][x + 1] =
+                    grid[y][x] + summed_area[y][x + 1] + summed_area[y + 1][x] - summed_area[y][x];
+            }
+        }
+
+        summed_area
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = FuelGrid;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<FuelGrid> {
+        let (idx, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty())
+            .ok_or_else(|| AocError::parse("Missing grid serial number."))?;
+        let column = line.len() - line.trim_start().len() + 1;
+        let serial_number = parse_at(signed(), line.trim(), idx + 1, column)?;
+
+        Ok(FuelGrid::new(300, 300, serial_number))
+    }
+
+    fn part_one(&self, input: &FuelGrid) -> AocResult<String> {
+        let MaxPower(x, y, _, _) = input.max_power_square(3, 3);
+
+        Ok(format!("{},{}", x, y))
+    }
+
+    fn part_two(&self, input: &FuelGrid) -> AocResult<String> {
+        let MaxPower(x, y, Size(size, _), _) = input.max_power_any_square();
+
+        Ok(format!("{},{},{}", x, y, size))
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +149,10 @@ mod tests {
         let grid = FuelGrid::new(300, 300, 18);
         assert_eq!(grid.max_power_square(3, 3), MaxPower(33, 45, Size(3, 3), 29));
     }
+
+    #[test]
+    fn test_max_power_any_square() {
+        let grid = FuelGrid::new(300, 300, 18);
+        assert_eq!(grid.max_power_any_square(), MaxPower(90, 269, Size(16, 16), 113));
+    }
 }
@@ -1,7 +1,12 @@
-use aoc_common::{GenericResult, GenericError, read_raw_file_content};
-use aoc_common::collections::RadixTree;
-use bit_vec::BitVec;
+use aoc_common::cycle;
+use aoc_common::solution::Solution;
+use aoc_common::{AocResult, AocError, read_raw_file_content};
+use aoc_collections::{BitSet, GrowableBitSet};
 use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// The pot and its two neighbours on either side.
+type Condition = BitSet<5>;
 
 #[derive(PartialEq, Debug, Clone)]
 enum GrowingResult {
@@ -10,20 +15,21 @@ enum GrowingResult {
 }
 
 #[derive(PartialEq, Debug, Clone)]
-pub struct GrowingRule(BitVec, GrowingResult);
+pub struct GrowingRule(Condition, GrowingResult);
 
 impl GrowingRule {
     const GROWING_SYMBOL: char = '#';
 
-    pub fn parse(rule: &str) -> GenericResult<GrowingRule> {
+    pub fn parse(rule: &str) -> AocResult<GrowingRule> {
         let splits: Vec<&str> = rule.split(" => ").collect();
 
         if splits.len() == 2 {
-            let condition = convert_to_bit_vec(splits[0]);
             let conclusion = splits[1];
 
-            if conclusion.len() != 1 {
-                Err(GenericError::new(&format!("Conclusion {} could not be parsed.", conclusion)).into())
+            if splits[0].len() != 5 {
+                Err(AocError::parse(&format!("Condition {} does not span 5 pots.", splits[0])))
+            } else if conclusion.len() != 1 {
+                Err(AocError::parse(&format!("Conclusion {} could not be parsed.", conclusion)))
             } else {
                 let growing_result = if conclusion == &GrowingRule::GROWING_SYMBOL.to_string() {
                     GrowingResult::Growing
@@ -31,50 +37,48 @@ impl GrowingRule {
                     GrowingResult::NotGrowing
                 };
 
-                Ok(GrowingRule(condition, growing_result))
+                Ok(GrowingRule(convert_to_bit_set(splits[0]), growing_result))
             }
         } else {
-            Err(GenericError::new(&format!("Input {} cannot be parsed into GrowingRule.", rule)).into())
+            Err(AocError::parse(&format!("Input {} cannot be parsed into GrowingRule.", rule)))
         }
     }
 }
 
-fn convert_to_bit_vec(condition: &str) -> BitVec {
-    let mut result = BitVec::from_elem(condition.len(), false);
-
-    for (index, chr) in condition.chars().enumerate() {
-        if chr == GrowingRule::GROWING_SYMBOL {
-            result.set(index, true);
-        }
-    }
-
-    result
+fn convert_to_bit_set<S: FromIterator<usize>>(pots: &str) -> S {
+    pots.chars()
+        .enumerate()
+        .filter(|&(_, chr)| chr == GrowingRule::GROWING_SYMBOL)
+        .map(|(index, _)| index)
+        .collect()
 }
 
+#[derive(Clone)]
 pub struct GrowingRules {
-    growing_rules: HashSet<BitVec>,
+    growing_rules: HashSet<Condition>,
 }
 
 impl GrowingRules {
     pub fn new(growing_rules: &Vec<GrowingRule>) -> GrowingRules {
         GrowingRules{
-            growing_rules: growing_rules.iter().filter(|&GrowingRule(_, growing_result)| *growing_result == GrowingResult::Growing).map(|GrowingRule(condition, _)| condition).cloned().collect()
+            growing_rules: growing_rules.iter().filter(|&GrowingRule(_, growing_result)| *growing_result == GrowingResult::Growing).map(|&GrowingRule(condition, _)| condition).collect()
         }
     }
 
-    pub fn grows(&self, condition: BitVec) -> bool {
+    pub fn grows(&self, condition: Condition) -> bool {
         self.growing_rules.contains(&condition)
     }
 }
 
+#[derive(Clone)]
 pub struct PlantPots {
-    configuration: BitVec,
+    configuration: GrowableBitSet,
     offset: isize,
     growing_rules: GrowingRules,
 }
 
 impl PlantPots {
-    pub fn new(initial_configuration: BitVec, growing_rules: &Vec<GrowingRule>) -> PlantPots {
+    pub fn new(initial_configuration: GrowableBitSet, growing_rules: &Vec<GrowingRule>) -> PlantPots {
         PlantPots {
             configuration: initial_configuration,
             offset: 0,
@@ -83,91 +87,29 @@ impl PlantPots {
     }
 
     pub fn advance(&mut self) {
-        let left_border = self.grow_left_border();
-        let right_border = self.grow_right_border();
-
-        let length = self.configuration.len();
-        let mut new_length = length;
+        let length = self.configuration.last().map_or(0, |last| last as isize + 1);
 
-        if left_border {
-            new_length += 1;
-            self.offset += 1;
-        }
+        let new_configuration: GrowableBitSet = (-2..length + 2)
+            .filter(|&index| self.growing_rules.grows(self.configuration.window(index - 2)))
+            .map(|index| (index + 2) as usize)
+            .collect();
 
-        if right_border {
-            new_length += 1;
-        }
-
-        let mut new_configuration = BitVec::from_elem(new_length, false);
-
-        let offset = if left_border {
-            new_configuration.set(0, true);
-            1
-        } else {
-            0
-        };
-
-        if right_border {
-            new_configuration.set(new_length - 1, true);
-        }
-
-        for (index, _) in (0..(length)).map(|index| (index, self.growing_rules.grows(PlantPots::extract_slice(&self.configuration, index as isize - 2, 5)))).filter(|&(_, grows)| grows == true) {
-            new_configuration.set(index + offset, true);
-        }
+        let zeros = new_configuration.first().unwrap_or(0);
 
-        let zeros = PlantPots::count_starting_zeros(&new_configuration);
-
-        if zeros >= 1 {
-            let mut z_configuration = BitVec::from_elem(new_configuration.len() - zeros, false);
-
-            for (index, value) in new_configuration.iter().skip(zeros).enumerate() {
-                z_configuration.set(index, value);
-            }
-
-            self.configuration = z_configuration;
-            self.offset -= zeros as isize;
-        } else {
-            self.configuration = new_configuration;
-        }
-    }
-
-    fn count_starting_zeros(configuration: &BitVec) -> usize {
-        configuration.iter().take_while(|&value| !value).count()
-    }
-
-    fn grow_left_border(&self) -> bool {
-        let next = PlantPots::extract_slice(&self.configuration, - 3, 5);
-        self.growing_rules.grows(next)
+        self.configuration = new_configuration.iter().map(|index| index - zeros).collect();
+        self.offset += 2 - zeros as isize;
     }
 
-    fn grow_right_border(&self) -> bool {
-        let next = PlantPots::extract_slice(&self.configuration, self.configuration.len() as isize - 2, 5);
-        self.growing_rules.grows(next)
-    }
-
-    fn extract_slice(configuration: &BitVec, start: isize, length: usize) -> BitVec {
-        let mut result = BitVec::from_elem(length, false);
-
-        let start_index = std::cmp::max(0, - start) as usize; // start_index + start >= 0
-        let end_index = std::cmp::min(5, configuration.len() as isize - start) as usize; // end_index + start <= configuration.len()
-
-        for index in start_index..end_index {
-            result.set(index, configuration.get((index as isize + start) as usize).unwrap());
-        }
-
-        result
-    }
-
-    pub fn get_configuration(&self) -> &BitVec {
+    pub fn get_configuration(&self) -> &GrowableBitSet {
         &self.configuration
     }
 
     pub fn count_plants(&self) -> usize {
-        self.configuration.iter().filter(|&plant| plant).count()
+        self.configuration.len()
     }
 
     pub fn sum_plant_containing_pots(&self) -> isize {
-        self.configuration.iter().enumerate().filter(|&(_, chr)| chr).map(|(index, _)| index as isize - self.offset as isize).sum::<isize>()
+        self.configuration.iter().map(|index| index as isize - self.offset).sum::<isize>()
     }
 
     pub fn get_growing_rules(&self) -> &GrowingRules {
@@ -179,48 +121,85 @@ impl PlantPots {
     }
 }
 
-pub fn parse_plant_pots_file(path: &str) -> GenericResult<PlantPots> {
+pub fn parse_plant_pots_file(path: &str) -> AocResult<PlantPots> {
     let file_content = read_raw_file_content(path)?;
 
-    let initial_configuration_line = file_content.get(0).ok_or(GenericError::new("File was empty."))?;
+    parse_plant_pots(&file_content)
+}
+
+pub fn parse_plant_pots(file_content: &[String]) -> AocResult<PlantPots> {
+    let initial_configuration_line = file_content.get(0).ok_or(AocError::invalid_input("File was empty."))?;
 
     if initial_configuration_line.find("initial state: ").is_none() {
-        Err(GenericError::new("File does not start with initial state line.").into())
+        Err(AocError::invalid_input("File does not start with initial state line."))
     } else {
         let initial_configuration = &initial_configuration_line[15..];
 
-        let mut configuration = BitVec::from_elem(initial_configuration.len(), false);
-        for (index, chr) in initial_configuration.chars().enumerate() {
-            if chr == GrowingRule::GROWING_SYMBOL {
-                configuration.set(index, true);
-            }
-        }
+        let configuration = convert_to_bit_set(initial_configuration);
 
-        let growing_rules: Vec<GrowingRule> = file_content.iter().skip(2).map(|line| GrowingRule::parse(line)).collect::<GenericResult<Vec<GrowingRule>>>()?;
+        let growing_rules: Vec<GrowingRule> = file_content.iter().skip(2).filter(|line| !line.trim().is_empty()).map(|line| GrowingRule::parse(line)).collect::<AocResult<Vec<GrowingRule>>>()?;
 
         Ok(PlantPots::new(configuration, &growing_rules))
     }
 }
 
+/// Sum of the plant containing pots after `generations`. Once the pattern of plants repeats it
+/// only drifts along the pots, so the sum is extrapolated from there.
+pub fn sum_after_generations(plant_pots: &PlantPots, generations: usize) -> isize {
+    cycle::extrapolate(
+        plant_pots.clone(),
+        generations,
+        PlantPots::advance,
+        |plant_pots| plant_pots.get_configuration().clone(),
+        |plant_pots| plant_pots.sum_plant_containing_pots() as i64,
+    ) as isize
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = PlantPots;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<PlantPots> {
+        parse_plant_pots(lines)
+    }
+
+    fn part_one(&self, input: &PlantPots) -> AocResult<String> {
+        Ok(sum_after_generations(input, 20).to_string())
+    }
+
+    fn part_two(&self, input: &PlantPots) -> AocResult<String> {
+        Ok(sum_after_generations(input, 50_000_000_000).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_parse_growing_rule() {
-        assert_eq!(GrowingRule::parse(".#... => #").unwrap(), GrowingRule(convert_to_bit_vec(".#..."), GrowingResult::Growing))
+        assert_eq!(GrowingRule::parse(".#... => #").unwrap(), GrowingRule(convert_to_bit_set(".#..."), GrowingResult::Growing))
     }
 
     #[test]
     fn test_parse_plant_pots_file() {
         let plant_pots = parse_plant_pots_file("test_input.txt").unwrap();
 
-        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_vec("#..#.#..##......###...###"));
+        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_set("#..#.#..##......###...###"));
 
         let growing_rules = plant_pots.get_growing_rules();
 
-        assert_eq!(growing_rules.grows(convert_to_bit_vec(".#.##")), true);
-        assert_eq!(growing_rules.grows(convert_to_bit_vec("#....")), false);
+        assert_eq!(growing_rules.grows(convert_to_bit_set(".#.##")), true);
+        assert_eq!(growing_rules.grows(convert_to_bit_set("#....")), false);
     }
 
     #[test]
@@ -228,9 +207,17 @@ mod tests {
         let mut plant_pots = parse_plant_pots_file("test_input.txt").unwrap();
 
         plant_pots.advance();
-        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_vec("#...#....#.....#..#..#..#"));
+        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_set("#...#....#.....#..#..#..#"));
 
         plant_pots.advance();
-        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_vec("##..##...##....#..#..#..##"));
+        assert_eq!(plant_pots.get_configuration(), &convert_to_bit_set("##..##...##....#..#..#..##"));
+    }
+
+    #[test]
+    fn test_sum_after_generations() {
+        let plant_pots = parse_plant_pots_file("test_input.txt").unwrap();
+
+        assert_eq!(sum_after_generations(&plant_pots, 20), 325);
+        assert_eq!(sum_after_generations(&plant_pots, 50_000_000_000), 999_999_999_374);
     }
 }
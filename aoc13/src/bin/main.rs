@@ -1,30 +1,8 @@
-use aoc13::{read_street_map, StreetMap};
+use aoc13::read_street_map;
 
 fn main() {
-    let mut street_map = read_street_map("input.txt").unwrap();
+    let street_map = read_street_map("input.txt").unwrap();
 
-//    solve_part_one(&mut street_map)
-    solve_part_two(&mut street_map);
-
-}
-
-fn solve_part_one(street_map: &mut StreetMap) -> () {
-    while street_map.check_collision().is_empty() {
-        street_map.advance(false);
-        println!("{}", street_map);
-    }
-
-    for point in street_map.check_collision().iter() {
-        println!("{}", point);
-    }
+    println!("First collision: {:?}", street_map.clone().find_first_collision());
+    println!("Last car: {:?}", street_map.clone().find_last_car());
 }
-
-fn solve_part_two(street_map: &mut StreetMap) -> () {
-    while street_map.get_cars().len() > 1 {
-        street_map.advance(true);
-    }
-
-    for car in street_map.get_cars().iter() {
-        println!("{}", car.get_position());
-    }
-}
\ No newline at end of file
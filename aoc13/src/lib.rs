@@ -3,8 +3,9 @@ use core::fmt::Write;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
-use aoc_common::{GenericResult, read_raw_file_content, GenericError};
+use aoc_common::{AocResult, read_raw_file_content, AocError};
 use aoc_common::math::Point;
+use aoc_common::solution::Solution;
 use std::collections::binary_heap::BinaryHeap;
 use std::collections::{HashMap, HashSet};
 
@@ -72,6 +73,7 @@ impl Display for Car {
     }
 }
 
+#[derive(Clone)]
 enum StreetElement {
     HorizontalRoad,
     VerticalRoad,
@@ -92,7 +94,7 @@ impl StreetElement {
         }
     }
 
-    fn parse_street_element(element: char) -> GenericResult<StreetElement> {
+    fn parse_street_element(element: char) -> AocResult<StreetElement> {
         match element {
             ' ' => Ok(StreetElement::Empty),
             '+' => Ok(StreetElement::Crossing),
@@ -100,7 +102,7 @@ impl StreetElement {
             '|' => Ok(StreetElement::VerticalRoad),
             '/' => Ok(StreetElement::LeftTurn),
             '\\' => Ok(StreetElement::RightTurn),
-            _ => Err(GenericError::new(&format!("Unknown street element {}", element)).into())
+            _ => Err(AocError::parse(&format!("Unknown street element {}", element)))
         }
     }
 }
@@ -118,6 +120,7 @@ impl Display for StreetElement {
     }
 }
 
+#[derive(Clone)]
 pub struct StreetMap {
     map: Vec<Vec<StreetElement>>,
     cars: Vec<Car>,
@@ -222,6 +225,26 @@ impl StreetMap {
     pub fn get_cars(&self) -> &Vec<Car> {
         &self.cars
     }
+
+    pub fn find_first_collision(&mut self) -> Option<Point> {
+        while self.check_collision().is_empty() {
+            if self.cars.is_empty() {
+                return None;
+            }
+
+            self.advance(false);
+        }
+
+        self.check_collision().into_iter().next()
+    }
+
+    pub fn find_last_car(&mut self) -> Option<Point> {
+        while self.cars.len() > 1 {
+            self.advance(true);
+        }
+
+        self.cars.first().map(|car| car.position)
+    }
 }
 
 impl Display for StreetMap {
@@ -241,8 +264,11 @@ impl Display for StreetMap {
     }
 }
 
-pub fn read_street_map(path: &str) -> GenericResult<StreetMap> {
-    let content = read_raw_file_content(path)?;
+pub fn read_street_map(path: &str) -> AocResult<StreetMap> {
+    parse_street_map(&read_raw_file_content(path)?)
+}
+
+pub fn parse_street_map(content: &[String]) -> AocResult<StreetMap> {
     let mut map = Vec::with_capacity(content.len());
     let mut cars = Vec::with_capacity(10);
 
@@ -259,7 +285,7 @@ pub fn read_street_map(path: &str) -> GenericResult<StreetMap> {
 
 struct PartialCar(isize, Direction);
 
-fn parse_map_row(row: &str) -> GenericResult<(Vec<StreetElement>, Vec<PartialCar>)> {
+fn parse_map_row(row: &str) -> AocResult<(Vec<StreetElement>, Vec<PartialCar>)> {
     let mut street_elements = Vec::with_capacity(row.len());
     let mut partial_cars = Vec::with_capacity(1);
 
@@ -272,7 +298,7 @@ fn parse_map_row(row: &str) -> GenericResult<(Vec<StreetElement>, Vec<PartialCar
     Ok((street_elements, partial_cars))
 }
 
-fn parse_street_element(element: char) -> GenericResult<(StreetElement, Option<Direction>)> {
+fn parse_street_element(element: char) -> AocResult<(StreetElement, Option<Direction>)> {
     let (element, optional_direction) = match element {
         '>' => ('-', Some(Point(1, 0))),
         '<' => ('-', Some(Point(-1, 0))),
@@ -284,6 +310,42 @@ fn parse_street_element(element: char) -> GenericResult<(StreetElement, Option<D
     StreetElement::parse_street_element(element).map(|street_element| (street_element, optional_direction))
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = StreetMap;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<StreetMap> {
+        parse_street_map(lines)
+    }
+
+    fn part_one(&self, input: &StreetMap) -> AocResult<String> {
+        let Point(x, y) = input
+            .clone()
+            .find_first_collision()
+            .ok_or_else(|| AocError::no_solution("The cars never collide."))?;
+
+        Ok(format!("{},{}", x, y))
+    }
+
+    fn part_two(&self, input: &StreetMap) -> AocResult<String> {
+        let Point(x, y) = input
+            .clone()
+            .find_last_car()
+            .ok_or_else(|| AocError::no_solution("No car is left."))?;
+
+        Ok(format!("{},{}", x, y))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -1,6 +1,9 @@
 use std::collections::vec_deque::VecDeque;
 use std::collections::HashSet;
 
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
 pub struct Recipes {
     recipes: Vec<usize>,
     counter: usize,
@@ -73,7 +76,7 @@ impl Recipes {
             }
         }
 
-        let Candidate(index, length) = dbg!(candidates)
+        let Candidate(index, length) = candidates
             .into_iter()
             .filter(|Candidate(index, length)| *length == sequence.len())
             .min_by(|Candidate(a, _), Candidate(b, _)| a.cmp(b))
@@ -86,6 +89,54 @@ impl Recipes {
 #[derive(Hash, Eq, PartialEq, Debug)]
 struct Candidate(usize, usize);
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<String> {
+        lines
+            .iter()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .filter(|line| line.chars().all(|chr| chr.is_ascii_digit()))
+            .map(String::from)
+            .ok_or_else(|| AocError::parse("Expected the puzzle input to be a number."))
+    }
+
+    fn part_one(&self, input: &String) -> AocResult<String> {
+        let warmup_count = input.parse::<usize>().map_err(|_| {
+            AocError::invalid_input(&format!("Recipe count {} is too large.", input))
+        })?;
+        let mut recipes = Recipes::new();
+
+        Ok(recipes
+            .find_recipes_after(warmup_count, 10)
+            .iter()
+            .map(|digit| digit.to_string())
+            .collect())
+    }
+
+    fn part_two(&self, input: &String) -> AocResult<String> {
+        let sequence: Vec<usize> = input
+            .chars()
+            .filter_map(|chr| chr.to_digit(10))
+            .map(|digit| digit as usize)
+            .collect();
+        let mut recipes = Recipes::new();
+
+        Ok(recipes.number_recipes_until_sequence(&sequence).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::fs;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -75,6 +77,50 @@ pub fn calculate_distance(a: &str, b: &str) -> usize {
     result
 }
 
+pub fn common_letters(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .filter(|(a_chr, b_chr)| a_chr == b_chr)
+        .map(|(chr, _)| chr)
+        .collect()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .map(String::from)
+            .collect())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        let (two, three) = calculate_checksum(input);
+
+        Ok((two * three).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        find_identical_ids(input.iter().map(AsRef::as_ref).collect())
+            .into_iter()
+            .next()
+            .map(|(a, b)| common_letters(&a, &b))
+            .ok_or_else(|| AocError::no_solution("No pair of ids differs in exactly one letter."))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,15 +3,6 @@ use aoc3::*;
 fn main() {
     let proposals: Vec<Proposal> = read_file("input.txt").iter().map(|line| parse_proposal(line).unwrap()).collect();
 
-    let mut fabric = Fabric::new();
-
-    for x in proposals.iter() {
-        fabric.add(x);
-    }
-
-    println!("{}", fabric.count());
-
-    let result: Vec<Proposal> = proposals.into_iter().filter(|proposal| fabric.check(proposal)).collect();
-
-    println!("{:?}", result)
-}
\ No newline at end of file
+    println!("{}", count_overlapping_square_inches(&proposals));
+    println!("{:?}", find_intact_proposal(&proposals))
+}
@@ -1,8 +1,12 @@
 #[macro_use]
 extern crate lazy_static;
 
+use aoc_collections::interval_set::area_covered_at_least;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::fs;
 use regex::Regex;
+use std::ops::Range;
 use std::str::FromStr;
 use std::io::BufReader;
 use std::io::BufRead;
@@ -25,8 +29,19 @@ impl Rectangle {
         }
     }
 
-    fn size(&self) -> usize {
-        (self.width * self.height) as usize
+    fn x_range(&self) -> Range<u32> {
+        self.x..self.x + self.width
+    }
+
+    fn y_range(&self) -> Range<u32> {
+        self.y..self.y + self.height
+    }
+
+    fn overlaps(&self, other: &Rectangle) -> bool {
+        let (x, other_x) = (self.x_range(), other.x_range());
+        let (y, other_y) = (self.y_range(), other.y_range());
+
+        x.start < other_x.end && other_x.start < x.end && y.start < other_y.end && other_y.start < y.end
     }
 }
 
@@ -74,124 +89,54 @@ pub fn parse_proposal(line: &str) -> Result<Proposal, String> {
     }
 }
 
-pub struct Fabric {
-    map: [[u32; 1000]; 1000]
-}
-
-impl Fabric {
-    pub fn new() -> Fabric {
-        Fabric {
-            map: [[0; 1000]; 1000],
-        }
-    }
-
-    fn iter<'a>(&'a self, rectangle: &'a Rectangle) -> FabricIter<'a> {
-        FabricIter::new(self, rectangle)
-    }
-
-    fn iter_mut<'a>(&'a mut self, rectangle: &'a Rectangle) -> FabricIterMut<'a> {
-        FabricIterMut::new(self, rectangle)
-    }
-
-    pub fn add(&mut self, proposal: &Proposal) {
-        self.iter_mut(&proposal.rectangle).for_each(|v| *v += 1);
-    }
-
-    pub fn print(&self) {
-        let rectangle = Rectangle::new(0, 0, 1000, 1000);
-        let a = self.iter(&rectangle);
-
-        for (index, element) in a.enumerate() {
-            print!("{};", element);
-
-            if index % 1000 == 999 {
-                print!("\n");
-            }
-        }
-    }
-
-    pub fn count(&self) -> usize {
-        self.iter(&Rectangle::new(0, 0, 1000, 1000)).filter(|&v| v > 1).count()
-    }
-
-    pub fn check(&self, proposal: &Proposal) -> bool {
-        self.iter(&proposal.rectangle).all(|v| v == 1)
-    }
-}
+/// Number of square inches which are claimed by at least two proposals.
+pub fn count_overlapping_square_inches(proposals: &[Proposal]) -> u32 {
+    let rectangles: Vec<(Range<u32>, Range<u32>)> = proposals
+        .iter()
+        .map(|proposal| (proposal.rectangle.x_range(), proposal.rectangle.y_range()))
+        .collect();
 
-struct FabricIter<'a> {
-    fabric : &'a Fabric,
-    rectangle: &'a Rectangle,
-    counter: usize,
+    area_covered_at_least(&rectangles, 2)
 }
 
-impl<'a> FabricIter<'a> {
-    fn new(fabric: &'a Fabric, rectangle: &'a Rectangle) -> FabricIter<'a> {
-        FabricIter {
-            fabric,
-            rectangle,
-            counter: 0
-        }
-    }
+pub fn find_intact_proposal(proposals: &[Proposal]) -> Option<&Proposal> {
+    proposals.iter().find(|proposal| {
+        proposals.iter().all(|other| {
+            other.id == proposal.id || !proposal.rectangle.overlaps(&other.rectangle)
+        })
+    })
 }
 
-impl<'a> Iterator for FabricIter<'a> {
-    type Item = u32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.counter >= self.rectangle.size() {
-            None
-        } else {
-            let width = self.counter % self.rectangle.width as usize;
-            let height = self.counter / self.rectangle.width as usize;
+pub struct Puzzle;
 
-            self.counter += 1;
+impl Solution for Puzzle {
+    type Input = Vec<Proposal>;
 
-            let x = width + self.rectangle.x as usize;
-            let y = height + self.rectangle.y as usize;
-
-            Some(self.fabric.map[y][x])
-        }
+    fn year(&self) -> u16 {
+        2018
     }
-}
-
-struct FabricIterMut<'a> {
-    fabric : &'a mut Fabric,
-    rectangle: &'a Rectangle,
-    counter: usize,
-}
 
-impl<'a> FabricIterMut<'a> {
-    fn new(fabric: &'a mut Fabric, rectangle: &'a Rectangle) -> FabricIterMut<'a> {
-        FabricIterMut {
-            fabric,
-            rectangle,
-            counter : 0,
-        }
+    fn day(&self) -> u8 {
+        3
     }
-}
-
-impl<'a> Iterator for FabricIterMut<'a> {
-    type Item = &'a mut u32;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.counter >= self.rectangle.size() {
-            None
-        } else {
-            let width = self.counter % self.rectangle.width as usize;
-            let height = self.counter / self.rectangle.width as usize;
-
-            self.counter += 1;
-
-            let x = width + self.rectangle.x as usize;
-            let y = height + self.rectangle.y as usize;
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Proposal>> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| parse_proposal(line).map_err(|err| AocError::parse_at(idx + 1, 1, &err)))
+            .collect()
+    }
 
-            let result = &mut self.fabric.map[y][x] as *mut u32;
+    fn part_one(&self, input: &Vec<Proposal>) -> AocResult<String> {
+        Ok(count_overlapping_square_inches(input).to_string())
+    }
 
-            unsafe {
-                Some(&mut *result)
-            }
-        }
+    fn part_two(&self, input: &Vec<Proposal>) -> AocResult<String> {
+        find_intact_proposal(input)
+            .map(|proposal| proposal.id.to_string())
+            .ok_or_else(|| AocError::no_solution("Every proposal overlaps with another one."))
     }
 }
 
@@ -204,6 +149,17 @@ mod tests {
         assert_eq!(parse_proposal("#24 @ 61,509: 10x17").unwrap(), Proposal::new(24, Rectangle::new(61, 509, 10, 17)))
     }
 
+    #[test]
+    fn test_overlapping_claims() {
+        let proposals: Vec<Proposal> = vec!["#1 @ 1,3: 4x4", "#2 @ 3,1: 4x4", "#3 @ 5,5: 2x2"]
+            .into_iter()
+            .map(|line| parse_proposal(line).unwrap())
+            .collect();
+
+        assert_eq!(count_overlapping_square_inches(&proposals), 4);
+        assert_eq!(find_intact_proposal(&proposals).map(|proposal| proposal.id), Some(3));
+    }
+
     #[test]
     #[should_panic]
     fn test_failing_parse_proposal() {
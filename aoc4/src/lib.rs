@@ -1,7 +1,6 @@
 #[macro_use]
 extern crate lazy_static;
 
-use std::error;
 use std::fmt;
 use std::fmt::Formatter;
 use std::fs::File;
@@ -11,6 +10,9 @@ use std::io::BufReader;
 use std::num::ParseIntError;
 use std::str::FromStr;
 
+use aoc_common::parse::{parse_at, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use chrono::prelude::*;
 use regex::Captures;
 use regex::Match;
@@ -22,29 +24,6 @@ use std::fmt::Error;
 use std::collections::hash_map::Values;
 
 
-pub type ParseResult<T> = Result<T, Box<error::Error>>;
-
-#[derive(Debug)]
-struct ParseError {
-    error_message: String,
-}
-
-impl ParseError {
-    fn new(message: &str) -> ParseError {
-        ParseError {
-            error_message: String::from(message),
-        }
-    }
-}
-
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "Parsing error: {}", self.error_message)
-    }
-}
-
-impl error::Error for ParseError {}
-
 #[derive(Debug, PartialEq)]
 pub struct LogEntry {
     date_time: DateTime<Utc>,
@@ -59,7 +38,7 @@ impl LogEntry {
         }
     }
 
-    pub fn parse_from(input: &str) -> ParseResult<LogEntry> {
+    pub fn parse_from(input: &str) -> AocResult<LogEntry> {
         lazy_static! {
             static ref LOG_REGEX: Regex = Regex::new(r"\[(\d+)-(\d+)-(\d+) (\d+):(\d+)\] (.*)").unwrap();
         }
@@ -72,24 +51,29 @@ impl LogEntry {
             let minutes = LogEntry::parse_capture(&captures, 5)?;
 
             let action_str = LogEntry::get_capture(&captures, 6)?;
+            let action_column = captures.get(6).map_or(1, |m| m.start() + 1);
+            let action = Action::parse_from(&action_str).map_err(|err| err.offset_by(1, action_column))?;
 
-            Ok(LogEntry::new(Utc.ymd(year, month, day).and_hms(hour, minutes, 0), Action::parse_from(&action_str)?))
+            Ok(LogEntry::new(Utc.ymd(year, month, day).and_hms(hour, minutes, 0), action))
         } else {
-            Err(ParseError::new(&format!("Could not parse input: {}", input)).into())
+            Err(AocError::parse(&format!("Could not parse input: {}", input)))
         }
     }
 
-    fn get_capture(captures: &Captures, index: usize) -> ParseResult<String> {
+    fn get_capture(captures: &Captures, index: usize) -> AocResult<String> {
         captures
             .get(index)
             .map(|m| m.as_str().to_string())
-            .ok_or(ParseError::new(&format!("Could not find capture group {}.", index)).into())
+            .ok_or(AocError::parse(&format!("Could not find capture group {}.", index)))
     }
 
 
-    fn parse_capture(captures: &Captures, index: usize) -> ParseResult<u32> {
-        LogEntry::get_capture(captures, index)
-            .and_then(|m| m.parse::<u32>().map_err(|e| e.into()))
+    fn parse_capture(captures: &Captures, index: usize) -> AocResult<u32> {
+        let m = captures
+            .get(index)
+            .ok_or(AocError::parse(&format!("Could not find capture group {}.", index)))?;
+
+        Ok(parse_at(unsigned(), m.as_str(), 1, m.start() + 1)?)
     }
 }
 
@@ -104,7 +88,8 @@ static FALLS_ASLEEP: &str = "falls asleep";
 static WAKES_UP: &str = "wakes up";
 
 impl Action {
-    pub fn parse_from(input: &str) -> ParseResult<Action> {
+    pub fn parse_from(input: &str) -> AocResult<Action> {
+        let leading_whitespace = input.len() - input.trim_start().len();
         let input = input.trim().to_lowercase();
 
         lazy_static! {
@@ -118,14 +103,14 @@ impl Action {
         } else if let Some(captures) = GUARD_REGEX.captures(&input) {
             match captures.get(1) {
                 Some(m) => {
-                    let guard_number = u32::from_str(m.as_str())?;
+                    let guard_number = parse_at(unsigned(), m.as_str(), 1, leading_whitespace + m.start() + 1)?;
                     Ok(Action::Guard(guard_number))
                 },
-                None => Err(ParseError::new("Guard with no number.").into())
+                None => Err(AocError::parse("Guard with no number."))
             }
 
         } else {
-            Err(ParseError::new(&format!("Could not parse input: {}", input)).into())
+            Err(AocError::parse(&format!("Could not parse input: {}", input)))
         }
     }
 }
@@ -143,10 +128,14 @@ pub fn read_file(path: &str) -> io::Result<Vec<String>> {
     Ok(result)
 }
 
-pub fn read_log(path: &str) -> ParseResult<Vec<LogEntry>> {
+pub fn read_log(path: &str) -> AocResult<Vec<LogEntry>> {
     let file_content = read_file(path)?;
 
-    let mut result = file_content.iter().map(|line| LogEntry::parse_from(line)).collect::<ParseResult<Vec<LogEntry>>>()?;
+    let mut result = file_content
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| LogEntry::parse_from(line).map_err(|err| err.offset_by(idx + 1, 1).in_file(path)))
+        .collect::<AocResult<Vec<LogEntry>>>()?;
 
     result.sort_by(|a, b| a.date_time.cmp(&b.date_time));
 
@@ -287,6 +276,53 @@ pub fn strategy_2(guard_overview: &GuardOverview) -> &Guard {
     }).unwrap()
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = GuardOverview;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<GuardOverview> {
+        let mut log = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| LogEntry::parse_from(line).map_err(|err| err.offset_by(idx + 1, 1)))
+            .collect::<AocResult<Vec<LogEntry>>>()?;
+
+        log.sort_by(|a, b| a.date_time.cmp(&b.date_time));
+
+        Ok(process_log(&log))
+    }
+
+    fn part_one(&self, input: &GuardOverview) -> AocResult<String> {
+        if input.iter().next().is_none() {
+            return Err(AocError::no_solution("The log does not contain any guard."));
+        }
+
+        let guard = strategy_1(input);
+
+        Ok((guard.get_id() as usize * guard.find_minute_most_often_asleep()).to_string())
+    }
+
+    fn part_two(&self, input: &GuardOverview) -> AocResult<String> {
+        if input.iter().next().is_none() {
+            return Err(AocError::no_solution("The log does not contain any guard."));
+        }
+
+        let guard = strategy_2(input);
+
+        Ok((guard.get_id() as usize * guard.find_minute_most_often_asleep()).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +340,9 @@ mod tests {
     #[test]
     fn test_action_parsing_guard() {
         assert_eq!(Action::parse_from("Guard #42 begins shift").unwrap(), Action::Guard(42));
+
+        let err = LogEntry::parse_from("[1518-07-13 23:58] Guard #99999999999 begins shift").unwrap_err();
+        assert_eq!(err.location().map(|location| location.column), Some(27));
     }
 
     #[test]
@@ -1,3 +1,6 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
 pub fn react_polymer(input: &str) -> String {
     let mut stack: Vec<char> = Vec::with_capacity(input.len());
 
@@ -43,6 +46,38 @@ pub fn is_minimal(input: &str) -> bool {
     true
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = String;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<String> {
+        Ok(lines.iter().map(|line| line.trim()).collect())
+    }
+
+    fn part_one(&self, input: &String) -> AocResult<String> {
+        Ok(react_polymer(input).len().to_string())
+    }
+
+    fn part_two(&self, input: &String) -> AocResult<String> {
+        if input.is_empty() {
+            return Err(AocError::no_solution("The polymer is empty."));
+        }
+
+        let (_, length) = find_minimal_polymer(input);
+
+        Ok(length.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,37 +1,14 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::error::Error;
-use std::fmt;
 use std::io;
 use std::io::BufRead;
 use std::io::BufReader;
-use std::num::ParseIntError;
 
+use aoc_common::parse::{delimited, literal, pair, parse_all, spaces, terminated, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use itertools::Itertools;
 
-type GenericResult<T> = Result<T, Box<dyn Error>>;
-
-#[derive(Debug)]
-struct GenericError {
-    message: String,
-}
-
-impl GenericError {
-    pub fn new(message: &str) -> GenericError {
-        GenericError {
-            message: message.to_string(),
-        }
-    }
-}
-
-impl Error for GenericError {}
-
-impl fmt::Display for GenericError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "GenericError: {}", self.message)
-    }
-}
-
 #[derive(Debug, PartialEq, Clone)]
 pub struct Coordinate(usize, usize);
 
@@ -118,16 +95,11 @@ fn read_file_content(path: &str) -> io::Result<Vec<String>> {
     buf_reader.lines().collect()
 }
 
-fn parse_coordinates(input: &str) -> GenericResult<Coordinate> {
-    let splits: Vec<&str> = input.trim().split(',').collect();
+fn parse_coordinates(input: &str) -> AocResult<Coordinate> {
+    let number = || delimited(spaces(), unsigned(), spaces());
+    let (x, y) = parse_all(pair(terminated(number(), literal(",")), number()), input)?;
 
-    if splits.len() == 2 {
-        let result = splits.iter().map(|&element| element.trim().parse::<usize>()).collect::<Result<Vec<usize>, ParseIntError>>()?;
-
-        Ok(Coordinate(result[0], result[1]))
-    } else {
-        Err(GenericError::new(&format!("Invalid line: {}", input)).into())
-    }
+    Ok(Coordinate(x, y))
 }
 
 struct Candidate(u8, usize, Coordinate);
@@ -150,10 +122,14 @@ impl Candidate {
     }
 }
 
-pub fn read_coordinates(path: &str) -> GenericResult<Vec<Coordinate>> {
+pub fn read_coordinates(path: &str) -> AocResult<Vec<Coordinate>> {
     let raw_content = read_file_content(path)?;
 
-    raw_content.iter().map(|line| parse_coordinates(line)).collect()
+    raw_content
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_coordinates(line).map_err(|err| err.offset_by(idx + 1, 1).in_file(path)))
+        .collect()
 }
 
 pub fn create_patch_map(initial_points: &Vec<Coordinate>) -> Map {
@@ -211,6 +187,47 @@ pub fn create_distance_map(initial_points: &Vec<Coordinate>, max_distance_sum: u
     map
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Coordinate>;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Coordinate>> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| parse_coordinates(line).map_err(|err| err.offset_by(idx + 1, 1)))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<Coordinate>) -> AocResult<String> {
+        create_patch_map(input)
+            .calculate_finite_patch_sizes()
+            .values()
+            .max()
+            .map(|size| size.to_string())
+            .ok_or_else(|| AocError::no_solution("There is no finite area."))
+    }
+
+    fn part_two(&self, input: &Vec<Coordinate>) -> AocResult<String> {
+        create_distance_map(input, 10000)
+            .calculate_finite_patch_sizes()
+            .values()
+            .max()
+            .map(|size| size.to_string())
+            .ok_or_else(|| AocError::no_solution("There is no region within the total distance."))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,10 +1,11 @@
 #[macro_use]
 extern crate lazy_static;
 
+use aoc_common::solution::Solution;
 use aoc_common;
-use aoc_common::GenericResult;
+use aoc_common::AocResult;
 use regex::Regex;
-use aoc_common::GenericError;
+use aoc_common::AocError;
 use std::rc::Rc;
 use std::collections::{HashSet, BinaryHeap, VecDeque};
 use std::collections::HashMap;
@@ -16,13 +17,13 @@ use lazy_static::initialize;
 #[derive(Debug, PartialEq)]
 pub struct Dependency(char, char);
 
-pub fn read_dependencies(path: &str) -> GenericResult<Vec<Dependency>> {
+pub fn read_dependencies(path: &str) -> AocResult<Vec<Dependency>> {
     let raw_input = aoc_common::read_raw_file_content(path)?;
 
     raw_input.iter().map(|input| parse_dependency(input)).collect()
 }
 
-fn parse_dependency(input: &str) -> GenericResult<Dependency> {
+fn parse_dependency(input: &str) -> AocResult<Dependency> {
     lazy_static! {
         static ref LOG_REGEX: Regex = Regex::new(r"Step (.) must be finished before step (.) can begin.").unwrap();
     }
@@ -32,21 +33,21 @@ fn parse_dependency(input: &str) -> GenericResult<Dependency> {
         .map(|captures| {
             internal_parse_dependency(&captures)
         })
-        .ok_or(GenericError::new(&format!("Could not parse input: {}", input)).into())
+        .ok_or(AocError::parse(&format!("Could not parse input: {}", input)))
         .and_then(|r| r)
 }
 
-fn internal_parse_dependency(captures: &regex::Captures) -> GenericResult<Dependency> {
+fn internal_parse_dependency(captures: &regex::Captures) -> AocResult<Dependency> {
     let source = parse_from_capture(captures, 1);
     let target = parse_from_capture(captures, 2);
 
     source.and_then(|s| target.map(|t| Dependency(s, t)))
 }
 
-fn parse_from_capture(captures: &regex::Captures, index: usize) -> GenericResult<char> {
+fn parse_from_capture(captures: &regex::Captures, index: usize) -> AocResult<char> {
     captures.get(index)
         .and_then(|m| m.as_str().chars().next())
-        .ok_or(GenericError::new(&format!("Could not find dependency {}", index)).into())
+        .ok_or(AocError::parse(&format!("Could not find dependency {}", index)))
 }
 
 #[derive(Debug, PartialEq)]
@@ -238,6 +239,45 @@ impl PartialEq for DependencyVertex {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Dependency>;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Dependency>> {
+        lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| parse_dependency(line))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<Dependency>) -> AocResult<String> {
+        let graph = DependencyGraph::generate_graph(input);
+
+        Ok(graph.sort_topologically().iter().collect())
+    }
+
+    fn part_two(&self, input: &Vec<Dependency>) -> AocResult<String> {
+        let graph = DependencyGraph::generate_graph(input);
+        let required_time = graph
+            .assign_work(5, 60)
+            .last()
+            .map(|&WorkAssignment(_, _, Time(_, end_time))| end_time)
+            .unwrap_or(0);
+
+        Ok(required_time.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
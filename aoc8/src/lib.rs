@@ -1,4 +1,6 @@
-use aoc_common::{GenericResult, GenericError};
+use aoc_common::parse::{parse_at, tokens, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::{AocResult, AocError};
 
 #[derive(Debug, PartialEq)]
 pub struct TreeNode {
@@ -21,24 +23,24 @@ impl TreeNode {
         }
     }
 
-    pub fn parse_tree(input: &Vec<usize>) -> GenericResult<TreeNode> {
+    pub fn parse_tree(input: &Vec<usize>) -> AocResult<TreeNode> {
         let mut input_iter = input.iter();
 
         TreeNode::internal_parse(&mut input_iter)
     }
 
-    fn internal_parse<'a, I>(input: &mut I) -> GenericResult<TreeNode> where I: Iterator<Item = &'a usize> {
+    fn internal_parse<'a, I>(input: &mut I) -> AocResult<TreeNode> where I: Iterator<Item = &'a usize> {
         if let Some(&num_children) = input.next() {
             if let Some(&num_meta_data) = input.next() {
-                let children = (0..num_children).map(|_| TreeNode::internal_parse(input)).collect::<GenericResult<Vec<TreeNode>>>()?;
+                let children = (0..num_children).map(|_| TreeNode::internal_parse(input)).collect::<AocResult<Vec<TreeNode>>>()?;
                 let meta_data: Vec<usize> = input.take(num_meta_data).map(|e| e.clone()).collect();
 
                 Ok(TreeNode::node(children, meta_data))
             } else {
-                Err(GenericError::new("Invalid meta data specification.").into())
+                Err(AocError::invalid_input("Invalid meta data specification."))
             }
         } else {
-            Err(GenericError::new("Invalid input.").into())
+            Err(AocError::invalid_input("Invalid input."))
         }
     }
 
@@ -60,16 +62,51 @@ impl TreeNode {
     }
 }
 
-pub fn read_input_from_file(path: &str) -> GenericResult<Vec<usize>> {
+pub fn read_input_from_file(path: &str) -> AocResult<Vec<usize>> {
     let content = std::fs::read_to_string(path)?;
+    let lines: Vec<&str> = content.lines().collect();
 
-    content
-        .trim()
-        .split_whitespace()
-        .map(|number| number
-            .parse::<usize>()
-            .map_err(|e| e.into()))
-        .collect()
+    parse_numbers(&lines).map_err(|err| err.in_file(path))
+}
+
+fn parse_numbers<S: AsRef<str>>(lines: &[S]) -> AocResult<Vec<usize>> {
+    let mut numbers = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        for (column, number) in tokens(line.as_ref()) {
+            numbers.push(parse_at(unsigned(), number, idx + 1, column)?);
+        }
+    }
+
+    Ok(numbers)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = TreeNode;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<TreeNode> {
+        let numbers = parse_numbers(lines)?;
+
+        TreeNode::parse_tree(&numbers)
+    }
+
+    fn part_one(&self, input: &TreeNode) -> AocResult<String> {
+        Ok(input.sum_meta_data().to_string())
+    }
+
+    fn part_two(&self, input: &TreeNode) -> AocResult<String> {
+        Ok(input.calculate_indexed_value().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -1,11 +1,10 @@
-use aoc9::MarbleGame;
-
 fn main() {
-    let game = MarbleGame::new(458, 72019);
+    let input = aoc_common::read_raw_file_content("input.txt").unwrap();
+    let game = aoc9::parse_marble_game(&input).unwrap();
     let result = game.play_game();
     println!("{}", result);
 
-    let large_game = MarbleGame::new(458, 72019 * 100);
+    let large_game = game.extended(100);
     let large_result = large_game.play_game();
     println!("{}", large_result);
-}
\ No newline at end of file
+}
@@ -1,4 +1,7 @@
-use aoc_common::collections::CircularLinkedList;
+use aoc_collections::CircularLinkedList;
+use aoc_common::parse::{literal, map, pair, parse_at, preceded, terminated, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 
 pub struct MarbleGame {
     num_players: usize,
@@ -13,6 +16,11 @@ impl MarbleGame {
         }
     }
 
+    /// The same game but with a last marble which is worth `factor` times as many points.
+    pub fn extended(&self, factor: usize) -> MarbleGame {
+        MarbleGame::new(self.num_players, self.last_marble * factor)
+    }
+
     pub fn play_game(&self) -> usize {
         let mut marbles = vec![0].into_iter().collect::<CircularLinkedList<usize>>();
 
@@ -46,10 +54,79 @@ impl MarbleGame {
     }
 }
 
+/// Parses "<players> players; last marble is worth <points> points".
+pub fn parse_marble_game(lines: &[String]) -> AocResult<MarbleGame> {
+    let (idx, line) = lines
+        .iter()
+        .enumerate()
+        .find(|(_, line)| !line.trim().is_empty())
+        .ok_or_else(|| AocError::invalid_input("The game description is missing."))?;
+    let game = map(
+        pair(
+            terminated(unsigned(), literal(" players; ")),
+            preceded(
+                literal("last marble is worth "),
+                terminated(unsigned(), literal(" points")),
+            ),
+        ),
+        |(num_players, last_marble)| MarbleGame::new(num_players, last_marble),
+    );
+    let game = parse_at(game, line.trim_end(), idx + 1, 1)?;
+
+    if game.num_players == 0 {
+        return Err(AocError::invalid_input("The game needs at least one player."));
+    }
+
+    Ok(game)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = MarbleGame;
+
+    fn year(&self) -> u16 {
+        2018
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<MarbleGame> {
+        parse_marble_game(lines)
+    }
+
+    fn part_one(&self, input: &MarbleGame) -> AocResult<String> {
+        Ok(input.play_game().to_string())
+    }
+
+    fn part_two(&self, input: &MarbleGame) -> AocResult<String> {
+        Ok(input.extended(100).play_game().to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_marble_game() {
+        let lines = vec!["10 players; last marble is worth 1618 points".to_string()];
+        let game = parse_marble_game(&lines).unwrap();
+
+        assert_eq!(game.play_game(), 8317);
+
+        let lines = vec!["10 players; last marble is worth x points".to_string()];
+        let err = parse_marble_game(&lines).err().unwrap();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((1, 34))
+        );
+    }
+
     #[test]
     fn test_play_game_1() {
         assert_eq!(MarbleGame::new(9, 25).play_game(), 32);
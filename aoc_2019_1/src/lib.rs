@@ -0,0 +1,122 @@
+use aoc_common::solution::Solution;
+use aoc_common::{read_raw_file_content, AocError, AocResult};
+
+pub fn fuel_for_mass(mass: u64) -> u64 {
+    (mass / 3).saturating_sub(2)
+}
+
+/// Fuel for a module including the fuel which is needed to carry the added fuel. Every step
+/// needs less than a third of the previous one, so the total never exceeds the module mass.
+pub fn fuel_for_module(mass: u64) -> u64 {
+    let mut total_fuel = 0;
+    let mut fuel = fuel_for_mass(mass);
+
+    while fuel > 0 {
+        total_fuel += fuel;
+        fuel = fuel_for_mass(fuel);
+    }
+
+    total_fuel
+}
+
+pub fn total_fuel(masses: &[u64], fuel: impl Fn(u64) -> u64) -> AocResult<u64> {
+    masses.iter().try_fold(0u64, |total, &mass| {
+        total
+            .checked_add(fuel(mass))
+            .ok_or_else(|| AocError::invalid_input("The total fuel does not fit into 64 bits."))
+    })
+}
+
+/// Parses one module mass per line. Blank lines are skipped.
+pub fn parse_masses(lines: &[String]) -> AocResult<Vec<u64>> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let column = line.len() - line.trim_start().len();
+
+            line.trim().parse().map_err(|_| {
+                AocError::parse_at(
+                    index + 1,
+                    column + 1,
+                    &format!("Could not parse module mass '{}'.", line.trim()),
+                )
+            })
+        })
+        .collect()
+}
+
+pub fn load_masses(filename: &str) -> AocResult<Vec<u64>> {
+    let lines = read_raw_file_content(filename)?;
+
+    parse_masses(&lines).map_err(|err| err.in_file(filename))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<u64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<u64>> {
+        parse_masses(lines)
+    }
+
+    fn part_one(&self, input: &Vec<u64>) -> AocResult<String> {
+        Ok(total_fuel(input, fuel_for_mass)?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<u64>) -> AocResult<String> {
+        Ok(total_fuel(input, fuel_for_module)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuel_examples() {
+        assert_eq!(fuel_for_mass(12), 2);
+        assert_eq!(fuel_for_mass(14), 2);
+        assert_eq!(fuel_for_mass(1969), 654);
+        assert_eq!(fuel_for_mass(100756), 33583);
+        assert_eq!(fuel_for_mass(5), 0);
+    }
+
+    #[test]
+    fn fuel_for_module_examples() {
+        assert_eq!(fuel_for_module(14), 2);
+        assert_eq!(fuel_for_module(1969), 966);
+        assert_eq!(fuel_for_module(100756), 50346);
+        assert_eq!(fuel_for_module(0), 0);
+        assert!(fuel_for_module(u64::MAX) < u64::MAX / 2);
+    }
+
+    #[test]
+    fn total_fuel_overflow() {
+        assert_eq!(total_fuel(&[12, 14, 1969], fuel_for_mass).unwrap(), 658);
+        assert!(total_fuel(&[u64::MAX, u64::MAX, u64::MAX, u64::MAX], fuel_for_mass).is_err());
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let lines: Vec<String> = vec!["12".into(), "".into(), "  19x69".into()];
+        let err = parse_masses(&lines).unwrap_err();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((3, 3))
+        );
+        assert_eq!(parse_masses(&lines[..2]).unwrap(), vec![12]);
+    }
+}
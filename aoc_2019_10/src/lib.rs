@@ -1,18 +1,15 @@
-use aoc_common::math::Point;
-use aoc_common::GenericResult;
-use std::cmp::Ordering;
-use std::collections::HashSet;
+use aoc_common::solution::Solution;
+use aoc_common::math::{Direction, Point};
+use aoc_common::{AocError, AocResult};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fs;
-use bigdecimal::BigDecimal;
-use bigdecimal::FromPrimitive;
-use std::ops::{Div, Mul};
 
 pub struct Map {
     asteroids: HashSet<Point>,
 }
 
 impl Map {
-    pub fn load_from_file(filename: &str) -> GenericResult<Map> {
+    pub fn load_from_file(filename: &str) -> AocResult<Map> {
         let raw_content = fs::read_to_string(filename)?;
         Ok(Map::parse_map(&raw_content))
     }
@@ -48,189 +45,111 @@ impl Map {
     fn number_visible_asteroids_from(&self, asteroid: Point) -> u32 {
         self.asteroids
             .iter()
-            .filter(|&&other| asteroid != other && self.is_visible_from(asteroid, other))
-            .count() as u32
+            .filter_map(|&other| Direction::between(asteroid, other))
+            .collect::<HashSet<Direction>>()
+            .len() as u32
     }
 
+    #[cfg(test)]
     fn is_visible_from(&self, asteroid_a: Point, asteroid_b: Point) -> bool {
-        self.asteroids
-            .iter()
-            .filter(|&&asteroid| asteroid_a != asteroid && asteroid_b != asteroid)
-            .filter(|&&asteroid| Map::is_in_between(asteroid_a, asteroid_b, asteroid))
-            .count()
-            == 0
-    }
-
-    fn is_in_between(asteroid_a: Point, asteroid_b: Point, asteroid: Point) -> bool {
-        let a_b = asteroid_b - asteroid_a;
-        let a_asteroid = asteroid - asteroid_a;
-
-        if a_b.0 * a_asteroid.1 == a_b.1 * a_asteroid.0 {
-            let t = match a_b {
-                Point(0, 0) => panic!("Asteroid a and b should not be the same."),
-                Point(x, 0) => a_asteroid.0 as f64 / x as f64,
-                Point(_, y) => a_asteroid.1 as f64 / y as f64,
-            };
-
-            0.0 < t && t < 1.0
-        } else {
-            false
-        }
+        let direction = Direction::between(asteroid_a, asteroid_b);
+        let distance = asteroid_a.manhattan_distance(asteroid_b);
+
+        !self.asteroids.iter().any(|&asteroid| {
+            asteroid != asteroid_a
+                && Direction::between(asteroid_a, asteroid) == direction
+                && asteroid_a.manhattan_distance(asteroid) < distance
+        })
     }
 
     pub fn vaporize_asteroids(&self, base: Point) -> AsteroidIterator {
-        let mut translated_asteroids: Vec<Point> = self
-            .asteroids
-            .iter()
-            .filter(|&&other| other != base)
-            .map(|&x| x)
-            .collect();
-
-        translated_asteroids.sort_by(|&a, &b| Map::compare_asteroids(base, a, b));
+        let mut lines_of_sight: BTreeMap<Direction, Vec<Point>> = BTreeMap::new();
 
-        AsteroidIterator::new(base,translated_asteroids)
-    }
-
-    fn compare_asteroids(base: Point, a: Point, b: Point) -> Ordering {
-        let (quarter_a, y_a, distance_sqrd_a) = Map::calculate_asteroid_properties(a - base);
-        let (quarter_b, y_b, distance_sqrd_b) = Map::calculate_asteroid_properties(b - base);
-
-        if quarter_a > quarter_b {
-            Ordering::Greater
-        } else if quarter_a < quarter_b {
-            Ordering::Less
-        } else {
-            let sign = quarter_a.get_sign();
-            let y_a = y_a;
-            let y_b = y_b;
-            let cos_a_times_distance_sqrd_b = sign as isize * y_a * y_a * distance_sqrd_b;
-            let cos_b_times_distance_sqrd_a = sign as isize * y_b * y_b * distance_sqrd_a;
-
-            if cos_a_times_distance_sqrd_b < cos_b_times_distance_sqrd_a {
-                Ordering::Less
-            } else if cos_a_times_distance_sqrd_b > cos_b_times_distance_sqrd_a {
-                Ordering::Greater
-            } else {
-                distance_sqrd_a.partial_cmp(&distance_sqrd_b).unwrap()
+        for &asteroid in self.asteroids.iter() {
+            if let Some(direction) = Direction::between(base, asteroid) {
+                lines_of_sight.entry(direction).or_default().push(asteroid);
             }
         }
-    }
 
-    fn calculate_asteroid_properties(asteroid: Point) -> (Quarter, isize, isize) {
-        let Point(x, y) = asteroid;
-        let quarter = Quarter::from_point(asteroid);
-        let distance_sqrd = asteroid.length_sqrd();
+        let lines_of_sight = lines_of_sight
+            .into_values()
+            .map(|mut asteroids| {
+                asteroids.sort_by_key(|&asteroid| base.manhattan_distance(asteroid));
+                asteroids.into_iter().collect()
+            })
+            .collect();
 
-        (quarter, y, distance_sqrd)
+        AsteroidIterator::new(lines_of_sight)
     }
 }
 
-#[derive(Debug)]
-enum Quarter {
-    First,
-    Second,
-    Third,
-    Fourth,
+/// Yields the asteroids in the order in which a laser rotating clockwise from north hits them.
+/// Each rotation only vaporizes the closest remaining asteroid of every line of sight.
+pub struct AsteroidIterator {
+    lines_of_sight: Vec<VecDeque<Point>>,
+    current_idx: usize,
 }
 
-impl Quarter {
-    fn from_point(point: Point) -> Quarter {
-        let Point(x, y) = point;
-
-        if x >= 0 && y < 0 {
-            Quarter::First
-        } else if x >= 0 && y >= 0 {
-            Quarter::Second
-        } else if x < 0 && y >= 0 {
-            Quarter::Third
-        } else {
-            Quarter::Fourth
+impl AsteroidIterator {
+    fn new(lines_of_sight: Vec<VecDeque<Point>>) -> AsteroidIterator {
+        AsteroidIterator {
+            lines_of_sight,
+            current_idx: 0,
         }
     }
+}
 
-    fn get_sign(&self) -> i32 {
-        match self {
-            Quarter::First => -1,
-            Quarter::Second => 1,
-            Quarter::Third => -1,
-            Quarter::Fourth => 1,
-        }
-    }
+impl Iterator for AsteroidIterator {
+    type Item = Point;
 
-    fn get_numeric_value(&self) -> i32 {
-        match self {
-            Quarter::First => 1,
-            Quarter::Second => 2,
-            Quarter::Third => 3,
-            Quarter::Fourth => 4,
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_idx >= self.lines_of_sight.len() {
+            self.lines_of_sight.retain(|line| !line.is_empty());
+            self.current_idx = 0;
         }
+
+        let asteroid = self.lines_of_sight.get_mut(self.current_idx)?.pop_front();
+        self.current_idx += 1;
+
+        asteroid
     }
 }
 
-impl PartialOrd for Quarter {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.get_numeric_value().partial_cmp(&other.get_numeric_value())
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Map;
+
+    fn year(&self) -> u16 {
+        2019
     }
-}
 
-impl PartialEq for Quarter {
-    fn eq(&self, other: &Self) -> bool {
-        self.get_numeric_value().eq(&other.get_numeric_value())
+    fn day(&self) -> u8 {
+        10
     }
-}
 
-pub struct AsteroidIterator {
-    sorted_asteroids: Vec<Option<Point>>,
-    last_returned_asteroid: Option<Point>,
-    base: Point,
-    current_idx: usize,
-    returned_values: usize,
-}
+    fn parse(&self, lines: &[String]) -> AocResult<Map> {
+        Ok(Map::parse_map(&lines.join("\n")))
+    }
 
-impl AsteroidIterator {
-    fn new(base: Point, sorted_asteroids: Vec<Point>) -> AsteroidIterator {
-        let sorted_asteroids = sorted_asteroids.into_iter().map(|x| Some(x)).collect();
-        AsteroidIterator {
-            sorted_asteroids,
-            last_returned_asteroid: None,
-            base,
-            current_idx: 0,
-            returned_values: 0,
-        }
+    fn part_one(&self, input: &Map) -> AocResult<String> {
+        let (_, visible_asteroids) = input
+            .find_best_asteroid()
+            .ok_or_else(|| AocError::no_solution("Map does not contain asteroids."))?;
+
+        Ok(visible_asteroids.to_string())
     }
-}
 
-impl Iterator for AsteroidIterator {
-    type Item = Point;
+    fn part_two(&self, input: &Map) -> AocResult<String> {
+        let (base, _) = input
+            .find_best_asteroid()
+            .ok_or_else(|| AocError::no_solution("Map does not contain asteroids."))?;
+        let Point(x, y) = input
+            .vaporize_asteroids(base)
+            .nth(199)
+            .ok_or_else(|| AocError::no_solution("Less than 200 asteroids can be vaporized."))?;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.returned_values >= self.sorted_asteroids.len() {
-            None
-        } else {
-            loop {
-                if let Some(new_value) = self.sorted_asteroids[self.current_idx] {
-                    let return_value = if let Some(previous_value) = self.last_returned_asteroid {
-                        !Map::is_in_between(self.base, new_value, previous_value)
-                    } else {
-                        true
-                    };
-
-                    if return_value {
-                        std::mem::replace(&mut self.sorted_asteroids[self.current_idx], None);
-                        self.last_returned_asteroid = Some(new_value);
-                        self.returned_values += 1;
-                        return Some(new_value);
-                    }
-                }
-
-                self.current_idx += 1;
-
-                if self.current_idx >= self.sorted_asteroids.len() {
-                    self.current_idx = 0;
-                    self.last_returned_asteroid = None;
-                }
-            }
-        }
+        Ok((x * 100 + y).to_string())
     }
 }
 
@@ -438,9 +357,4 @@ mod tests {
         assert_eq!(asteroid_iterator.next(), Some(Point(11, 1)));
         assert_eq!(asteroid_iterator.next(), None);
     }
-
-    #[test]
-    fn compare_asteroids() {
-        assert_eq!(Map::compare_asteroids(Point(8, 3), Point(8, 1), Point(9, 2)), Ordering::Less);
-    }
 }
@@ -1,5 +1,8 @@
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use aoc_2019_7::{AsyncIntComputer, ChannelReader, ChannelWriter};
 use aoc_common::math::Point;
+use aoc_common::ocr;
 use std::collections::HashMap;
 use std::io;
 use std::io::{BufRead, BufReader, ErrorKind};
@@ -122,6 +125,16 @@ impl PaintRobot {
         result
     }
 
+    /// Reads the letters painted white.
+    pub fn read_painted_letters(&self) -> AocResult<String> {
+        ocr::recognize_points(
+            self.painted_fields
+                .iter()
+                .filter(|(_, color)| matches!(color, Color::White))
+                .map(|(&position, _)| position),
+        )
+    }
+
     fn fold_keys(&self, cmp: fn(isize, isize) -> isize) -> Point {
         self.painted_fields
             .keys()
@@ -237,6 +250,38 @@ impl<T: BufRead> IntOutputReader<T> {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        aoc_2019_2::parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        let mut robot = PaintRobot::new(input.clone());
+        robot.paint(Color::Black);
+
+        Ok(robot.get_num_at_least_once_painted_fields().to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        let mut robot = PaintRobot::new(input.clone());
+        robot.paint(Color::White);
+
+        robot.read_painted_letters()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
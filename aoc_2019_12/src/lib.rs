@@ -1,3 +1,7 @@
+use aoc_common::cycle;
+use aoc_common::solution::Solution;
+use aoc_common::parse::{delimited, literal, pair, parse_lines, preceded, signed};
+use aoc_common::{AocError, AocResult};
 use aoc_common::math::{Point3d, IntErrorKind};
 use std::cmp::Ordering;
 
@@ -16,7 +20,7 @@ impl Moon {
         Moon::new(position, Point3d(0, 0, 0))
     }
 
-    fn energy(&self) -> usize {
+    pub fn energy(&self) -> usize {
         let Point3d(x, y, z) = self.position;
         let Point3d(r, s, t) = self.velocity;
 
@@ -29,163 +33,334 @@ impl Moon {
     fn calculate_energy(values: Vec<isize>) -> usize {
         values.into_iter().map(|x| x.abs() as usize).sum()
     }
+}
+
+/// Velocity change which a body at `other` causes for a body at `position` along one axis. Rules
+/// only see a single axis, so that every axis evolves independently of the others.
+pub trait Interaction {
+    fn pull(&self, position: isize, other: isize) -> isize;
+}
 
-    fn update_velocity(&mut self, gravity: Point3d) {
-        self.velocity = self.velocity + gravity;
+/// Pulls bodies towards each other by one unit per axis.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Gravity;
+
+impl Interaction for Gravity {
+    fn pull(&self, position: isize, other: isize) -> isize {
+        match position.cmp(&other) {
+            Ordering::Greater => -1,
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+        }
     }
+}
 
-    fn update_position(&mut self) {
-        self.position = self.position + self.velocity;
+impl<F: Fn(isize, isize) -> isize> Interaction for F {
+    fn pull(&self, position: isize, other: isize) -> isize {
+        self(position, other)
     }
 }
 
-#[derive(Debug)]
-pub struct MoonSimulator {
-    moons: Vec<Moon>,
+/// Positions and velocities of all bodies along one axis.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AxisState {
+    pub positions: Vec<isize>,
+    pub velocities: Vec<isize>,
 }
 
-impl MoonSimulator {
-    pub fn new(moons: Vec<Moon>) -> MoonSimulator {
-        MoonSimulator { moons }
-    }
+impl AxisState {
+    fn step<R: Interaction>(&mut self, rule: &R) {
+        self.apply_pulls(rule, 1);
 
-    pub fn energy(&self) -> usize {
-        self.moons.iter().map(|moon| moon.energy()).sum()
+        for (position, velocity) in self.positions.iter_mut().zip(&self.velocities) {
+            *position += velocity;
+        }
     }
 
-    pub fn simulate_steps(&mut self, steps: isize) {
-        for _ in 0..steps {
-            self.simulate_step();
+    /// Undoes `step`: the positions before the step follow from the velocities, and these
+    /// determine the velocities before the step.
+    fn step_back<R: Interaction>(&mut self, rule: &R) {
+        for (position, velocity) in self.positions.iter_mut().zip(&self.velocities) {
+            *position -= velocity;
         }
+
+        self.apply_pulls(rule, -1);
     }
 
-    pub fn moons(&self) -> &Vec<Moon> {
-        &self.moons
+    fn apply_pulls<R: Interaction>(&mut self, rule: &R, sign: isize) {
+        for i in 0..self.positions.len() {
+            let pull: isize = self
+                .positions
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .map(|(_, &other)| rule.pull(self.positions[i], other))
+                .sum();
+
+            self.velocities[i] += sign * pull;
+        }
     }
+}
 
-    fn simulate_step(&mut self) {
-        self.update_velocity();
-        self.moons
-            .iter_mut()
-            .for_each(|moon| moon.update_position())
+/// State of a simulation after a number of steps, which may be negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub step: isize,
+    pub axes: Vec<AxisState>,
+}
+
+/// Simulates bodies in any number of dimensions. A step first changes the velocities according
+/// to the interaction rule and then moves every body by its velocity. Both parts can be undone,
+/// so the simulation can also run backwards.
+#[derive(Debug, Clone)]
+pub struct Simulator<R = Gravity> {
+    axes: Vec<AxisState>,
+    rule: R,
+    step: isize,
+}
+
+impl Simulator<Gravity> {
+    pub fn new(positions: &[Vec<isize>]) -> AocResult<Simulator<Gravity>> {
+        Simulator::with_rule(positions, Gravity)
     }
+}
 
-    fn update_velocity(&mut self) -> () {
-        for i in 0..self.moons.len() - 1 {
-            for j in i + 1..self.moons.len() {
-                let (gravity_a, gravity_b) =
-                    MoonSimulator::calculate_gravity(&self.moons[i], &self.moons[j]);
-                self.moons[i].update_velocity(gravity_a);
-                self.moons[j].update_velocity(gravity_b);
-            }
+impl<R: Interaction> Simulator<R> {
+    /// Creates a simulation of resting bodies at the given positions, which all need the same
+    /// number of dimensions.
+    pub fn with_rule(positions: &[Vec<isize>], rule: R) -> AocResult<Simulator<R>> {
+        let dimensions = positions.first().map_or(0, |position| position.len());
+
+        if positions
+            .iter()
+            .any(|position| position.len() != dimensions)
+        {
+            return Err(AocError::invalid_input(
+                "All bodies need to have the same number of dimensions.",
+            ));
         }
+
+        let axes = (0..dimensions)
+            .map(|axis| AxisState {
+                positions: positions.iter().map(|position| position[axis]).collect(),
+                velocities: vec![0; positions.len()],
+            })
+            .collect();
+
+        Ok(Simulator {
+            axes,
+            rule,
+            step: 0,
+        })
     }
 
-    fn calculate_gravity(moon_a: &Moon, moon_b: &Moon) -> (Point3d, Point3d) {
-        let Point3d(x_a, y_a, z_a) = moon_a.position;
-        let Point3d(x_b, y_b, z_b) = moon_b.position;
+    pub fn dimensions(&self) -> usize {
+        self.axes.len()
+    }
 
-        let gravity = Point3d(
-            MoonSimulator::gravity(x_a, x_b),
-            MoonSimulator::gravity(y_a, y_b),
-            MoonSimulator::gravity(z_a, z_b),
-        );
+    pub fn number_bodies(&self) -> usize {
+        self.axes.first().map_or(0, |axis| axis.positions.len())
+    }
 
-        (gravity, gravity * -1)
+    /// Number of steps simulated so far. Stepping backwards decreases it.
+    pub fn current_step(&self) -> isize {
+        self.step
     }
 
-    fn gravity(a: isize, b: isize) -> isize {
-        match a.cmp(&b) {
-            Ordering::Greater => -1,
-            Ordering::Less => 1,
-            Ordering::Equal => 0,
-        }
+    pub fn position(&self, body: usize) -> Vec<isize> {
+        self.axes.iter().map(|axis| axis.positions[body]).collect()
     }
 
-    pub fn find_period(&self) -> Result<usize, IntErrorKind> {
-        let moons_x = self.moons.iter().map(|&moon| Moon1d::new_x(moon)).collect();
-        let moons_y = self.moons.iter().map(|&moon| Moon1d::new_y(moon)).collect();
-        let moons_z = self.moons.iter().map(|&moon| Moon1d::new_z(moon)).collect();
+    pub fn velocity(&self, body: usize) -> Vec<isize> {
+        self.axes.iter().map(|axis| axis.velocities[body]).collect()
+    }
 
-        let x_period = PeriodFinder::find_period(moons_x);
-        let y_period = PeriodFinder::find_period(moons_y);
-        let z_period = PeriodFinder::find_period(moons_z);
+    pub fn step(&mut self) {
+        for axis in &mut self.axes {
+            axis.step(&self.rule);
+        }
 
-        aoc_common::math::least_common_multiple(&vec![x_period, y_period, z_period])
+        self.step += 1;
     }
-}
-
-#[derive(Copy, Clone, PartialEq)]
-struct Moon1d {
-    position: isize,
-    velocity: isize,
-}
 
-impl Moon1d {
-    fn new_x(moon: Moon) -> Moon1d {
-        Moon1d {
-            position: moon.position.0,
-            velocity: moon.velocity.0,
+    pub fn step_back(&mut self) {
+        for axis in &mut self.axes {
+            axis.step_back(&self.rule);
         }
+
+        self.step -= 1;
     }
 
-    fn new_y(moon: Moon) -> Moon1d {
-        Moon1d {
-            position: moon.position.1,
-            velocity: moon.velocity.1,
+    /// Simulates forwards or backwards until the given step is reached.
+    pub fn run_to(&mut self, step: isize) {
+        while self.step < step {
+            self.step();
+        }
+
+        while self.step > step {
+            self.step_back();
         }
     }
 
-    fn new_z(moon: Moon) -> Moon1d {
-        Moon1d {
-            position: moon.position.2,
-            velocity: moon.velocity.2,
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            step: self.step,
+            axes: self.axes.clone(),
         }
     }
-}
 
-pub struct PeriodFinder {}
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.step = snapshot.step;
+        self.axes = snapshot.axes.clone();
+    }
 
-impl PeriodFinder {
-    fn find_period(mut initial_configuration: Vec<Moon1d>) -> usize {
-       let mut moons = initial_configuration.clone();
+    /// Sum over all bodies of the product of their potential and kinetic energies.
+    pub fn energy(&self) -> usize {
+        (0..self.number_bodies())
+            .map(|body| {
+                let potential: usize = self
+                    .axes
+                    .iter()
+                    .map(|axis| axis.positions[body].unsigned_abs())
+                    .sum();
+                let kinetic: usize = self
+                    .axes
+                    .iter()
+                    .map(|axis| axis.velocities[body].unsigned_abs())
+                    .sum();
+
+                potential * kinetic
+            })
+            .sum()
+    }
 
-        let mut period = 0;
+    /// Number of steps after which every axis returns to its current state. Since the step is
+    /// invertible, each axis runs in a pure cycle which passes through the current state again.
+    /// Does not terminate for rules under which the bodies drift apart forever.
+    pub fn axis_periods(&self) -> Vec<usize> {
+        self.axes
+            .iter()
+            .map(|initial| {
+                // steps are reversible, so every cycle starts at the initial state
+                cycle::brent(initial.clone(), |axis| {
+                    let mut next = axis.clone();
+                    next.step(&self.rule);
+                    next
+                })
+                .length
+            })
+            .collect()
+    }
 
-        loop {
-            period += 1;
+    /// Number of steps after which the whole system returns to its current state.
+    pub fn period(&self) -> Result<usize, IntErrorKind> {
+        aoc_common::math::least_common_multiple(&self.axis_periods())
+    }
+}
 
-            moons = PeriodFinder::simulate_step(moons);
+#[derive(Debug)]
+pub struct MoonSimulator {
+    simulator: Simulator,
+}
 
-            if moons == initial_configuration {
-                break;
+impl MoonSimulator {
+    pub fn new(moons: Vec<Moon>) -> MoonSimulator {
+        let positions: Vec<Vec<isize>> = moons
+            .iter()
+            .map(|moon| {
+                let Point3d(x, y, z) = moon.position;
+                vec![x, y, z]
+            })
+            .collect();
+        let mut simulator =
+            Simulator::new(&positions).expect("Three dimensional moons always fit together.");
+
+        for (body, moon) in moons.iter().enumerate() {
+            let Point3d(x, y, z) = moon.velocity;
+
+            for (axis, velocity) in simulator.axes.iter_mut().zip(&[x, y, z]) {
+                axis.velocities[body] = *velocity;
             }
         }
 
-        period
+        MoonSimulator { simulator }
+    }
+
+    pub fn energy(&self) -> usize {
+        self.simulator.energy()
     }
 
-    fn simulate_step(mut moons: Vec<Moon1d>) -> Vec<Moon1d> {
-        PeriodFinder::update_velocity(&mut moons);
-        PeriodFinder::update_position(&mut moons);
+    pub fn simulate_steps(&mut self, steps: isize) {
+        let target = self.simulator.current_step() + steps;
 
-        moons
+        self.simulator.run_to(target);
     }
 
-    fn update_velocity(moons: &mut Vec<Moon1d>) {
-        for i in 0..moons.len() {
-            for j in i+1..moons.len() {
-                let gravity = MoonSimulator::gravity(moons[i].position, moons[j].position);
-                moons[i].velocity += gravity;
-                moons[j].velocity -= gravity;
-            }
-        }
+    pub fn moons(&self) -> Vec<Moon> {
+        (0..self.simulator.number_bodies())
+            .map(|body| {
+                let (position, velocity) =
+                    (self.simulator.position(body), self.simulator.velocity(body));
+
+                Moon::new(
+                    Point3d(position[0], position[1], position[2]),
+                    Point3d(velocity[0], velocity[1], velocity[2]),
+                )
+            })
+            .collect()
     }
 
-    fn update_position(moons: &mut Vec<Moon1d>) {
-        for moon in moons.iter_mut() {
-            moon.position += moon.velocity
-        }
+    pub fn find_period(&self) -> Result<usize, IntErrorKind> {
+        self.simulator.period()
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Moon>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Moon>> {
+        let position = delimited(
+            literal("<x="),
+            pair(
+                signed::<isize>(),
+                pair(
+                    preceded(literal(", y="), signed::<isize>()),
+                    preceded(literal(", z="), signed::<isize>()),
+                ),
+            ),
+            literal(">"),
+        );
+        let positions = parse_lines(position, lines)?;
+
+        Ok(positions
+            .into_iter()
+            .map(|(x, (y, z))| Moon::new_static_moon(Point3d(x, y, z)))
+            .collect())
+    }
+
+    fn part_one(&self, input: &Vec<Moon>) -> AocResult<String> {
+        let mut simulator = MoonSimulator::new(input.clone());
+        simulator.simulate_steps(1000);
+
+        Ok(simulator.energy().to_string())
+    }
+
+    fn part_two(&self, input: &Vec<Moon>) -> AocResult<String> {
+        let period = MoonSimulator::new(input.clone())
+            .find_period()
+            .map_err(|_| AocError::no_solution("Period does not fit into usize."))?;
+
+        Ok(period.to_string())
     }
 }
 
@@ -259,18 +434,76 @@ mod tests {
         i: isize,
     ) -> () {
         simulator.simulate_steps(i);
-        assert_eq!(simulator.moons, expected_moon_states)
+        assert_eq!(simulator.moons(), expected_moon_states)
     }
 
     #[test]
     fn find_period_example_one() {
-        let mut simulator = MoonSimulator::new(create_moons_example_one());
+        let simulator = MoonSimulator::new(create_moons_example_one());
         assert_eq!(simulator.find_period().unwrap(), 2772);
     }
 
     #[test]
     fn find_period_example_two() {
-        let mut simulator = MoonSimulator::new(create_moons_example_two());
+        let simulator = MoonSimulator::new(create_moons_example_two());
         assert_eq!(simulator.find_period().unwrap(), 4686774924);
     }
+
+    fn example_one_positions() -> Vec<Vec<isize>> {
+        vec![
+            vec![-1, 0, 2],
+            vec![2, -10, -7],
+            vec![4, -8, 8],
+            vec![3, 5, -1],
+        ]
+    }
+
+    #[test]
+    fn simulator_steps_backwards() {
+        let mut simulator = Simulator::new(&example_one_positions()).unwrap();
+        let initial = simulator.snapshot();
+
+        simulator.run_to(10);
+        assert_eq!(simulator.energy(), 179);
+        assert_eq!(simulator.position(3), vec![2, 0, 4]);
+
+        let tenth = simulator.snapshot();
+        simulator.run_to(-5);
+        assert_eq!(simulator.current_step(), -5);
+
+        simulator.run_to(0);
+        assert_eq!(simulator.snapshot(), initial);
+
+        simulator.restore(&tenth);
+        assert_eq!(simulator.velocity(0), vec![-3, -2, 1]);
+    }
+
+    #[test]
+    fn simulator_periods_per_axis() {
+        let simulator = Simulator::new(&example_one_positions()).unwrap();
+
+        assert_eq!(simulator.axis_periods(), vec![18, 28, 44]);
+        assert_eq!(simulator.period().unwrap(), 2772);
+
+        let positions: Vec<Vec<isize>> = (0..5).map(|body| vec![2 * body, body - 2]).collect();
+        let plane = Simulator::new(&positions).unwrap();
+
+        assert_eq!(plane.axis_periods(), vec![6, 4]);
+        assert_eq!(plane.period().unwrap(), 12);
+        assert!(Simulator::new(&[vec![0, 1], vec![2]]).is_err());
+    }
+
+    #[test]
+    fn simulator_with_custom_rule() {
+        let repulsion = |position: isize, other: isize| -Gravity.pull(position, other);
+        let mut simulator = Simulator::with_rule(&[vec![0], vec![1]], repulsion).unwrap();
+
+        simulator.run_to(3);
+        assert_eq!(simulator.position(0), vec![-6]);
+        assert_eq!(simulator.position(1), vec![7]);
+
+        simulator.run_to(0);
+        assert_eq!(simulator.position(1), vec![1]);
+        assert_eq!(simulator.velocity(1), vec![0]);
+    }
 }
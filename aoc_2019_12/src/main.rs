@@ -30,7 +30,7 @@ fn solve_day_12_1() -> () {
 
 fn solve_day_12_2() {
     let moons = create_moons();
-    let mut simulator = MoonSimulator::new(moons);
+    let simulator = MoonSimulator::new(moons);
     println!("Period: {}", simulator.find_period().unwrap());
 }
 
@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use aoc_2019_2::IntComputer;
 use std::cell::RefCell;
 use std::fmt::Formatter;
@@ -11,17 +13,24 @@ pub struct Pinball {
 
 impl Pinball {
     pub fn new(path: &str) -> Pinball {
-        let mut game_memory = aoc_2019_2::read_memory_from_file(path);
+        Pinball::from_memory(aoc_2019_2::read_memory_from_file(path))
+    }
+
+    pub fn from_memory(mut game_memory: Vec<i64>) -> Pinball {
         game_memory[0] = 2;
         Pinball { game_memory }
     }
 
     pub fn start(&self) {
-        let mut game = Rc::new(RefCell::new(PinballGame::new()));
+        self.play(true);
+    }
+
+    /// Plays the game until the program halts and returns the final score.
+    pub fn play(&self, draw: bool) -> isize {
+        let game = Rc::new(RefCell::new(PinballGame::new(draw)));
         let game_output_reader = PinballGameOutputReader::new(Rc::clone(&game));
         let joystick_controller = JoystickController::new(Rc::clone(&game));
         let mut output_reader = IntComputerOutputReader::new(Box::new(game_output_reader));
-        let input = std::io::BufReader::new(std::io::stdin());
 
         let mut computer = IntComputer::new(
             self.game_memory.clone(),
@@ -32,6 +41,9 @@ impl Pinball {
         computer.compute();
 
         game.borrow_mut().finalize_input_sequence();
+
+        let score = game.borrow().display.score;
+        score
     }
 }
 
@@ -127,13 +139,15 @@ enum GameElement {
 struct PinballGame {
     display: Display,
     next_joystick_move: Option<Joystick>,
+    draw: bool,
 }
 
 impl PinballGame {
-    fn new() -> PinballGame {
+    fn new(draw: bool) -> PinballGame {
         PinballGame {
             display: Display::new(44, 23),
             next_joystick_move: None,
+            draw,
         }
     }
 
@@ -145,7 +159,9 @@ impl PinballGame {
     }
 
     fn finalize_input_sequence(&mut self) {
-        self.display.draw();
+        if self.draw {
+            self.display.draw();
+        }
         self.calculate_next_joystick_move();
     }
 
@@ -334,3 +350,32 @@ impl std::io::Read for JoystickController {
         command.as_bytes().read(buf)
     }
 }
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        aoc_2019_2::parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        let output = aoc_2019_2::compute_with_inputs(input.clone(), &[])?;
+        let block_tiles = output.chunks(3).filter(|chunk| chunk[2] == 2).count();
+
+        Ok(block_tiles.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        Ok(Pinball::from_memory(input.clone()).play(false).to_string())
+    }
+}
@@ -1,27 +1,24 @@
-use aoc_2019_14::{QuantifiedChemical, FuelCalculator};
+use aoc_2019_14::{QuantifiedChemical, ReactionGraph};
 
 fn main() -> () {
     let chemical_reactions = aoc_2019_14::read_chemical_reactions_from_file("input.txt").unwrap();
-    let reactor = aoc_2019_14::Reactor::build_reactor(&chemical_reactions);
-    solve_part_1(&reactor);
-    solve_part_2(reactor);
+    let graph = ReactionGraph::new(&chemical_reactions, &["ORE"]).unwrap();
+    solve_part_1(&graph);
+    solve_part_2(&graph);
 }
 
-fn solve_part_1(reactor: &aoc_2019_14::Reactor) {
-    let reactor_result = reactor.calculate_reaction(QuantifiedChemical::new(1, "FUEL".to_owned()));
-    println!(
-        "Chemical reactions: {:?}",
-        reactor_result
-            .constituent("ORE".into())
-            .map(|r| r.required_constituent())
-            .unwrap_or(0)
-    );
+fn solve_part_1(graph: &ReactionGraph) {
+    let requirements = graph
+        .solve(&[QuantifiedChemical::new(1, "FUEL".to_owned())])
+        .unwrap();
+    println!("Chemical reactions: {:?}", requirements.raw_material("ORE"));
 }
 
-fn solve_part_2(reactor: aoc_2019_14::Reactor) {
-    let fuel_calculator = FuelCalculator::new(reactor);
+fn solve_part_2(graph: &ReactionGraph) {
     let ore = 1_000_000_000_000;
-    let max_fuel = fuel_calculator.calculate_max_fuel(ore);
+    let max_fuel = graph
+        .max_output("FUEL", &[QuantifiedChemical::new(ore, "ORE".to_owned())])
+        .unwrap();
 
     println!("Max fuel with {} ore: {}", ore, max_fuel);
 }
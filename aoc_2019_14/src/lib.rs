@@ -1,5 +1,7 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use core::ops;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::io::ErrorKind;
 use std::num::ParseIntError;
@@ -117,139 +119,369 @@ fn parse_chemical_reactions(
     reactions.into_iter().map(|line| line.parse()).collect()
 }
 
-#[derive(Debug, Copy, Clone)]
-pub struct ReactionResult {
-    produced: usize,
-    required: usize,
+/// The reactions as a graph in which every chemical points to the ingredients of the reaction
+/// producing it. Chemicals which are not produced by any reaction have to be raw materials.
+#[derive(Debug, Clone)]
+pub struct ReactionGraph {
+    reactions: HashMap<Chemical, ChemicalReaction>,
+    raw_materials: BTreeSet<Chemical>,
+    /// Every chemical appears before the ingredients it is made of.
+    topological_order: Vec<Chemical>,
 }
 
-impl ReactionResult {
-    fn empty() -> ReactionResult {
-        ReactionResult {
-            produced: 0,
-            required: 0,
+impl ReactionGraph {
+    pub fn new(reactions: &[ChemicalReaction], raw_materials: &[&str]) -> AocResult<ReactionGraph> {
+        let raw_materials: BTreeSet<Chemical> =
+            raw_materials.iter().map(|&raw| raw.to_owned()).collect();
+        let mut index = HashMap::new();
+
+        for reaction in reactions {
+            let chemical = &reaction.output.chemical;
+
+            if raw_materials.contains(chemical) {
+                return Err(AocError::invalid_input(&format!(
+                    "Raw material {} is produced by a reaction.",
+                    chemical
+                )));
+            }
+
+            if index.insert(chemical.clone(), reaction.clone()).is_some() {
+                return Err(AocError::invalid_input(&format!(
+                    "{} is produced by more than one reaction.",
+                    chemical
+                )));
+            }
         }
-    }
 
-    pub fn required_constituent(&self) -> usize {
-        self.required
+        for reaction in reactions {
+            for input in &reaction.inputs {
+                if !index.contains_key(&input.chemical) && !raw_materials.contains(&input.chemical)
+                {
+                    return Err(AocError::invalid_input(&format!(
+                        "Unknown chemical {} is required to produce {}.",
+                        input.chemical, reaction.output.chemical
+                    )));
+                }
+            }
+        }
+
+        let topological_order = ReactionGraph::topological_order(&index, &raw_materials)?;
+
+        Ok(ReactionGraph {
+            reactions: index,
+            raw_materials,
+            topological_order,
+        })
     }
-}
 
-pub struct Reactor {
-    chemical_reactions: HashMap<Chemical, ChemicalReaction>,
-}
+    /// Orders the chemicals with Kahn's algorithm so that every chemical comes before its
+    /// ingredients. Chemicals which cannot be ordered lie on a cycle.
+    fn topological_order(
+        reactions: &HashMap<Chemical, ChemicalReaction>,
+        raw_materials: &BTreeSet<Chemical>,
+    ) -> AocResult<Vec<Chemical>> {
+        let mut consumers: HashMap<&Chemical, usize> = HashMap::new();
 
-impl Reactor {
-    pub fn build_reactor(chemical_reactions: &Vec<ChemicalReaction>) -> Reactor {
-        let index = chemical_reactions
-            .iter()
-            .map(|reaction| (reaction.output.chemical.clone(), reaction.clone()))
+        for reaction in reactions.values() {
+            for input in &reaction.inputs {
+                *consumers.entry(&input.chemical).or_insert(0) += 1;
+            }
+        }
+
+        let mut ready: Vec<&Chemical> = reactions
+            .keys()
+            .chain(raw_materials.iter())
+            .filter(|chemical| !consumers.contains_key(chemical))
             .collect();
-        Reactor {
-            chemical_reactions: index,
+        ready.sort();
+        let mut order = Vec::with_capacity(reactions.len() + raw_materials.len());
+
+        while let Some(chemical) = ready.pop() {
+            order.push(chemical.clone());
+
+            for input in reactions
+                .get(chemical)
+                .iter()
+                .flat_map(|reaction| &reaction.inputs)
+            {
+                if let Some(count) = consumers.get_mut(&input.chemical) {
+                    *count -= 1;
+
+                    if *count == 0 {
+                        ready.push(&input.chemical);
+                    }
+                }
+            }
+        }
+
+        let unordered: HashSet<&Chemical> = consumers
+            .into_iter()
+            .filter(|&(_, count)| count > 0)
+            .map(|(chemical, _)| chemical)
+            .collect();
+
+        match unordered.iter().min() {
+            None => Ok(order),
+            Some(&start) => Err(AocError::invalid_input(&format!(
+                "The reactions form the cycle {}.",
+                ReactionGraph::find_cycle(reactions, &unordered, start).join(" -> ")
+            ))),
         }
     }
 
-    pub fn calculate_reaction(&self, target: QuantifiedChemical) -> ReactorResult {
-        let mut reaction_results: HashMap<Chemical, ReactionResult> = HashMap::new();
-        let mut required_chemicals = vec![target];
+    /// Every unordered chemical is consumed by another unordered chemical, so following the
+    /// consumers from `start` eventually runs into a cycle.
+    fn find_cycle<'a>(
+        reactions: &'a HashMap<Chemical, ChemicalReaction>,
+        unordered: &HashSet<&Chemical>,
+        start: &'a Chemical,
+    ) -> Vec<Chemical> {
+        let mut path = vec![start];
 
-        while let Some(QuantifiedChemical { chemical, amount }) = required_chemicals.pop() {
-            let reaction_result = reaction_results
-                .entry(chemical.clone())
-                .or_insert(ReactionResult::empty());
+        loop {
+            let current = path[path.len() - 1];
+            let consumer = reactions
+                .values()
+                .filter(|reaction| unordered.contains(&reaction.output.chemical))
+                .filter(|reaction| {
+                    reaction
+                        .inputs
+                        .iter()
+                        .any(|input| &input.chemical == current)
+                })
+                .map(|reaction| &reaction.output.chemical)
+                .min()
+                .expect("Every unordered chemical has an unordered consumer.");
+
+            if let Some(position) = path.iter().position(|&chemical| chemical == consumer) {
+                path.push(consumer);
+                return path[position..]
+                    .iter()
+                    .map(|&chemical| chemical.clone())
+                    .collect();
+            }
 
-            if reaction_result.required + amount > reaction_result.produced {
-                let chemical_reaction = self.chemical_reactions.get(&chemical);
+            path.push(consumer);
+        }
+    }
 
-                if let Some(chemical_reaction) = chemical_reaction {
-                    let reaction_amount = chemical_reaction.output.amount;
+    /// Computes the raw materials needed for the targets in a single pass over the chemicals in
+    /// topological order. Every reaction is run as few times as possible.
+    pub fn solve(&self, targets: &[QuantifiedChemical]) -> AocResult<Requirements> {
+        self.checked_solve(targets)?
+            .ok_or_else(|| AocError::invalid_input("Required amounts do not fit into usize."))
+    }
 
-                    let unused_produced_chemical =
-                        reaction_result.produced - reaction_result.required;
+    /// Like `solve` but returns `None` if an amount overflows.
+    fn checked_solve(&self, targets: &[QuantifiedChemical]) -> AocResult<Option<Requirements>> {
+        let mut required: HashMap<&Chemical, usize> = HashMap::new();
 
-                    let factor = ((amount - unused_produced_chemical) + (reaction_amount - 1))
-                        / reaction_amount;
+        for target in targets {
+            if !self.reactions.contains_key(&target.chemical)
+                && !self.raw_materials.contains(&target.chemical)
+            {
+                return Err(AocError::invalid_input(&format!(
+                    "Unknown chemical {}.",
+                    target.chemical
+                )));
+            }
 
-                    let produced_amount = factor * reaction_amount;
+            let amount = required.entry(&target.chemical).or_insert(0);
+            match amount.checked_add(target.amount) {
+                Some(sum) => *amount = sum,
+                None => return Ok(None),
+            }
+        }
 
-                    reaction_result.produced += produced_amount;
+        let mut requirements = Requirements::default();
+
+        for chemical in &self.topological_order {
+            let amount = match required.get(chemical) {
+                Some(&amount) => amount,
+                None => continue,
+            };
+
+            match self.reactions.get(chemical) {
+                Some(reaction) => {
+                    let runs = amount.div_ceil(reaction.output.amount);
+                    let produced = match runs.checked_mul(reaction.output.amount) {
+                        Some(produced) => produced,
+                        None => return Ok(None),
+                    };
+                    let surplus = produced - amount;
+
+                    if surplus > 0 {
+                        requirements.surplus.insert(chemical.clone(), surplus);
+                    }
 
-                    for input in &chemical_reaction.inputs {
-                        required_chemicals.push(input * factor);
+                    for input in &reaction.inputs {
+                        let input_amount = required.entry(&input.chemical).or_insert(0);
+                        match input
+                            .amount
+                            .checked_mul(runs)
+                            .and_then(|needed| input_amount.checked_add(needed))
+                        {
+                            Some(sum) => *input_amount = sum,
+                            None => return Ok(None),
+                        }
                     }
-                } else {
-                    // end of chemical reaction reached
                 }
+                None => {
+                    requirements.raw_materials.insert(chemical.clone(), amount);
+                }
+            }
+        }
+
+        Ok(Some(requirements))
+    }
+
+    /// Finds the largest amount of `target` which can be produced from the available raw
+    /// materials. Raw materials which are not listed are not available at all. Fails if `target`
+    /// does not consume any raw material.
+    pub fn max_output(&self, target: &str, available: &[QuantifiedChemical]) -> AocResult<usize> {
+        let available: HashMap<&Chemical, usize> = available
+            .iter()
+            .map(|raw| (&raw.chemical, raw.amount))
+            .collect();
+        let unbounded = || AocError::invalid_input(&format!("Unbounded output of {}.", target));
+        let requirements = |amount: usize| {
+            self.checked_solve(&[QuantifiedChemical::new(amount, target.to_owned())])
+        };
+        // amounts which overflow need more than any available raw material
+        let feasible = |amount: usize| -> AocResult<bool> {
+            Ok(requirements(amount)?.is_some_and(|requirements| {
+                requirements
+                    .raw_materials
+                    .iter()
+                    .all(|(chemical, &required)| {
+                        available
+                            .get(chemical)
+                            .is_some_and(|&amount| amount >= required)
+                    })
+            }))
+        };
+
+        let consumes_raw_materials = requirements(1)?
+            .ok_or_else(|| AocError::invalid_input("Required amounts do not fit into usize."))?
+            .raw_materials
+            .values()
+            .any(|&amount| amount > 0);
+
+        if !consumes_raw_materials {
+            return Err(unbounded());
+        }
+
+        if !feasible(1)? {
+            return Ok(0);
+        }
+
+        // producing more never needs more raw materials per unit, so doubling finds an
+        // infeasible upper bound
+        let mut lower = 1;
+        let mut upper = 2;
+
+        while feasible(upper)? {
+            lower = upper;
+            upper = upper.checked_mul(2).ok_or_else(unbounded)?;
+        }
+
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+
+            if feasible(middle)? {
+                lower = middle;
+            } else {
+                upper = middle;
             }
+        }
+
+        Ok(lower)
+    }
+
+    /// Exports the reactions to Graphviz DOT with an edge from every ingredient to the chemical
+    /// it is used for. Raw materials are drawn as boxes.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph reactions {\n");
+
+        for raw in &self.raw_materials {
+            dot.push_str(&format!("    \"{}\" [shape=box];\n", raw));
+        }
+
+        let mut outputs: Vec<&Chemical> = self.reactions.keys().collect();
+        outputs.sort();
+
+        for output in outputs {
+            let reaction = &self.reactions[output];
+
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{} {}\"];\n",
+                output, reaction.output.amount, output
+            ));
 
-            reaction_result.required += amount;
+            for input in &reaction.inputs {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                    input.chemical, output, input.amount
+                ));
+            }
         }
 
-        ReactorResult::new(reaction_results)
+        dot.push_str("}\n");
+        dot
     }
 }
 
-#[derive(Debug)]
-pub struct ReactorResult {
-    reaction_results: HashMap<Chemical, ReactionResult>,
+/// Raw materials consumed and leftovers of intermediate chemicals after producing some targets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Requirements {
+    raw_materials: BTreeMap<Chemical, usize>,
+    surplus: BTreeMap<Chemical, usize>,
 }
 
-impl ReactorResult {
-    fn new(reaction_results: HashMap<Chemical, ReactionResult>) -> ReactorResult {
-        ReactorResult { reaction_results }
+impl Requirements {
+    pub fn raw_material(&self, chemical: &str) -> usize {
+        self.raw_materials.get(chemical).cloned().unwrap_or(0)
     }
 
-    pub fn constituent(&self, chemical: Chemical) -> Option<ReactionResult> {
-        self.reaction_results.get(&chemical).map(|r| r.clone())
+    pub fn raw_materials(&self) -> &BTreeMap<Chemical, usize> {
+        &self.raw_materials
     }
-}
 
-pub struct FuelCalculator {
-    reactor: Reactor,
+    pub fn surplus(&self) -> &BTreeMap<Chemical, usize> {
+        &self.surplus
+    }
 }
 
-impl FuelCalculator {
-    pub fn new(reactor: Reactor) -> FuelCalculator {
-        FuelCalculator { reactor }
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<ChemicalReaction>;
+
+    fn year(&self) -> u16 {
+        2019
     }
 
-    pub fn calculate_max_fuel(&self, ore: usize) -> usize {
-        let mut fuel = 1;
-        let mut lower_bound = None;
-        let mut upper_bound = None;
+    fn day(&self) -> u8 {
+        14
+    }
 
-        loop {
-            if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
-                if upper - lower == 1 {
-                    break;
-                }
-            }
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<ChemicalReaction>> {
+        parse_chemical_reactions(lines.to_vec())
+            .map_err(|err| AocError::parse(&err.to_string()))
+    }
 
-            let reactor_result = self
-                .reactor
-                .calculate_reaction(QuantifiedChemical::new(fuel, "FUEL".into()));
-            let required_ore = reactor_result
-                .constituent("ORE".into())
-                .map(|c| c.required_constituent())
-                .unwrap_or(0);
+    fn part_one(&self, input: &Vec<ChemicalReaction>) -> AocResult<String> {
+        let graph = ReactionGraph::new(input, &["ORE"])?;
+        let requirements = graph.solve(&[QuantifiedChemical::new(1, "FUEL".to_owned())])?;
 
-            if required_ore <= ore {
-                lower_bound = Some(fuel);
-            } else {
-                upper_bound = Some(fuel);
-            }
+        Ok(requirements.raw_material("ORE").to_string())
+    }
 
-            fuel = match (lower_bound, upper_bound) {
-                (Some(lower), Some(upper)) => lower + (upper - lower) / 2,
-                (None, Some(upper)) => upper / 2,
-                (Some(lower), None) => lower * 2,
-                _ => panic!("Unexpected bounds")
-            }
-        }
+    fn part_two(&self, input: &Vec<ChemicalReaction>) -> AocResult<String> {
+        let graph = ReactionGraph::new(input, &["ORE"])?;
+        let ore = QuantifiedChemical::new(1_000_000_000_000, "ORE".to_owned());
 
-        lower_bound.unwrap_or(0)
+        Ok(graph.max_output("FUEL", &[ore])?.to_string())
     }
 }
 
@@ -322,23 +554,93 @@ mod tests {
     }
 
     fn run_max_fuel_test(chemical_reactions: &Vec<ChemicalReaction>, expected_fuel: usize) {
-        let reactor = Reactor::build_reactor(chemical_reactions);
-        let fuel_calculator = FuelCalculator::new(reactor);
+        let ore = [QuantifiedChemical::new(1_000_000_000_000, "ORE".to_owned())];
 
-        let max_fuel = fuel_calculator.calculate_max_fuel(1_000_000_000_000);
+        let max_fuel = graph(chemical_reactions).max_output("FUEL", &ore).unwrap();
 
         assert_eq!(expected_fuel, max_fuel);
     }
 
     fn run_test(chemical_reactions: &Vec<ChemicalReaction>, expected_ore_constituent: usize) {
-        let reactor = Reactor::build_reactor(&chemical_reactions);
-        let reactor_result = reactor.calculate_reaction(QuantifiedChemical::new(1, "FUEL".into()));
+        let requirements = graph(chemical_reactions).solve(&[fuel(1)]).unwrap();
+
+        assert_eq!(requirements.raw_material("ORE"), expected_ore_constituent);
+    }
+
+    fn graph(reactions: &[ChemicalReaction]) -> ReactionGraph {
+        ReactionGraph::new(reactions, &["ORE"]).unwrap()
+    }
+
+    fn fuel(amount: usize) -> QuantifiedChemical {
+        QuantifiedChemical::new(amount, "FUEL".to_owned())
+    }
+
+    #[test]
+    fn graph_reports_surplus() {
+        let requirements = graph(&create_first_chemical_reactions())
+            .solve(&[fuel(1)])
+            .unwrap();
+        let surplus: Vec<(&str, usize)> = requirements
+            .surplus()
+            .iter()
+            .map(|(chemical, &amount)| (chemical.as_str(), amount))
+            .collect();
+
+        assert_eq!(surplus, vec![("A", 2)]);
+    }
+
+    #[test]
+    fn graph_rejects_unbounded_output() {
+        let ore = [QuantifiedChemical::new(10, "ORE".to_owned())];
+        let free = parse_raw_string_into_chemical_reactions("0 ORE => 1 FUEL");
+        let expensive = parse_raw_string_into_chemical_reactions("11 ORE => 1 FUEL");
+
+        assert!(graph(&free).max_output("FUEL", &ore).is_err());
+        assert_eq!(graph(&expensive).max_output("FUEL", &ore).unwrap(), 0);
+    }
+
+    #[test]
+    fn graph_supports_multiple_raw_materials() {
+        let reactions = parse_raw_string_into_chemical_reactions(
+            "3 ORE, 1 WATER => 2 MUD\n5 MUD, 2 WATER => 1 BRICK",
+        );
+        let graph = ReactionGraph::new(&reactions, &["ORE", "WATER"]).unwrap();
+        let requirements = graph
+            .solve(&[QuantifiedChemical::new(2, "BRICK".to_owned())])
+            .unwrap();
+
+        assert_eq!(requirements.raw_material("ORE"), 15);
+        assert_eq!(requirements.raw_material("WATER"), 9);
+        assert!(requirements.surplus().is_empty());
+
+        let available = [
+            QuantifiedChemical::new(100, "ORE".to_owned()),
+            QuantifiedChemical::new(20, "WATER".to_owned()),
+        ];
+        assert_eq!(graph.max_output("BRICK", &available).unwrap(), 4);
+        assert!(ReactionGraph::new(&reactions, &["ORE"]).is_err());
+    }
+
+    #[test]
+    fn graph_rejects_cycles_and_unknown_chemicals() {
+        let reactions = parse_raw_string_into_chemical_reactions(
+            "1 ORE, 1 C => 1 A\n1 A => 1 B\n1 B => 1 C\n1 C => 1 FUEL",
+        );
+        let err = ReactionGraph::new(&reactions, &["ORE"]).unwrap_err();
+
+        assert!(err.to_string().contains("A -> B -> C -> A"), "{}", err);
+        assert!(graph(&create_first_chemical_reactions())
+            .solve(&[QuantifiedChemical::new(1, "GOLD".to_owned())])
+            .is_err());
+    }
+
+    #[test]
+    fn graph_to_dot() {
+        let dot = graph(&parse_raw_string_into_chemical_reactions("10 ORE => 2 A")).to_dot();
+
         assert_eq!(
-            reactor_result
-                .constituent("ORE".into())
-                .map(|reaction| reaction.required_constituent())
-                .unwrap_or(0),
-            expected_ore_constituent
+            dot,
+            "digraph reactions {\n    \"ORE\" [shape=box];\n    \"A\" [label=\"2 A\"];\n    \"ORE\" -> \"A\" [label=\"10\"];\n}\n"
         );
     }
 
@@ -1,5 +1,7 @@
 fn main() {
-    let mut droid_program = aoc_2019_15::create_droid_program_from_input("input.txt");
+    let droid_program = aoc_2019_15::create_droid_program_from_input("input.txt");
+    let oxygen_system = droid_program.explore(true).unwrap();
 
-    droid_program.run();
+    println!("Shortest path to oxygen leak: {}", oxygen_system.distance);
+    println!("Time to fill the area: {}", oxygen_system.fill_time);
 }
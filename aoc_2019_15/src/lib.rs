@@ -1,4 +1,7 @@
+use aoc_2019_2::ComputationResult;
 use aoc_common::math::Point;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use rand::Rng;
 use std::cell::RefCell;
 use std::collections::{HashSet, VecDeque};
@@ -20,18 +23,50 @@ impl DroidProgram {
         DroidProgram { program }
     }
 
-    pub fn run(&mut self) {
+    /// Lets the droid explore the whole area and locates the oxygen system. The map is drawn
+    /// after every move if `draw` is set.
+    pub fn explore(&self, draw: bool) -> AocResult<OxygenSystem> {
         let controller = Rc::new(RefCell::new(DroidController::new()));
         let mut output = aoc_2019_13::IntComputerOutputReader::new(Box::new(
-            DroidOutputReader::new(Rc::clone(&controller)),
+            DroidOutputReader::new(Rc::clone(&controller), draw),
         ));
         let input = io::BufReader::new(DroidDirectionController::new(Rc::clone(&controller)));
         let mut computer = aoc_2019_2::IntComputer::new(self.program.clone(), input, &mut output);
 
-        computer.compute();
+        // the droid program never stops on its own but fails once the exploration ends
+        let result = computer.compute();
+        let controller = controller.borrow();
+
+        if let ComputationResult::Failure { error } = result {
+            if !controller.is_explored() {
+                return Err(AocError::invalid_input(&error));
+            }
+        }
+
+        let oxygen = controller
+            .droid_map
+            .find(DroidMapElement::Oxygen)
+            .ok_or_else(|| AocError::no_solution("Could not find the oxygen system."))?;
+        let distance = controller
+            .find_path_to(Point(0, 0), oxygen)
+            .map(|path| path.len())
+            .ok_or_else(|| AocError::no_solution("Could not reach the oxygen system."))?;
+
+        Ok(OxygenSystem {
+            distance,
+            fill_time: controller.find_longest_path_to_any(oxygen),
+        })
     }
 }
 
+/// The oxygen system with the fewest moves from the start and the minutes until oxygen has
+/// spread from it to the whole area.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OxygenSystem {
+    pub distance: usize,
+    pub fill_time: usize,
+}
+
 #[derive(Copy, Clone, PartialOrd, PartialEq)]
 enum DroidMapElement {
     Wall,
@@ -190,7 +225,12 @@ impl DroidController {
         }
     }
 
-    fn next_droid_direction(&mut self) -> DroidDirection {
+    fn is_explored(&self) -> bool {
+        self.unexplored_fields.is_empty()
+    }
+
+    /// Direction of the next move or `None` once every reachable field has been explored.
+    fn next_droid_direction(&mut self) -> Option<DroidDirection> {
         while self.unexplored_fields.back() == Some(&self.droid_position) {
             self.unexplored_fields.pop_back();
         }
@@ -209,19 +249,9 @@ impl DroidController {
             let command_seq = self.find_path_to(self.droid_position, next_target).unwrap();
 
             self.current_direction = command_seq[0];
-            self.current_direction
+            Some(self.current_direction)
         } else {
-            let oxygen = self.droid_map.find(DroidMapElement::Oxygen);
-            if let Some(oxygen) = oxygen {
-                let command_seq = self.find_path_to(Point(0, 0), oxygen).unwrap();
-                println!("Shortest path to oxygen leak: {}", command_seq.len());
-
-                let longest_path_to_any = self.find_longest_path_to_any(oxygen);
-                println!("Time to fill the area: {}", longest_path_to_any);
-            } else {
-                println!("Could not find path to oxygen.");
-            }
-            panic!("Terminated exploration.");
+            None
         }
     }
 
@@ -353,6 +383,7 @@ impl DroidController {
 
 struct DroidOutputReader {
     droid_controller: Rc<RefCell<DroidController>>,
+    draw: bool,
 }
 
 #[derive(Debug)]
@@ -422,8 +453,11 @@ impl FromStr for DroidStatus {
 }
 
 impl DroidOutputReader {
-    fn new(droid_controller: Rc<RefCell<DroidController>>) -> DroidOutputReader {
-        DroidOutputReader { droid_controller }
+    fn new(droid_controller: Rc<RefCell<DroidController>>, draw: bool) -> DroidOutputReader {
+        DroidOutputReader {
+            droid_controller,
+            draw,
+        }
     }
 }
 
@@ -436,8 +470,10 @@ impl aoc_2019_13::OutputReader for DroidOutputReader {
         Ok(())
     }
 
-    fn finalize_input_sequence(&self) {
-        self.droid_controller.borrow().draw();
+    fn finalize_input_sequence(&mut self) {
+        if self.draw {
+            self.droid_controller.borrow().draw();
+        }
     }
 }
 
@@ -497,10 +533,13 @@ impl DroidDirectionController {
 
 impl io::Read for DroidDirectionController {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let direction = self.controller.borrow_mut().next_droid_direction();
-
-        let command = DroidDirectionController::translate_into_command(direction);
-        (&command[..]).read(buf)
+        match self.controller.borrow_mut().next_droid_direction() {
+            Some(direction) => {
+                let command = DroidDirectionController::translate_into_command(direction);
+                (&command[..]).read(buf)
+            }
+            None => Ok(0),
+        }
     }
 }
 
@@ -510,6 +549,32 @@ pub fn create_droid_program_from_input(path: &str) -> DroidProgram {
     DroidProgram::new(program)
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = DroidProgram;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<DroidProgram> {
+        Ok(DroidProgram::new(aoc_2019_2::parse_memory(lines)?))
+    }
+
+    fn part_one(&self, input: &DroidProgram) -> AocResult<String> {
+        Ok(input.explore(false)?.distance.to_string())
+    }
+
+    fn part_two(&self, input: &DroidProgram) -> AocResult<String> {
+        Ok(input.explore(false)?.fill_time.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
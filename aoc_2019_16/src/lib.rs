@@ -1,3 +1,6 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
 type Element = i32;
 
 pub struct FFT<'a> {
@@ -5,46 +8,105 @@ pub struct FFT<'a> {
 }
 
 impl<'a> FFT<'a> {
-    const BASE_FREQUENCY: [Element; 4] = [0, 1, 0, -1];
-
     pub fn new(input: &'a Vec<Element>) -> FFT {
         FFT { input }
     }
 
     pub fn calculate(&self, num_phases: usize) -> Vec<Element> {
-        let length = self.input.len();
         let mut result = self.input.clone();
 
-        for i in 0..num_phases {
-            let new_result = (0..length)
-                .map(|idx| self.calculate_entry(&result, idx))
-                .collect();
-
-            result = new_result;
+        for _ in 0..num_phases {
+            result = FFT::phase(&result);
         }
 
         result
     }
 
-    fn calculate_entry(&self, input: &Vec<Element>, idx: usize) -> Element {
-        let mut result = 0;
-
-        for (n, x) in input.iter().enumerate() {
-            result += FFT::twiddle(n, idx) * x;
+    /// Applies one phase to a suffix of the signal. Output digit `k` only depends on the input
+    /// digits from `k` on, so a suffix can be transformed on its own. `offset` is the position
+    /// of the suffix in the whole signal.
+    ///
+    /// The pattern of output digit `k` consists of runs of length `k + 1`, so every run is summed
+    /// in constant time from prefix sums. With at most `n / (k + 1)` runs per digit, a phase
+    /// takes O(n log n).
+    fn phase_from(suffix: &[Element], offset: usize) -> Vec<Element> {
+        let length = offset + suffix.len();
+        let mut prefix_sums = vec![0i64; suffix.len() + 1];
+
+        for (idx, &value) in suffix.iter().enumerate() {
+            prefix_sums[idx + 1] = prefix_sums[idx] + value as i64;
         }
 
-        Element::abs(result % 10)
+        // sum of the digits at positions [from, to) of the whole signal
+        let sum = |from: usize, to: usize| {
+            prefix_sums[to.min(length) - offset] - prefix_sums[from.min(length) - offset]
+        };
+
+        (offset..length)
+            .map(|k| {
+                let run = k + 1;
+                let mut total = 0;
+                let mut start = k;
+
+                while start < length {
+                    total += sum(start, start + run);
+                    total -= sum(start + 2 * run, start + 3 * run);
+                    start += 4 * run;
+                }
+
+                (total % 10).abs() as Element
+            })
+            .collect()
+    }
+
+    fn phase(input: &[Element]) -> Vec<Element> {
+        FFT::phase_from(input, 0)
     }
 
-    fn twiddle(n: usize, k: usize) -> Element {
-        let remainder = (n + 1) / (k + 1) % 4;
+    /// Reads `count` digits starting at `offset` after `num_phases` phases. Only the digits from
+    /// `offset` on are transformed.
+    ///
+    /// In the second half of the signal the pattern of every output digit is one for all later
+    /// digits, so a phase is a suffix sum. After `K` phases the digit at distance `d` contributes
+    /// with the binomial coefficient C(K - 1 + d, d), which is computed mod 10 from Lucas'
+    /// theorem for the primes 2 and 5.
+    pub fn digits_at(&self, offset: usize, count: usize, num_phases: usize) -> Vec<Element> {
+        let length = self.input.len();
+        let end = (offset + count).min(length);
+
+        if offset >= length {
+            return Vec::new();
+        }
+
+        if num_phases == 0 {
+            return self.input[offset..end].to_vec();
+        }
+
+        if 2 * offset < length {
+            let mut suffix = self.input[offset..].to_vec();
+
+            for _ in 0..num_phases {
+                suffix = FFT::phase_from(&suffix, offset);
+            }
 
-        match remainder {
-            0 | 2 => 0,
-            1 => 1,
-            3 => -1,
-            _ => panic!("Illegal remainder")
+            return suffix[..end - offset].to_vec();
         }
+
+        let coefficients: Vec<Element> = (0..length - offset)
+            .map(|distance| binomial_mod_10(num_phases - 1 + distance, distance))
+            .collect();
+
+        (offset..end)
+            .map(|k| {
+                let total: Element = self.input[k..]
+                    .iter()
+                    .zip(&coefficients)
+                    .map(|(value, coefficient)| value * coefficient % 10)
+                    .sum();
+
+                total % 10
+            })
+            .collect()
     }
 
     pub fn calculate_fast(&self, num_phases: usize) -> Vec<Element> {
@@ -68,17 +130,60 @@ impl<'a> FFT<'a> {
     }
 }
 
-pub fn split_string_into_digits(content: String) -> Result<Vec<Element>, String> {
+/// C(n, k) mod 10, combined from the remainders mod 2 and mod 5.
+fn binomial_mod_10(n: usize, k: usize) -> Element {
+    let mod_2 = lucas(n, k, 2);
+    let mod_5 = lucas(n, k, 5);
+
+    (5 * mod_2 + 6 * mod_5) % 10
+}
+
+/// C(n, k) mod `prime` as the product of the binomials of the base `prime` digits of n and k.
+fn lucas(mut n: usize, mut k: usize, prime: usize) -> Element {
+    let mut result = 1;
+
+    while k > 0 {
+        let (n_digit, k_digit) = (n % prime, k % prime);
+
+        if k_digit > n_digit {
+            return 0;
+        }
+
+        result = result * small_binomial(n_digit, k_digit) % prime;
+        n /= prime;
+        k /= prime;
+    }
+
+    result as Element
+}
+
+fn small_binomial(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+pub fn split_string_into_digits(content: String) -> AocResult<Vec<Element>> {
     const RADIX: u32 = 10;
-    content
-        .trim()
-        .chars()
-        .map(|chr| {
-            chr.to_digit(RADIX)
-                .map(|i| i as Element)
-                .ok_or(format!("Could not parse char {} as digit.", chr))
-        })
-        .collect()
+    let mut digits = Vec::new();
+
+    for (line, text) in content
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| !text.trim().is_empty())
+    {
+        for (column, chr) in text.trim_end().chars().enumerate() {
+            let digit = chr.to_digit(RADIX).ok_or_else(|| {
+                AocError::parse_at(
+                    line + 1,
+                    column + 1,
+                    &format!("Could not parse char {} as digit.", chr),
+                )
+            })?;
+
+            digits.push(digit as Element);
+        }
+    }
+
+    Ok(digits)
 }
 
 pub struct PartTwoSolver {
@@ -100,16 +205,56 @@ impl PartTwoSolver {
         }
     }
 
+    /// Reads the message at the offset given by the first seven digits, wherever it lies.
     pub fn calculate(&self, num_phases: usize) -> Vec<Element> {
         let fft = FFT::new(&self.input);
-        let output = fft.calculate_fast(num_phases);
         let start = self
             .input
             .iter()
             .take(7)
             .fold(0, |acc, &value| acc * 10 + value) as usize;
 
-        output[start..(start + 8)].to_vec()
+        fft.digits_at(start, 8, num_phases)
+    }
+}
+
+fn digits_to_string(digits: &[Element]) -> String {
+    digits.iter().map(|digit| digit.to_string()).collect()
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Element>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Element>> {
+        split_string_into_digits(lines.join("\n"))
+    }
+
+    fn part_one(&self, input: &Vec<Element>) -> AocResult<String> {
+        let output = FFT::new(input).digits_at(0, 8, 100);
+
+        Ok(digits_to_string(&output))
+    }
+
+    fn part_two(&self, input: &Vec<Element>) -> AocResult<String> {
+        let output = PartTwoSolver::new(input, 10_000).calculate(100);
+
+        if output.len() < 8 {
+            return Err(AocError::invalid_input(
+                "The message offset lies beyond the end of the signal.",
+            ));
+        }
+
+        Ok(digits_to_string(&output))
     }
 }
 
@@ -167,6 +312,68 @@ mod tests {
         run_part_2_fft_test(input, expected_output);
     }
 
+    #[test]
+    fn phase_matches_pattern_definition() {
+        let input = split_string_into_digits("12345678".into()).unwrap();
+        let after_one_phase = split_string_into_digits("48226158".into()).unwrap();
+
+        assert_eq!(FFT::new(&input).calculate(1), after_one_phase);
+    }
+
+    #[test]
+    fn digits_at_any_offset() {
+        let input = split_string_into_digits("69317163492948606335995924319873".into()).unwrap();
+        let fft = FFT::new(&input);
+        let expected = fft.calculate(100);
+
+        for offset in 0..input.len() {
+            assert_eq!(
+                fft.digits_at(offset, 8, 100),
+                expected[offset..(offset + 8).min(input.len())].to_vec()
+            );
+        }
+
+        assert_eq!(fft.digits_at(3, 4, 0), input[3..7].to_vec());
+        assert!(fft.digits_at(40, 8, 100).is_empty());
+    }
+
+    #[test]
+    fn binomials_mod_10() {
+        for n in 0..40 {
+            let mut binomial: u128 = 1;
+
+            for k in 0..=n {
+                assert_eq!(
+                    binomial_mod_10(n, k) as u128,
+                    binomial % 10,
+                    "C({}, {})",
+                    n,
+                    k
+                );
+                binomial = binomial * (n - k) as u128 / (k + 1) as u128;
+            }
+        }
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let err = split_string_into_digits("0123\n45x6".into()).unwrap_err();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((2, 3))
+        );
+
+        let err = split_string_into_digits("\n0123\n\n45x6\n".into()).unwrap_err();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((4, 3))
+        );
+    }
+
     fn run_fft_test(input: &str, expected_output: &str) {
         let input = split_string_into_digits(input.into()).unwrap();
         let fft = FFT::new(&input);
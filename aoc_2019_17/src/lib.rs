@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use crate::CommandSequence::{Comma, Command};
 use crate::Direction::{East, North, South, West};
 use crate::MapElement::{Char, Robot, Space, Wall};
@@ -346,6 +348,25 @@ impl VacuumCleaner {
 
         computer.compute();
     }
+
+    /// Runs the movement routine without video feed and returns the amount of collected dust.
+    pub fn collect_dust(&self) -> AocResult<i64> {
+        let controller = VacuumController::new(Rc::new(RefCell::new(VacuumCleanerController::new())));
+        let (command, _) = controller.get_vacuum_controller_command();
+        let inputs = String::from_utf8_lossy(&command)
+            .lines()
+            .map(|line| {
+                line.parse().map_err(|_| {
+                    AocError::invalid_input(&format!("Controller input {} is not a number.", line))
+                })
+            })
+            .collect::<AocResult<Vec<i64>>>()?;
+
+        aoc_2019_2::compute_with_inputs(self.program.clone(), &inputs)?
+            .last()
+            .cloned()
+            .ok_or_else(|| AocError::no_solution("Vacuum cleaner did not report collected dust."))
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -587,6 +608,43 @@ impl io::Read for VacuumController {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        17
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        aoc_2019_2::parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        let map = Scaffolding::new(input)
+            .extract_scaffolding()
+            .map_err(|err| AocError::invalid_input(&err))?;
+        let result: isize = find_intersections(&map)
+            .iter()
+            .map(|&Point(x, y)| x * y)
+            .sum();
+
+        Ok(result.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        let mut program = input.clone();
+        program[0] = 2;
+
+        Ok(VacuumCleaner::new(&program).collect_dust()?.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,11 @@
 use aoc_2019_18::solve_map;
 
-fn main_other() {
+fn main() {
     let map = aoc_2019_18::read_map("input.txt").unwrap();
 
-    let result = solve_map(&map);
-    println!("{}", result)
-}
\ No newline at end of file
+    let solution = solve_map(&map).unwrap();
+    println!("{} steps collecting {:?}", solution.steps, solution.key_order);
+
+    let solution = solve_map(&map.split_into_quadrants().unwrap()).unwrap();
+    println!("{} steps with four robots collecting {:?}", solution.steps, solution.key_order);
+}
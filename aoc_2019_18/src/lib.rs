@@ -1,10 +1,12 @@
-use aoc_common::{GenericResult, GenericError};
+use aoc_common::solution::Solution;
+use aoc_common::{AocResult, AocError};
 use std::convert::{TryInto, TryFrom};
 use core::{fmt, slice, iter};
 use std::fmt::Formatter;
 use aoc_common::math::Point;
-use std::collections::{HashSet, BinaryHeap, HashMap};
-use std::cmp::{Ordering, Reverse};
+use aoc_collections::{BitSet, IndexedHeap};
+use std::collections::hash_map::Entry;
+use std::collections::{HashSet, HashMap, VecDeque};
 use std::iter::Enumerate;
 
 pub struct Map {
@@ -25,13 +27,60 @@ impl Map {
 
         let index = x + y * self.width as isize;
 
-        if index < 0 {
+        if index < 0 || x < 0 || x >= self.width as isize {
             None
         } else {
             self.map.get(index as usize).map(MapElement::to_owned)
         }
     }
 
+    fn set(&mut self, point: Point, map_element: MapElement) {
+        let Point(x, y) = point;
+
+        self.map[x as usize + y as usize * self.width] = map_element;
+    }
+
+    fn entrances(&self) -> Vec<Point> {
+        self.pos_iter()
+            .filter(|(_, &map_element)| map_element == MapElement::Entrance)
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    /// Splits a vault with a single entrance into four quadrants by walling up the entrance and
+    /// the passages around it and placing an entrance in every diagonal corner. Maps which
+    /// already have four entrances are returned unchanged.
+    pub fn split_into_quadrants(&self) -> AocResult<Map> {
+        let entrances = self.entrances();
+
+        if entrances.len() == 4 {
+            return Ok(Map::new(self.map.clone(), self.width));
+        }
+
+        let center = match entrances.as_slice() {
+            [center] => *center,
+            _ => return Err(AocError::invalid_input(&format!("Expected a single entrance but found {}.", entrances.len()))),
+        };
+
+        let mut map = Map::new(self.map.clone(), self.width);
+
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let point = center + Point(dx, dy);
+
+                match self.get(point) {
+                    Some(MapElement::Passage) | Some(MapElement::Entrance) => {
+                        let map_element = if dx != 0 && dy != 0 { MapElement::Entrance } else { MapElement::Wall };
+                        map.set(point, map_element);
+                    }
+                    _ => return Err(AocError::invalid_input("The entrance needs to be surrounded by open passages.")),
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
     fn iter(&self) -> MapIterator<'_> {
         MapIterator::new(self.map.iter())
     }
@@ -147,21 +196,21 @@ impl fmt::Display for MapElement {
     }
 }
 
-pub fn read_map(filename: &str) -> GenericResult<Map> {
+pub fn read_map(filename: &str) -> AocResult<Map> {
     let lines = aoc_common::read_raw_file_content(filename)?;
 
     read_map_from_lines(lines)
 }
 
-fn read_map_from_lines(lines: Vec<String>) -> GenericResult<Map> {
+fn read_map_from_lines(lines: Vec<String>) -> AocResult<Map> {
     let width = lines[0].len();
-    let result: Result<Vec<MapElement>, String> = lines.into_iter().flat_map(|line| {
-        line.chars().map(|c| c.try_into()).collect::<Vec<Result<MapElement, String>>>()
+    let result: AocResult<Vec<MapElement>> = lines.iter().enumerate().flat_map(|(y, line)| {
+        line.chars().enumerate().map(move |(x, c)| c.try_into().map_err(|err: String| AocError::parse_at(y + 1, x + 1, &err)))
     }).collect();
     Ok(Map::new(result?, width))
 }
 
-fn read_from_string(map: &str) -> GenericResult<Map> {
+fn read_from_string(map: &str) -> AocResult<Map> {
     let lines: Vec<String> = map.split("\n").map(|l| l.to_string()).collect();
 
     read_map_from_lines(lines)
@@ -171,6 +220,9 @@ struct Solver<'a> {
     map: &'a Map,
 }
 
+/// Positions of all robots in the compressed graph together with the collected keys.
+type State = (Vec<usize>, KeySet);
+
 impl<'a> Solver<'a> {
     fn new(map: &'a Map) -> Solver {
         Solver {
@@ -178,178 +230,190 @@ impl<'a> Solver<'a> {
         }
     }
 
-    fn solve(&self) -> usize {
+    /// Finds the fewest steps to collect all keys with one robot per entrance where only one
+    /// robot moves at a time.
+    fn solve(&self) -> Option<VaultSolution> {
         let graph = Graph::from_map(self.map);
-        let number_keys = graph.nodes.iter().filter(|&node| node.is_key()).count();
+        let all_keys: KeySet = graph.nodes.iter().filter_map(|node| node.key()).map(key_index).collect();
 
         let mut visited = HashSet::new();
-        let mut candidates = BinaryHeap::new();
-        candidates.push(Reverse(SolutionCandidate::new(GraphElement::Entrance, 0, HashSet::new())));
-
-        while let Some(Reverse(candidate)) = candidates.pop() {
-            println!("{:?}", candidate);
-            if candidate.keys.len() == number_keys {
-                return candidate.steps;
-            } else {
-                if !visited.contains(&(candidate.position, candidate.get_sorted_keys())) {
-                    visited.insert((candidate.position, candidate.get_sorted_keys()));
-                    if let Some(neighbours) = graph.neighbour(&candidate.position) {
-                        for neighbour in neighbours {
-                            let can_go = match neighbour.node {
-                                GraphElement::Entrance => true,
-                                GraphElement::Door(door) => candidate.keys.contains(&door.to_ascii_lowercase()),
-                                GraphElement::Key(key) => true
-                            };
-
-                            if can_go {
-                                let new_key_set = match neighbour.node {
-                                    GraphElement::Key(key) => {
-                                        let mut new_key_set = candidate.keys.clone();
-                                        new_key_set.insert(key);
-                                        new_key_set
-                                    },
-                                    _ => candidate.keys.clone(),
-                                };
-
-                                candidates.push(Reverse(SolutionCandidate::new(neighbour.node, candidate.steps + neighbour.distance, new_key_set)));
-                            }
-                        }
+        let mut predecessors: HashMap<State, (State, usize, usize)> = HashMap::new();
+        let mut candidates = IndexedHeap::new();
+        let initial: State = ((0..graph.number_entrances).collect(), KeySet::new());
+        candidates.push(initial, 0);
+
+        while let Some(((positions, keys), steps)) = candidates.pop() {
+            if keys == all_keys {
+                return Some(self.reconstruct(&graph, &predecessors, (positions, keys), steps));
+            }
+
+            visited.insert((positions.clone(), keys));
+
+            for (robot, &position) in positions.iter().enumerate() {
+                for (index, edge) in graph.edges[position].iter().enumerate() {
+                    if !edge.doors.is_subset(&keys) {
+                        continue;
+                    }
+
+                    let new_keys = match graph.nodes[edge.target] {
+                        GraphElement::Key(key) => keys.with(key_index(key)),
+                        GraphElement::Entrance(_) => keys,
+                    };
+                    let mut new_positions = positions.clone();
+                    new_positions[robot] = edge.target;
+                    let next = (new_positions, new_keys);
+
+                    if !visited.contains(&next) && candidates.push(next.clone(), steps + edge.distance) {
+                        predecessors.insert(next, ((positions.clone(), keys), robot, index));
                     }
                 }
             }
         }
 
-        0
+        None
     }
-}
 
-#[derive(PartialEq, Eq, Debug)]
-struct SolutionCandidate {
-    position: GraphElement,
-    steps: usize,
-    keys: HashSet<char>,
-}
+    fn reconstruct(&self, graph: &Graph, predecessors: &HashMap<State, (State, usize, usize)>, last: State, steps: usize) -> VaultSolution {
+        let mut moves = Vec::new();
+        let mut state = last;
 
-impl PartialOrd for SolutionCandidate {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.steps.partial_cmp(&other.steps)
-    }
-}
+        while let Some((previous, robot, index)) = predecessors.get(&state) {
+            moves.push((*robot, &graph.edges[previous.0[*robot]][*index]));
+            state = previous.clone();
+        }
 
-impl Ord for SolutionCandidate {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.steps.cmp(&other.steps)
-    }
-}
+        moves.reverse();
+
+        let mut key_order = Vec::new();
+        let mut paths: Vec<Vec<Point>> = graph.positions[..graph.number_entrances].iter().map(|&entrance| vec![entrance]).collect();
 
-impl SolutionCandidate {
-    fn new(position: GraphElement, steps: usize, keys: HashSet<char>) -> SolutionCandidate {
-        SolutionCandidate {
-            position,
+        for (robot, edge) in moves {
+            if let Some(key) = graph.nodes[edge.target].key() {
+                if !key_order.contains(&key) {
+                    key_order.push(key);
+                }
+            }
+
+            paths[robot].extend_from_slice(&edge.path);
+        }
+
+        VaultSolution {
             steps,
-            keys
+            key_order,
+            paths,
         }
     }
+}
 
-    fn get_sorted_keys(&self) -> Vec<char> {
-        let mut keys: Vec<char> = self.keys.iter().map(|&x| x).collect();
-        keys.sort();
+/// Collected keys where `a` is stored as 0.
+type KeySet = BitSet<26>;
 
-        keys
-    }
+fn key_index(key: char) -> usize {
+    (key as u8 - b'a') as usize
+}
+
+/// Fewest steps to collect all keys, the order in which they are collected and the path of
+/// every robot starting at its entrance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VaultSolution {
+    pub steps: usize,
+    pub key_order: Vec<char>,
+    pub paths: Vec<Vec<Point>>,
 }
 
-pub fn solve_map(map: &Map) -> usize {
+pub fn solve_map(map: &Map) -> AocResult<VaultSolution> {
     let solver = Solver::new(map);
 
-    solver.solve()
+    solver.solve().ok_or_else(|| AocError::no_solution("Not all keys can be collected."))
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
 enum GraphElement {
     Key(char),
-    Door(char),
-    Entrance,
+    Entrance(usize),
 }
 
 impl GraphElement {
-    fn is_key(&self) -> bool {
+    fn key(&self) -> Option<char> {
         match self {
-            GraphElement::Key(_) => true,
-            _ => false,
-        }
-    }
-}
-
-impl TryFrom<&MapElement> for GraphElement {
-    type Error = String;
-
-    fn try_from(value: &MapElement) -> Result<Self, Self::Error> {
-        match value {
-            MapElement::Door(door) => Ok(GraphElement::Door(*door)),
-            MapElement::Key(key) => Ok(GraphElement::Key(*key)),
-            MapElement::Entrance => Ok(GraphElement::Entrance),
-            _ => Err(format!("Cannot convert {} into a GraphElement.", value))
+            GraphElement::Key(key) => Some(*key),
+            GraphElement::Entrance(_) => None,
         }
     }
 }
 
+/// The map compressed to its entrances and keys. Doors are no nodes of their own but the keys
+/// they need are stored on the edges leading through them.
 #[derive(Debug)]
 struct Graph {
-    nodes: HashSet<GraphElement>,
-    neighbours: HashMap<GraphElement, HashSet<Neighbour>>,
+    /// Entrances come first, numbered like the robots starting at them.
+    nodes: Vec<GraphElement>,
+    positions: Vec<Point>,
+    edges: Vec<Vec<Edge>>,
+    number_entrances: usize,
 }
 
 impl Graph {
     fn from_map(map: &Map) -> Graph {
-        let neighbours: Result<HashMap<GraphElement, HashSet<Neighbour>>, String> = map.pos_iter()
-            .filter(|(_, map_element)| Graph::is_node_candidate(map_element))
-            .map(|(starting_point, map_element)| (map_element.try_into().map(|m| (m, Graph::find_neighbours(starting_point, map)))))
-            .collect();
-
-        let neighbours = neighbours.unwrap();
+        let entrances = map.entrances();
+        let keys = map.pos_iter()
+            .filter_map(|(point, map_element)| match map_element {
+                MapElement::Key(key) => Some((point, GraphElement::Key(*key))),
+                _ => None,
+            });
+        let (positions, nodes): (Vec<Point>, Vec<GraphElement>) = entrances.iter()
+            .enumerate()
+            .map(|(robot, &entrance)| (entrance, GraphElement::Entrance(robot)))
+            .chain(keys)
+            .unzip();
+
+        let index: HashMap<Point, usize> = positions.iter().enumerate().map(|(node, &point)| (point, node)).collect();
+        let edges = positions.iter().map(|&position| Graph::find_edges(position, map, &index)).collect();
 
         Graph {
-            nodes: neighbours.keys().map(|&x| x).collect(),
-            neighbours: neighbours,
+            nodes,
+            positions,
+            edges,
+            number_entrances: entrances.len(),
         }
     }
 
-    fn is_node_candidate(map_element: &MapElement) -> bool {
-        match map_element {
-            MapElement::Door(_) | MapElement::Key(_) | MapElement::Entrance => true,
-            _ => false
-        }
-    }
+    /// Breadth-first search from `start` to every key which can be reached without passing
+    /// another key. Doors on the way are recorded as the keys they need.
+    fn find_edges(start: Point, map: &Map, index: &HashMap<Point, usize>) -> Vec<Edge> {
+        let directions = [Point(0, 1), Point(0, -1), Point(1, 0), Point(-1, 0)];
+        let mut search_front = VecDeque::new();
+        let mut parents = HashMap::new();
+        let mut result = Vec::new();
+
+        search_front.push_back(Candidate::new(start, 0, KeySet::new()));
+        parents.insert(start, start);
+
+        while let Some(candidate) = search_front.pop_front() {
+            let position = candidate.new_position;
+
+            if let (Some(MapElement::Key(_)), true) = (map.get(position), position != start) {
+                result.push(Edge {
+                    target: index[&position],
+                    distance: candidate.steps,
+                    doors: candidate.doors,
+                    path: Graph::path_to(position, start, &parents),
+                });
+                continue;
+            }
 
-    fn find_neighbours(starting_point: Point, map: &Map) -> HashSet<Neighbour> {
-        let directions = vec![Point(0, 1), Point(0, -1), Point(1, 0), Point(-1, 0)];
-        let mut search_front = Vec::new();
-        let mut visited = HashSet::new();
-        let mut result = HashSet::new();
-
-        search_front.push(Candidate::new(starting_point, 0));
-        visited.insert(starting_point);
-
-        while let Some(next_candidate) = search_front.pop() {
-            if let Some(map_element) = map.get(next_candidate.new_position) {
-                if Graph::is_node_candidate(&map_element) && next_candidate.new_position != starting_point {
-                    result.insert(Neighbour::new((&map_element).try_into().unwrap(), next_candidate.steps));
-                } else {
-                    let next_steps: Vec<Point> = directions.iter().map(|&direction| next_candidate.new_position + direction).collect();
-
-                    let next_steps: Vec<Point> = next_steps.into_iter()
-                        .filter(|next_step|
-                            !visited.contains(next_step) &&
-                                map
-                                    .get(*next_step)
-                                    .map_or(false, |element| element != MapElement::Wall))
-                        .collect();
-
-                    for next_step in next_steps {
-                        visited.insert(next_step);
-                        search_front.push(Candidate::new(next_step, next_candidate.steps + 1))
-                    }
+            for &direction in &directions {
+                let next_step = position + direction;
+
+                let doors = match map.get(next_step) {
+                    None | Some(MapElement::Wall) => continue,
+                    Some(MapElement::Door(door)) => candidate.doors.with(key_index(door.to_ascii_lowercase())),
+                    Some(_) => candidate.doors,
+                };
+
+                if let Entry::Vacant(entry) = parents.entry(next_step) {
+                    entry.insert(position);
+                    search_front.push_back(Candidate::new(next_step, candidate.steps + 1, doors));
                 }
             }
         }
@@ -357,37 +421,69 @@ impl Graph {
         result
     }
 
-    fn neighbour(&self, graph_element: &GraphElement) -> Option<&HashSet<Neighbour>> {
-        self.neighbours.get(graph_element)
+    /// Positions after `start` up to and including `end`.
+    fn path_to(end: Point, start: Point, parents: &HashMap<Point, Point>) -> Vec<Point> {
+        let mut path = vec![end];
+        let mut position = end;
+
+        while parents[&position] != start {
+            position = parents[&position];
+            path.push(position);
+        }
+
+        path.reverse();
+        path
     }
 }
 
 struct Candidate {
     new_position: Point,
     steps: usize,
+    doors: KeySet,
 }
 
 impl Candidate {
-    fn new(position: Point, steps: usize) -> Candidate {
+    fn new(position: Point, steps: usize, doors: KeySet) -> Candidate {
         Candidate {
             new_position: position,
             steps,
+            doors,
         }
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Debug)]
-struct Neighbour {
-    node: GraphElement,
+#[derive(Debug)]
+struct Edge {
+    target: usize,
     distance: usize,
+    /// Keys needed to open the doors on the way.
+    doors: KeySet,
+    path: Vec<Point>,
 }
 
-impl Neighbour {
-    fn new(node: GraphElement, distance: usize) -> Neighbour {
-        Neighbour{
-            node,
-            distance,
-        }
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Map;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        18
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Map> {
+        read_map_from_lines(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Map) -> AocResult<String> {
+        Ok(solve_map(input)?.steps.to_string())
+    }
+
+    fn part_two(&self, input: &Map) -> AocResult<String> {
+        Ok(solve_map(&input.split_into_quadrants()?)?.steps.to_string())
     }
 }
 
@@ -425,10 +521,79 @@ mod tests {
         run_test(&map, 81);
     }
 
+    #[test]
+    fn test_key_order_and_path() {
+        let solution = solve_map(&get_simple_map()).unwrap();
+
+        assert_eq!(solution.key_order, vec!['a', 'b']);
+        assert_eq!(solution.paths.len(), 1);
+        assert_eq!(solution.paths[0].len(), solution.steps + 1);
+        assert_eq!(solution.paths[0].first(), Some(&Point(5, 1)));
+        assert_eq!(solution.paths[0].last(), Some(&Point(1, 1)));
+        assert!(solution.paths[0].windows(2).all(|step| step[0].manhattan_distance(step[1]) == 1));
+    }
+
+    #[test]
+    fn test_split_vault() {
+        let map = read_from_string(r"#######
+#a.#Cd#
+##...##
+##.@.##
+##...##
+#cB#Ab#
+#######").unwrap();
+        let solution = solve_map(&map.split_into_quadrants().unwrap()).unwrap();
+
+        assert_eq!(solution.steps, 8);
+        assert_eq!(solution.key_order, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(solution.paths.iter().map(|path| path.len() - 1).sum::<usize>(), 8);
+    }
+
+    #[test]
+    fn test_four_robots() {
+        let vaults = [(r"###############
+#d.ABC.#.....a#
+######@#@######
+###############
+######@#@######
+#b.....#.....c#
+###############", 24), (r"#############
+#DcBa.#.GhKl#
+#.###@#@#I###
+#e#d#####j#k#
+###C#@#@###J#
+#fEbA.#.FgHi#
+#############", 32), (r"#############
+#g#f.D#..h#l#
+#F###e#E###.#
+#dCba@#@BcIJ#
+#############
+#nK.L@#@G...#
+#M###N#H###.#
+#o#m..#i#jk.#
+#############", 72)];
+
+        for (vault, steps) in vaults.iter() {
+            let map = read_from_string(vault).unwrap().split_into_quadrants().unwrap();
+
+            assert_eq!(solve_map(&map).unwrap().steps, *steps);
+        }
+    }
+
+    #[test]
+    fn test_unreachable_key() {
+        let map = read_from_string(r"#######
+#@.A.a#
+#######").unwrap();
+
+        assert!(solve_map(&map).is_err());
+        assert!(map.split_into_quadrants().is_err());
+    }
+
     fn run_test(map: &Map, expected_result: usize) {
         let solver = Solver::new(map);
 
-        assert_eq!(solver.solve(), expected_result);
+        assert_eq!(solver.solve().map(|solution| solution.steps), Some(expected_result));
     }
 
     fn get_simple_map() -> Map {
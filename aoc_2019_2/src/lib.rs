@@ -1,3 +1,6 @@
+use aoc_common::parse::{parse_at, signed};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::fs;
 use std::io;
 use std::io::BufRead;
@@ -145,7 +148,7 @@ impl CommandLike for Command {
         relative_base: &mut usize,
         input: &mut I,
         output: &mut O,
-    ) {
+    ) -> Result<(), String> {
         match *self {
             Stop => (),
             Add(parameter_a, parameter_b, dst) => {
@@ -163,7 +166,11 @@ impl CommandLike for Command {
             Input(dst) => loop {
                 writeln!(output, "{}", INPUT_PREFIX);
                 let mut line = String::new();
-                input.read_line(&mut line);
+                let bytes_read = input.read_line(&mut line).map_err(|err| err.to_string())?;
+
+                if bytes_read == 0 {
+                    return Err("Input ended before the program stopped.".to_string());
+                }
 
                 let parsed_result = line.trim().parse();
 
@@ -213,6 +220,8 @@ impl CommandLike for Command {
                     (*relative_base as i64 + offset.interpret(memory, *relative_base)) as usize
             }
         }
+
+        Ok(())
     }
 }
 
@@ -228,7 +237,7 @@ trait CommandLike {
         relative_base: &mut usize,
         input: &mut I,
         output: &mut O,
-    );
+    ) -> Result<(), String>;
 }
 
 pub fn compute_memory_with_stdin_stdout(memory: Vec<i64>) -> (ComputationResult, Memory) {
@@ -257,21 +266,21 @@ impl<'a, I: io::BufRead, O: io::Write> IntComputer<'a, I, O> {
             let command_result = self.next_command();
 
             let optional_result = command_result
-                .map(|command| {
+                .and_then(|command| {
                     command.execute(
                         &mut self.memory,
                         &mut self.pointer,
                         &mut self.relative_base,
                         &mut self.input,
                         &mut self.output,
-                    );
+                    )?;
 
                     self.pointer += command.command_length() as isize;
 
                     if command.is_stop() {
-                        Some(Success)
+                        Ok(Some(Success))
                     } else {
-                        None
+                        Ok(None)
                     }
                 })
                 .unwrap_or_else(|error| Some(Failure { error }));
@@ -388,6 +397,134 @@ fn parse_input_parameters(
     result
 }
 
+pub fn parse_memory(lines: &[String]) -> AocResult<Vec<i64>> {
+    let mut memory = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let mut column = 1;
+
+        for split in line.split(',') {
+            let value = split.trim();
+
+            if !value.is_empty() {
+                let leading_whitespace = split.len() - split.trim_start().len();
+                memory.push(parse_at(
+                    signed(),
+                    value,
+                    idx + 1,
+                    column + leading_whitespace,
+                )?);
+            }
+
+            column += split.chars().count() + 1;
+        }
+    }
+
+    Ok(memory)
+}
+
+/// Runs the program with the given inputs and returns all values it outputs.
+pub fn compute_with_inputs(memory: Vec<i64>, inputs: &[i64]) -> AocResult<Vec<i64>> {
+    let input: String = inputs.iter().map(|value| format!("{}\n", value)).collect();
+    let mut output = Vec::new();
+    let mut computer = IntComputer::new(memory, input.as_bytes(), &mut output);
+
+    if let Failure { error } = computer.compute() {
+        return Err(AocError::invalid_input(&error));
+    }
+
+    String::from_utf8_lossy(&output)
+        .lines()
+        .filter_map(|line| line.strip_prefix(OUTPUT_PREFIX))
+        .map(|value| {
+            value.trim().parse().map_err(|_| {
+                AocError::invalid_input(&format!("The program output {} is not a number.", value))
+            })
+        })
+        .collect()
+}
+
+fn compute_with_noun_verb(memory: &[i64], noun: i64, verb: i64) -> AocResult<i64> {
+    let mut memory = memory.to_vec();
+    memory[1] = noun;
+    memory[2] = verb;
+
+    let mut output = Vec::new();
+    let mut computer = IntComputer::new(memory, "".as_bytes(), &mut output);
+
+    match computer.compute() {
+        Success => Ok(computer.memory()[0]),
+        Failure { error } => Err(AocError::invalid_input(&error)),
+    }
+}
+
+fn last_output(memory: &[i64], input: i64) -> AocResult<String> {
+    compute_with_inputs(memory.to_vec(), &[input])?
+        .last()
+        .map(|value| value.to_string())
+        .ok_or_else(|| AocError::no_solution("Program did not produce any output."))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        Ok(compute_with_noun_verb(input, 12, 2)?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        for noun in 0..=99 {
+            for verb in 0..=99 {
+                if let Ok(19_690_720) = compute_with_noun_verb(input, noun, verb) {
+                    return Ok((100 * noun + verb).to_string());
+                }
+            }
+        }
+
+        Err(AocError::no_solution("Could not find noun and verb."))
+    }
+}
+
+pub struct Day5Puzzle;
+
+impl Solution for Day5Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        last_output(input, 1)
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        last_output(input, 5)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,6 +557,20 @@ mod tests {
         run_test(input, &output);
     }
 
+    #[test]
+    fn parse_memory_errors_have_positions() {
+        let lines = vec!["1,0,-3,".to_string(), "99, 1x,3".to_string()];
+
+        assert_eq!(parse_memory(&lines[..1]).unwrap(), vec![1, 0, -3]);
+        assert_eq!(
+            parse_memory(&lines)
+                .unwrap_err()
+                .location()
+                .map(|location| (location.line, location.column)),
+            Some((2, 6))
+        );
+    }
+
     fn run_test(input: Vec<i64>, output: &Vec<i64>) {
         let (result, resulting_memory) = compute_memory_with_stdin_stdout(input);
         assert_eq!(result, ComputationResult::Success);
@@ -448,6 +599,13 @@ mod tests {
 
         assert_eq!(result, memory);
     }
+
+    #[test]
+    fn missing_input_fails() {
+        let result = compute_with_inputs(vec![3, 5, 3, 5, 99, 0], &[1]);
+
+        assert!(result.is_err());
+    }
 }
 
 impl Command {
@@ -1,259 +1,271 @@
-use crate::LineDirections::Left;
-use hamcrest2::prelude::*;
-use std::convert::TryInto;
+use aoc_common::math::segment::{Intersection, Polyline};
+use aoc_common::math::Point;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+use std::collections::{BTreeMap, BTreeSet};
 use std::str::FromStr;
 
 #[derive(Debug)]
-enum LineDirections {
-    Right(i32),
-    Left(i32),
-    Up(i32),
-    Down(i32),
+pub enum LineDirections {
+    Right(isize),
+    Left(isize),
+    Up(isize),
+    Down(isize),
 }
 
 impl LineDirections {
     fn to_relative_point(&self) -> Point {
         match *self {
-            LineDirections::Right(right) => Point::new(right, 0),
-            LineDirections::Left(left) => Point::new(-left, 0),
-            LineDirections::Up(up) => Point::new(0, up),
-            LineDirections::Down(down) => Point::new(0, -down),
+            LineDirections::Right(right) => Point::EAST * right,
+            LineDirections::Left(left) => Point::WEST * left,
+            LineDirections::Up(up) => Point::NORTH * up,
+            LineDirections::Down(down) => Point::SOUTH * down,
         }
     }
 }
 
-struct LineSegments {
-    line_segments: Vec<Line>,
+fn create_wire(line_directions: &[LineDirections]) -> Polyline {
+    Polyline::from_moves(
+        Point::ORIGIN,
+        line_directions.iter().map(LineDirections::to_relative_point),
+    )
+    .expect("Wires only consist of horizontal and vertical segments.")
 }
 
-impl LineSegments {
-    pub fn from_line_directions(line_directions: Vec<LineDirections>) -> LineSegments {
-        let mut line_segments = Vec::new();
-        let mut start_point = Point::new(0, 0);
-
-        for line_direction in &line_directions {
-            let end_point = start_point.plus(&line_direction.to_relative_point());
-            line_segments.push(Line::new(start_point, end_point));
-            start_point = end_point;
-        }
-
-        LineSegments { line_segments }
-    }
-}
-
-impl IntoIterator for LineSegments {
-    type Item = Line;
-    type IntoIter = std::vec::IntoIter<Line>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.line_segments.into_iter()
-    }
+/// Meeting of two different wires with the fewest steps each wire needs to reach it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct WireCrossing {
+    pub point: Point,
+    pub wires: (usize, usize),
+    pub steps: (isize, isize),
 }
 
-impl<'a> IntoIterator for &'a LineSegments {
-    type Item = &'a Line;
-    type IntoIter = std::slice::Iter<'a, Line>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        (&self.line_segments).into_iter()
-    }
+/// Point which is reached by several wires together with the fewest steps of each of them,
+/// ordered by wire.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Junction {
+    pub point: Point,
+    pub steps: Vec<(usize, isize)>,
 }
 
-#[derive(Debug)]
-struct Line {
-    start: Point,
-    end: Point,
+pub struct LineIntersectionCalculator {
+    wires: Vec<Polyline>,
 }
 
-impl Line {
-    fn new(start: Point, end: Point) -> Line {
-        Line { start, end }
-    }
-
-    fn calculate_intersections(&self, other: &Line) -> Vec<Point> {
-        let min_self = self.start.min(&self.end);
-        let max_self = self.start.max(&self.end);
-
-        let min_other = other.start.min(&other.end);
-        let max_other = other.start.max(&other.end);
-
-        let start_overlap = min_self.max(&min_other);
-        let end_overlap = max_self.min(&max_other);
-
-        if start_overlap.x == end_overlap.x || start_overlap.y == end_overlap.y {
-            let mut result = Vec::new();
-            for x in start_overlap.x..=end_overlap.x {
-                for y in start_overlap.y..=end_overlap.y {
-                    result.push(Point::new(x, y))
-                }
-            }
-
-            result
-        } else {
-            vec![]
+impl LineIntersectionCalculator {
+    pub fn new(lines: &[Vec<LineDirections>]) -> LineIntersectionCalculator {
+        LineIntersectionCalculator {
+            wires: lines.iter().map(|line| create_wire(line)).collect(),
         }
     }
 
-    fn direction(&self) -> Point {
-        self.end.minus(&self.start)
+    fn calculate_minimal_manhattan_distance_intersection(&self) -> u32 {
+        self.crossings()
+            .iter()
+            .map(|crossing| crossing.point.manhattan() as u32)
+            .min()
+            .unwrap_or(0)
     }
 
-    fn length(&self) -> u32 {
-        self.end.minus(&self.start).manhattan_distance()
+    fn calculate_minimal_steps_intersection(&self) -> u32 {
+        self.crossings()
+            .iter()
+            .map(|crossing| (crossing.steps.0 + crossing.steps.1) as u32)
+            .min()
+            .unwrap_or(0)
     }
-}
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+    /// Crossings of every pair of wires apart from the common origin, ordered by wires and
+    /// point.
+    pub fn crossings(&self) -> Vec<WireCrossing> {
+        let mut crossings = Vec::new();
+
+        for (one, wire_one) in self.wires.iter().enumerate() {
+            for (two, wire_two) in self.wires.iter().enumerate().skip(one + 1) {
+                let points: BTreeSet<Point> = wire_one
+                    .crossings(wire_two)
+                    .into_iter()
+                    .map(|crossing| crossing.point)
+                    .filter(|&point| point != Point::ORIGIN)
+                    .collect();
+
+                crossings.extend(points.into_iter().map(|point| WireCrossing {
+                    point,
+                    wires: (one, two),
+                    steps: (
+                        wire_one.steps_to(point).unwrap_or(0),
+                        wire_two.steps_to(point).unwrap_or(0),
+                    ),
+                }));
+            }
+        }
 
-impl Point {
-    fn new(x: i32, y: i32) -> Point {
-        Point { x, y }
+        crossings
     }
 
-    fn plus(&self, other: &Point) -> Point {
-        Point {
-            x: self.x + other.x,
-            y: self.y + other.y,
+    /// Points apart from the origin which `wire` passes more than once.
+    pub fn self_intersections(&self, wire: usize) -> Vec<Point> {
+        let segments = self.wires[wire].segments();
+        let mut points = BTreeSet::new();
+
+        for (i, segment_one) in segments.iter().enumerate() {
+            for (j, segment_two) in segments.iter().enumerate().skip(i + 1) {
+                let meeting: Vec<Point> = match segment_one.intersection(segment_two) {
+                    Intersection::None => vec![],
+                    Intersection::Point(point) => vec![point],
+                    Intersection::Overlap(overlap) => overlap.points().collect(),
+                };
+
+                // consecutive segments always share the point where they are joined
+                let joint = if j == i + 1 {
+                    Some(segment_one.end())
+                } else {
+                    None
+                };
+
+                points.extend(
+                    meeting
+                        .into_iter()
+                        .filter(|&point| Some(point) != joint && point != Point::ORIGIN),
+                );
+            }
         }
-    }
 
-    fn minus(&self, other: &Point) -> Point {
-        Point {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
+        points.into_iter().collect()
     }
 
-    fn dot_product(&self, other: &Point) -> i32 {
-        self.x * other.x + self.y * other.y
-    }
+    /// Points apart from the origin which are reached by at least `min_wires` wires, ordered by
+    /// point. Every junction joins at least two wires, so smaller values behave like two.
+    pub fn junctions(&self, min_wires: usize) -> Vec<Junction> {
+        let mut junctions: BTreeMap<Point, BTreeMap<usize, isize>> = BTreeMap::new();
 
-    fn min(&self, other: &Point) -> Point {
-        Point {
-            x: self.x.min(other.x),
-            y: self.y.min(other.y),
-        }
-    }
+        for crossing in self.crossings() {
+            let steps = junctions.entry(crossing.point).or_default();
 
-    fn max(&self, other: &Point) -> Point {
-        Point {
-            x: self.x.max(other.x),
-            y: self.y.max(other.y),
+            steps.insert(crossing.wires.0, crossing.steps.0);
+            steps.insert(crossing.wires.1, crossing.steps.1);
         }
-    }
 
-    fn manhattan_distance(&self) -> u32 {
-        (self.x.abs() + self.y.abs()).try_into().unwrap()
+        junctions
+            .into_iter()
+            .filter(|(_, steps)| steps.len() >= min_wires.max(2))
+            .map(|(point, steps)| Junction {
+                point,
+                steps: steps.into_iter().collect(),
+            })
+            .collect()
     }
-}
 
-struct LineIntersectionCalculator {
-    line_segments_one: LineSegments,
-    line_segments_two: LineSegments,
-}
+    /// Renders the wires in different colors with their crossings marked in red and the origin
+    /// in black.
+    pub fn to_svg(&self) -> String {
+        const COLORS: [&str; 6] = [
+            "#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#17becf",
+        ];
 
-impl LineIntersectionCalculator {
-    pub fn new(
-        line_one: Vec<LineDirections>,
-        line_two: Vec<LineDirections>,
-    ) -> LineIntersectionCalculator {
-        let line_segments_one = LineSegments::from_line_directions(line_one);
-        let line_segments_two = LineSegments::from_line_directions(line_two);
+        let points: Vec<Point> = self
+            .wires
+            .iter()
+            .flat_map(|wire| {
+                wire.segments()
+                    .iter()
+                    .flat_map(|segment| vec![segment.start(), segment.end()])
+            })
+            .chain(std::iter::once(Point::ORIGIN))
+            .collect();
+        let min_x = points.iter().map(|point| point.0).min().unwrap_or(0);
+        let max_x = points.iter().map(|point| point.0).max().unwrap_or(0);
+        let min_y = points.iter().map(|point| point.1).min().unwrap_or(0);
+        let max_y = points.iter().map(|point| point.1).max().unwrap_or(0);
+        let size = (max_x - min_x).max(max_y - min_y).max(1);
+        let margin = size / 50 + 1;
+        let stroke = size / 500 + 1;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min_x - margin,
+            min_y - margin,
+            max_x - min_x + 2 * margin,
+            max_y - min_y + 2 * margin
+        );
 
-        LineIntersectionCalculator {
-            line_segments_one,
-            line_segments_two,
+        for (index, wire) in self.wires.iter().enumerate() {
+            let coordinates: Vec<String> = wire
+                .segments()
+                .first()
+                .map(|segment| segment.start())
+                .into_iter()
+                .chain(wire.segments().iter().map(|segment| segment.end()))
+                .map(|Point(x, y)| format!("{},{}", x, y))
+                .collect();
+
+            svg.push_str(&format!(
+                "  <polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"/>\n",
+                coordinates.join(" "),
+                COLORS[index % COLORS.len()],
+                stroke
+            ));
         }
-    }
 
-    fn calculate_minimal_manhattan_distance_intersection(&self) -> u32 {
-        let intersections = self.calculate_intersections();
+        for junction in self.junctions(2) {
+            let Point(x, y) = junction.point;
 
-        intersections
-            .iter()
-            .map(|point_with_distance| point_with_distance.point)
-            .map(|point| point.manhattan_distance())
-            .min()
-            .unwrap_or(0)
-    }
+            svg.push_str(&format!(
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"red\"/>\n",
+                x,
+                y,
+                3 * stroke
+            ));
+        }
 
-    fn calculate_minimal_steps_intersection(&self) -> u32 {
-        let intersections = self.calculate_intersections();
+        svg.push_str(&format!(
+            "  <circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"black\"/>\n</svg>\n",
+            3 * stroke
+        ));
 
-        intersections
-            .iter()
-            .map(|point_with_distance| point_with_distance.distance)
-            .min()
-            .unwrap_or(0)
+        svg
     }
+}
 
-    fn calculate_intersections(&self) -> Vec<PointWithDistance> {
-        let mut intersections = Vec::new();
-        let mut steps_one = 0;
-        for line_segment_one in &self.line_segments_one {
-            let mut steps_two = 0;
+pub fn calculate_minimal_distance_intersections(lines: &[String]) -> AocResult<u32> {
+    let line_intersection_calculator = create_line_intersection_calculator_from_strings(lines)?;
 
-            for line_segment_two in &self.line_segments_two {
-                let new_intersections = line_segment_one.calculate_intersections(line_segment_two);
-                intersections.extend(
-                    new_intersections
-                        .into_iter()
-                        .filter(|point| Point::new(0, 0) != *point)
-                        .map(|point| {
-                            let additional_steps_one =
-                                point.minus(&line_segment_one.start).manhattan_distance();
-                            let additional_steps_two =
-                                point.minus(&line_segment_two.start).manhattan_distance();
-                            PointWithDistance {
-                                point,
-                                distance: steps_one + steps_two + additional_steps_one + additional_steps_two,
-                            }
-                        }),
-                );
+    Ok(line_intersection_calculator.calculate_minimal_manhattan_distance_intersection())
+}
 
-                steps_two += line_segment_two.length();
-            }
+pub fn calculate_minimal_steps_intersections(lines: &[String]) -> AocResult<u32> {
+    let line_intersection_calculator = create_line_intersection_calculator_from_strings(lines)?;
 
-            steps_one += line_segment_one.length();
-        }
-        intersections
-    }
+    Ok(line_intersection_calculator.calculate_minimal_steps_intersection())
 }
 
-struct PointWithDistance {
-    point: Point,
-    distance: u32,
+pub fn create_line_intersection_calculator_from_strings(
+    lines: &[String],
+) -> AocResult<LineIntersectionCalculator> {
+    Ok(LineIntersectionCalculator::new(&parse_wires(lines)?))
 }
 
-pub fn calculate_minimal_distance_intersections(
-    line_one: &str,
-    line_two: &str,
-) -> Result<u32, String> {
-    let line_intersection_calculator = create_line_intersection_calculator_from_strings(line_one, line_two)?;
-
-    Ok(line_intersection_calculator.calculate_minimal_manhattan_distance_intersection())
+/// Parses one wire per line. Blank lines are skipped but still count for error positions.
+pub fn parse_wires(lines: &[String]) -> AocResult<Vec<Vec<LineDirections>>> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse_line_directions(line, index + 1))
+        .collect()
 }
 
-pub fn calculate_minimal_steps_intersections(line_one: &str, line_two: &str) -> Result<u32, String> {
-    let line_inersection_calculator = create_line_intersection_calculator_from_strings(line_one, line_two)?;
+fn parse_line_directions(line: &str, line_number: usize) -> AocResult<Vec<LineDirections>> {
+    let mut column = 1;
 
-    Ok(line_inersection_calculator.calculate_minimal_steps_intersection())
-}
-
-fn create_line_intersection_calculator_from_strings(line_one: &str, line_two: &str) -> Result<LineIntersectionCalculator, String> {
-    let line_one_directions = parse_line_directions(line_one)?;
-    let line_two_directions = parse_line_directions(line_two)?;
-    let line_intersection_calculator =
-        LineIntersectionCalculator::new(line_one_directions, line_two_directions);
-    Ok(line_intersection_calculator)
-}
+    line.split(",")
+        .map(|word| {
+            let word_column = column;
+            column += word.chars().count() + 1;
 
-fn parse_line_directions(line: &str) -> Result<Vec<LineDirections>, String> {
-    line.split(",").map(|word| parse_word(word)).collect()
+            parse_word(word).map_err(|err| AocError::parse_at(line_number, word_column, &err))
+        })
+        .collect()
 }
 
 fn parse_word(word: &str) -> Result<LineDirections, String> {
@@ -263,7 +275,7 @@ fn parse_word(word: &str) -> Result<LineDirections, String> {
 
     let distance: String = chars.collect();
     let distance =
-        i32::from_str(&distance).map_err(|err| format!("Could not parse distance: {}", err))?;
+        isize::from_str(&distance).map_err(|err| format!("Could not parse distance: {}", err))?;
 
     match line_direction_type {
         'R' => Ok(LineDirections::Right(distance)),
@@ -277,58 +289,53 @@ fn parse_word(word: &str) -> Result<LineDirections, String> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::LineDirections::*;
+pub struct Puzzle;
 
-    #[test]
-    fn orthogonal_line_intersection() {
-        let line_one = Line::new(Point::new(4, 4), Point::new(0, 4));
-        let line_two = Line::new(Point::new(2, 2), Point::new(2, 6));
+impl Solution for Puzzle {
+    type Input = Vec<Vec<LineDirections>>;
 
-        let intersections = line_one.calculate_intersections(&line_two);
-
-        assert_eq!(intersections, vec![Point::new(2, 4)]);
+    fn year(&self) -> u16 {
+        2019
     }
 
-    #[test]
-    fn parallel_line_intersection() {
-        let line_one = Line::new(Point::new(3, 3), Point::new(6, 3));
-        let line_two = Line::new(Point::new(4, 2), Point::new(7, 2));
+    fn day(&self) -> u8 {
+        3
+    }
 
-        let intersections = line_one.calculate_intersections(&line_two);
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Vec<LineDirections>>> {
+        let wires = parse_wires(lines)?;
 
-        assert_eq!(intersections, vec![]);
+        if wires.len() >= 2 {
+            Ok(wires)
+        } else {
+            Err(AocError::invalid_input(&format!(
+                "Expected at least 2 wires but found {}.",
+                wires.len()
+            )))
+        }
     }
 
-    #[test]
-    fn parallel_line_intersection_with_gap() {
-        let line_one = Line::new(Point::new(3, 3), Point::new(5, 3));
-        let line_two = Line::new(Point::new(6, 3), Point::new(8, 3));
-
-        let intersections = line_one.calculate_intersections(&line_two);
+    fn part_one(&self, input: &Vec<Vec<LineDirections>>) -> AocResult<String> {
+        let calculator = LineIntersectionCalculator::new(input);
 
-        assert_eq!(intersections, vec!());
+        Ok(calculator
+            .calculate_minimal_manhattan_distance_intersection()
+            .to_string())
     }
 
-    #[test]
-    fn overlapping_line_intersection() {
-        let line_one = Line::new(Point::new(1, 4), Point::new(5, 4));
-        let line_two = Line::new(Point::new(7, 4), Point::new(2, 4));
-
-        let intersections = line_one.calculate_intersections(&line_two);
-
-        assert_that!(
-            &intersections,
-            contains(vec![
-                Point::new(2, 4),
-                Point::new(3, 4),
-                Point::new(4, 4),
-                Point::new(5, 4)
-            ])
-        );
+    fn part_two(&self, input: &Vec<Vec<LineDirections>>) -> AocResult<String> {
+        let calculator = LineIntersectionCalculator::new(input);
+
+        Ok(calculator
+            .calculate_minimal_steps_intersection()
+            .to_string())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LineDirections::*;
 
     #[test]
     fn test_example_one() {
@@ -376,7 +383,7 @@ mod tests {
             Up(6),
             Right(7),
         ];
-        LineIntersectionCalculator::new(line_one, line_two)
+        LineIntersectionCalculator::new(&[line_one, line_two])
     }
 
     #[test]
@@ -421,6 +428,88 @@ mod tests {
             Down(58),
             Right(83),
         ];
-        LineIntersectionCalculator::new(line_one, line_two)
+        LineIntersectionCalculator::new(&[line_one, line_two])
+    }
+
+    fn parse(lines: &[&str]) -> LineIntersectionCalculator {
+        let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+
+        create_line_intersection_calculator_from_strings(&lines).unwrap()
+    }
+
+    #[test]
+    fn test_multiple_wires() {
+        let calculator = parse(&["R8,U5,L5,D3", "U7,R6,D4,L4", "U5,R10"]);
+        let crossings = calculator.crossings();
+
+        assert_eq!(
+            crossings
+                .iter()
+                .filter(|crossing| crossing.wires == (0, 1))
+                .count(),
+            2
+        );
+        assert!(crossings.contains(&WireCrossing {
+            point: Point(6, -5),
+            wires: (1, 2),
+            steps: (15, 11),
+        }));
+        // wires two and three overlap right above the origin
+        assert_eq!(calculator.calculate_minimal_steps_intersection(), 2);
+        assert_eq!(
+            calculator.junctions(3),
+            vec![Junction {
+                point: Point(6, -5),
+                steps: vec![(0, 15), (1, 15), (2, 11)],
+            }]
+        );
+        assert_eq!(calculator.junctions(0), calculator.junctions(2));
+    }
+
+    #[test]
+    fn test_self_intersections() {
+        let calculator = parse(&["R4,U2,L2,D4", "R3,L1,U1"]);
+
+        assert_eq!(calculator.self_intersections(0), vec![Point(2, 0)]);
+        assert_eq!(calculator.self_intersections(1), vec![Point(2, 0)]);
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = parse(&["R8,U5,L5,D3", "U7,R6,D4,L4"]).to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        // two crossings and the origin
+        assert_eq!(svg.matches("<circle").count(), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let lines = vec!["R8,U5".to_string(), "U7,X6".to_string()];
+        let err = create_line_intersection_calculator_from_strings(&lines)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((2, 4))
+        );
+
+        let lines = vec![
+            "".to_string(),
+            "R8,U5".to_string(),
+            "".to_string(),
+            "U7,X6".to_string(),
+        ];
+        let err = Puzzle.parse(&lines).unwrap_err();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((4, 4))
+        );
     }
 }
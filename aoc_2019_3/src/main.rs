@@ -1,20 +1,31 @@
-use std::{fs, io};
+use std::{env, fs, io};
 
 fn main() {
     let input: Vec<String> = read_lines_of_file("input.txt").unwrap();
 
-    let minimal_distance =
-        aoc_2019_3::calculate_minimal_distance_intersections(&input[0], &input[1]).unwrap();
+    let minimal_distance = aoc_2019_3::calculate_minimal_distance_intersections(&input).unwrap();
 
-    let minimal_steps = aoc_2019_3::calculate_minimal_steps_intersections(&input[0], &input[1]).unwrap();
+    let minimal_steps = aoc_2019_3::calculate_minimal_steps_intersections(&input).unwrap();
 
-    println!("Minimal manhattan distance: {}, minimal steps: {}", minimal_distance, minimal_steps);
+    println!(
+        "Minimal manhattan distance: {}, minimal steps: {}",
+        minimal_distance, minimal_steps
+    );
+
+    if let Some(path) = env::args().nth(1) {
+        let circuit = aoc_2019_3::create_line_intersection_calculator_from_strings(&input).unwrap();
+
+        match fs::write(&path, circuit.to_svg()) {
+            Ok(()) => println!("Wrote wires to {}", path),
+            Err(err) => eprintln!("Could not write wires to {}: {}", path, err),
+        }
+    }
 }
 
 fn read_lines_of_file(filename: &str) -> io::Result<Vec<String>> {
     let file = fs::read_to_string(filename)?;
 
-    let result: Vec<String> = file.split("\n").map(|line| line.to_string()).collect();
+    let result: Vec<String> = file.lines().map(|line| line.to_string()).collect();
 
     Ok(result)
 }
@@ -1,76 +1,297 @@
-struct PasswordVerifier {}
+use aoc_common::memo::Memo;
+use aoc_common::parse::{literal, pair, parse_at, terminated, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 
-impl PasswordVerifier {
-    fn is_valid_password(&self, password: u32, exact_double: bool) -> bool {
-        let digits: Vec<u32> = PasswordVerifier::split_into_digits(password);
+/// Constraint on the digits of a password, read from the most significant digit on. Leading
+/// zeros are not part of a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Every digit is at least as large as the one before it.
+    NonDecreasing,
+    /// There is at least one maximal run of equal digits whose length lies between `min` and
+    /// `max`. Without `max`, runs can be arbitrarily long.
+    RunLength {
+        min: usize,
+        max: Option<usize>,
+    },
+    DigitSum {
+        min: u32,
+        max: u32,
+    },
+    ForbiddenDigits(Vec<u32>),
+    DigitCount(usize),
+}
 
-        PasswordVerifier::has_length(&digits, 6)
-            && PasswordVerifier::has_double(&digits, exact_double)
-            && PasswordVerifier::is_monotonic_increasing(&digits)
-    }
+/// Progress of a rule after some digits. Rules only keep as much state as they need so that
+/// the digit DP can share counts between numbers which agree on it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RuleState {
+    Stateless,
+    Last(Option<u32>),
+    Run {
+        last: Option<u32>,
+        length: usize,
+        satisfied: bool,
+    },
+    Sum(u32),
+    Count(usize),
+}
 
-    fn has_length(digits: &Vec<u32>, length: usize) -> bool {
-        digits.len() == length
+impl Rule {
+    fn start(&self) -> RuleState {
+        match self {
+            Rule::NonDecreasing => RuleState::Last(None),
+            Rule::RunLength { .. } => RuleState::Run {
+                last: None,
+                length: 0,
+                satisfied: false,
+            },
+            Rule::DigitSum { .. } => RuleState::Sum(0),
+            Rule::ForbiddenDigits(_) => RuleState::Stateless,
+            Rule::DigitCount(_) => RuleState::Count(0),
+        }
     }
 
-    fn has_double(digits: &Vec<u32>, exact_double: bool) -> bool {
-        let mut streak = 0;
-        let mut last_digit = 10;
-
-        for &digit in digits {
-            if digit != last_digit {
-                if (!exact_double || streak == 2) && streak >= 2 {
-                    return true
+    /// Feeds the next digit to the rule. Returns `None` if no continuation can satisfy it.
+    fn step(&self, state: &RuleState, digit: u32) -> Option<RuleState> {
+        match (self, state) {
+            (Rule::NonDecreasing, RuleState::Last(last)) => {
+                if last.is_some_and(|last| digit < last) {
+                    None
+                } else {
+                    Some(RuleState::Last(Some(digit)))
                 }
+            }
+            (
+                Rule::RunLength { min, max },
+                RuleState::Run {
+                    last,
+                    length,
+                    satisfied,
+                },
+            ) => {
+                // longer runs than this behave the same
+                let cap = max.unwrap_or(*min).max(*min) + 1;
 
-                streak = 0;
+                if *last == Some(digit) {
+                    Some(RuleState::Run {
+                        last: *last,
+                        length: (length + 1).min(cap),
+                        satisfied: *satisfied,
+                    })
+                } else {
+                    Some(RuleState::Run {
+                        last: Some(digit),
+                        length: 1,
+                        satisfied: *satisfied || self.run_fits(*length),
+                    })
+                }
+            }
+            (Rule::DigitSum { max, .. }, RuleState::Sum(sum)) => {
+                Some(RuleState::Sum(sum + digit)).filter(|_| sum + digit <= *max)
             }
+            (Rule::ForbiddenDigits(digits), RuleState::Stateless) => {
+                Some(RuleState::Stateless).filter(|_| !digits.contains(&digit))
+            }
+            (Rule::DigitCount(count), RuleState::Count(length)) => {
+                Some(RuleState::Count(length + 1)).filter(|_| length < count)
+            }
+            _ => unreachable!("Rule {:?} does not use state {:?}.", self, state),
+        }
+    }
 
-            streak += 1;
-            last_digit = digit;
+    fn accepts(&self, state: &RuleState) -> bool {
+        match (self, state) {
+            (
+                Rule::RunLength { .. },
+                RuleState::Run {
+                    length, satisfied, ..
+                },
+            ) => *satisfied || self.run_fits(*length),
+            (Rule::DigitSum { min, .. }, RuleState::Sum(sum)) => sum >= min,
+            (Rule::DigitCount(count), RuleState::Count(length)) => length == count,
+            _ => true,
         }
+    }
 
-        (!exact_double || streak == 2) && streak >= 2
+    fn run_fits(&self, length: usize) -> bool {
+        match self {
+            Rule::RunLength { min, max } => length >= *min && max.is_none_or(|max| length <= max),
+            _ => false,
+        }
     }
+}
 
-    fn current_and_next_iterator(digits: &Vec<u32>) -> impl Iterator<Item = (&u32, &u32)>{
-        digits.iter().zip(digits.iter().skip(1))
+/// Conjunction of rules which a password has to satisfy.
+#[derive(Debug, Clone, Default)]
+pub struct PasswordRules {
+    rules: Vec<Rule>,
+}
+
+impl PasswordRules {
+    pub fn new() -> PasswordRules {
+        PasswordRules::default()
+    }
+
+    pub fn with(mut self, rule: Rule) -> PasswordRules {
+        self.rules.push(rule);
+        self
     }
 
-    fn is_monotonic_increasing(digits: &Vec<u32>) -> bool {
-        PasswordVerifier::current_and_next_iterator(digits).all(|(left, right)| left <= right)
+    /// Six digits which never decrease and contain a double, which may be part of a longer run
+    /// unless `exact_double` is set.
+    pub fn puzzle(exact_double: bool) -> PasswordRules {
+        let max = if exact_double { Some(2) } else { None };
+
+        PasswordRules::new()
+            .with(Rule::DigitCount(6))
+            .with(Rule::NonDecreasing)
+            .with(Rule::RunLength { min: 2, max })
     }
 
-    fn split_into_digits(password: u32) -> Vec<u32> {
-        let mut result = Vec::new();
-        let mut password = password;
+    pub fn is_valid(&self, password: u64) -> bool {
+        let mut states = self.start();
+
+        for digit in split_into_digits(password) {
+            states = match self.step(&states, digit) {
+                Some(states) => states,
+                None => return false,
+            };
+        }
+
+        self.accepts(&states)
+    }
 
-        while password > 0 {
-            result.push(password % 10);
-            password /= 10;
+    /// Counts the valid passwords between `start` and `end`, both included, with a digit DP
+    /// instead of checking every number. The count is a `u128` since the full range of `u64`
+    /// holds 2^64 numbers.
+    pub fn count_in_range(&self, start: u64, end: u64) -> u128 {
+        if start > end {
+            return 0;
         }
 
-        result.reverse();
+        let below_start = match start.checked_sub(1) {
+            Some(below_start) => self.count_up_to(below_start),
+            None => 0,
+        };
+
+        self.count_up_to(end) - below_start
+    }
+
+    /// Counts the valid passwords from 0 to `bound`. The digits are chosen from the most
+    /// significant one on; as long as the prefix equals the one of `bound`, the next digit is
+    /// limited by it. Numbers which start with the same number of free digits and whose prefixes
+    /// leave the rules in the same states have the same number of valid completions.
+    fn count_up_to(&self, bound: u64) -> u128 {
+        let digits = split_into_digits(bound);
+        let mut memo = Memo::new();
+
+        memo.compute((0, true, false, self.start()), &|count,
+                                                       (
+            position,
+            tight,
+            started,
+            states,
+        ): &(
+            usize,
+            bool,
+            bool,
+            Vec<RuleState>,
+        )| {
+            if *position == digits.len() {
+                return if !*started {
+                    // zero consists of a single digit
+                    self.step(states, 0)
+                        .is_some_and(|states| self.accepts(&states)) as u128
+                } else {
+                    self.accepts(states) as u128
+                };
+            }
+
+            let limit = if *tight { digits[*position] } else { 9 };
+
+            (0..=limit)
+                .map(|digit| {
+                    let tight = *tight && digit == limit;
 
-        result
+                    if !*started && digit == 0 {
+                        count((position + 1, tight, false, states.clone()))
+                    } else {
+                        match self.step(states, digit) {
+                            Some(states) => count((position + 1, tight, true, states)),
+                            None => 0,
+                        }
+                    }
+                })
+                .sum()
+        })
     }
 
-    fn new() -> PasswordVerifier {
-        PasswordVerifier {}
+    fn start(&self) -> Vec<RuleState> {
+        self.rules.iter().map(Rule::start).collect()
+    }
+
+    fn step(&self, states: &[RuleState], digit: u32) -> Option<Vec<RuleState>> {
+        self.rules
+            .iter()
+            .zip(states)
+            .map(|(rule, state)| rule.step(state, digit))
+            .collect()
+    }
+
+    fn accepts(&self, states: &[RuleState]) -> bool {
+        self.rules
+            .iter()
+            .zip(states)
+            .all(|(rule, state)| rule.accepts(state))
     }
 }
 
-pub fn valid_passwords_in_range(start: u32, end: u32, exact_double: bool) -> u32 {
-    let mut valid_passwords = 0;
-    let password_verifier = PasswordVerifier::new();
+fn split_into_digits(password: u64) -> Vec<u32> {
+    password
+        .to_string()
+        .chars()
+        .filter_map(|chr| chr.to_digit(10))
+        .collect()
+}
 
-    for password in start..=end {
-        if password_verifier.is_valid_password(password, exact_double) {
-            valid_passwords += 1;
-        }
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = (u64, u64);
+
+    fn year(&self) -> u16 {
+        2019
     }
 
-    valid_passwords
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<(u64, u64)> {
+        let (idx, line) = lines
+            .iter()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty())
+            .ok_or_else(|| AocError::invalid_input("Missing password range."))?;
+        let column = line.len() - line.trim_start().len() + 1;
+        let range = pair(terminated(unsigned(), literal("-")), unsigned());
+
+        Ok(parse_at(range, line.trim(), idx + 1, column)?)
+    }
+
+    fn part_one(&self, input: &(u64, u64)) -> AocResult<String> {
+        Ok(PasswordRules::puzzle(false)
+            .count_in_range(input.0, input.1)
+            .to_string())
+    }
+
+    fn part_two(&self, input: &(u64, u64)) -> AocResult<String> {
+        Ok(PasswordRules::puzzle(true)
+            .count_in_range(input.0, input.1)
+            .to_string())
+    }
 }
 
 #[cfg(test)]
@@ -80,22 +301,89 @@ mod tests {
 
     #[test]
     fn example_password_one() {
-        let password_verifier = PasswordVerifier::new();
+        let password_rules = PasswordRules::puzzle(false);
 
-        assert!(password_verifier.is_valid_password(111111, false))
+        assert!(password_rules.is_valid(111111))
     }
 
     #[test]
     fn example_password_two() {
-        let password_verifier = PasswordVerifier::new();
+        let password_rules = PasswordRules::puzzle(false);
 
-        assert!(!password_verifier.is_valid_password(223450, false))
+        assert!(!password_rules.is_valid(223450))
     }
 
     #[test]
     fn example_password_three() {
-        let password_verifier = PasswordVerifier::new();
+        let password_rules = PasswordRules::puzzle(false);
+
+        assert!(!password_rules.is_valid(123789))
+    }
+
+    #[test]
+    fn example_passwords_with_exact_doubles() {
+        let password_rules = PasswordRules::puzzle(true);
+
+        assert!(password_rules.is_valid(112233));
+        assert!(!password_rules.is_valid(123444));
+        assert!(password_rules.is_valid(111122));
+    }
+
+    #[test]
+    fn count_matches_enumeration() {
+        let rule_sets = vec![
+            PasswordRules::puzzle(false),
+            PasswordRules::puzzle(true),
+            PasswordRules::new().with(Rule::DigitSum { min: 10, max: 12 }),
+            PasswordRules::new()
+                .with(Rule::ForbiddenDigits(vec![0, 7]))
+                .with(Rule::RunLength {
+                    min: 3,
+                    max: Some(4),
+                }),
+            PasswordRules::new(),
+        ];
+
+        for rules in &rule_sets {
+            for &(start, end) in &[
+                (0, 0),
+                (0, 1),
+                (5, 999),
+                (111_000, 125_000),
+                (9_990, 100_123),
+            ] {
+                let expected = (start..=end)
+                    .filter(|&password| rules.is_valid(password))
+                    .count() as u128;
+
+                assert_eq!(
+                    rules.count_in_range(start, end),
+                    expected,
+                    "{:?} in {}..={}",
+                    rules,
+                    start,
+                    end
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn count_large_ranges() {
+        let rules = PasswordRules::new()
+            .with(Rule::DigitCount(18))
+            .with(Rule::NonDecreasing);
 
-        assert!(!password_verifier.is_valid_password(123789, false))
+        // multisets of 18 digits from 1 to 9
+        assert_eq!(
+            rules.count_in_range(100_000_000_000_000_000, 999_999_999_999_999_999),
+            1_562_275
+        );
+        assert_eq!(
+            PasswordRules::new().count_in_range(5, 1_000_000_000_000_000_000),
+            999_999_999_999_999_996
+        );
+        assert_eq!(PasswordRules::new().count_in_range(0, u64::MAX), 1 << 64);
+        assert_eq!(PasswordRules::new().count_in_range(u64::MAX, u64::MAX), 1);
     }
 }
@@ -1,6 +1,8 @@
+use aoc_2019_4::PasswordRules;
+
 fn main() {
-    let valid_passwords = aoc_2019_4::valid_passwords_in_range(138241, 674034, false);
-    let valid_passwords_exact_doubles = aoc_2019_4::valid_passwords_in_range(138241, 674034, true);
+    let valid_passwords = PasswordRules::puzzle(false).count_in_range(138241, 674034);
+    let valid_passwords_exact_doubles = PasswordRules::puzzle(true).count_in_range(138241, 674034);
 
     println!("Valid passwords: {}, valid passwords with exact doubles: {}", valid_passwords, valid_passwords_exact_doubles);
 }
@@ -1,116 +1,354 @@
-use std::collections::HashMap;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 
+/// Tree of bodies in which every body orbits its parent. Ancestors are stored for every power of
+/// two so that lowest common ancestors can be found in O(log n).
 pub struct OrbitCounter {
-    orbits: HashMap<String, Vec<String>>,
-    parents: HashMap<String, String>,
+    bodies: Vec<String>,
+    index: HashMap<String, usize>,
+    children: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+    subtree_sizes: Vec<usize>,
+    /// `ancestors[k][body]` is the ancestor 2^k levels above `body`, or the root.
+    ancestors: Vec<Vec<usize>>,
+    root: usize,
 }
 
 impl OrbitCounter {
-    fn parse_from_string(input: &str) -> Result<OrbitCounter, String> {
-        let (orbits, parents) = OrbitCounter::parse_orbits_from_string(input)?;
-
-        Ok(OrbitCounter { orbits, parents })
-    }
+    fn parse_from_string(input: &str) -> AocResult<OrbitCounter> {
+        let mut bodies = Vec::new();
+        let mut index = HashMap::new();
+        let mut parents: Vec<Option<usize>> = Vec::new();
+        let mut children: Vec<Vec<usize>> = Vec::new();
+
+        let mut body_index = |name: &str| -> usize {
+            *index.entry(name.to_string()).or_insert_with(|| {
+                bodies.push(name.to_string());
+                parents.push(None);
+                children.push(Vec::new());
+                bodies.len() - 1
+            })
+        };
+        let mut relations = Vec::new();
 
-    fn parse_orbits_from_string(input: &str) -> Result<(HashMap<String, Vec<String>>, HashMap<String, String>), String> {
-        let relations: Result<Vec<(String, String)>, String> = input
-            .split("\n")
-            .map(|word| {
-                let mut splits = word.trim().split(")");
+        for (line, relation) in input.lines().enumerate() {
+            let relation = relation.trim();
 
-                let parent = splits.next();
-                let child = splits.next();
+            if relation.is_empty() {
+                continue;
+            }
 
-                match (parent, child) {
-                    (Some(parent), Some(child)) => Ok((parent.to_string(), child.to_string())),
-                    _ => Err("Could not extract parent children relation from input.".to_string()),
+            let (separator, parent, child) = match relation.find(')') {
+                Some(separator) => (
+                    separator,
+                    &relation[..separator],
+                    &relation[separator + 1..],
+                ),
+                None => {
+                    return Err(AocError::parse_at(
+                        line + 1,
+                        1,
+                        "Expected an orbit of the form A)B.",
+                    ))
                 }
-            })
+            };
+
+            if parent.is_empty() || child.is_empty() || child.contains(')') {
+                return Err(AocError::parse_at(
+                    line + 1,
+                    1,
+                    "Expected an orbit of the form A)B.",
+                ));
+            }
+
+            relations.push((line, separator, body_index(parent), body_index(child)));
+        }
+
+        for (line, separator, parent, child) in relations {
+            if let Some(previous) = parents[child] {
+                return Err(AocError::parse_at(
+                    line + 1,
+                    separator + 2,
+                    &format!("{} already orbits {}.", bodies[child], bodies[previous]),
+                ));
+            }
+
+            parents[child] = Some(parent);
+            children[parent].push(child);
+        }
+
+        let roots: Vec<usize> = (0..bodies.len())
+            .filter(|&body| parents[body].is_none())
             .collect();
 
-        relations.map(|relations| {
-            let mut parents = HashMap::new();
-            let mut result = HashMap::new();
+        let root = match roots.as_slice() {
+            [root] => *root,
+            [] if bodies.is_empty() => return Err(AocError::invalid_input("There are no orbits.")),
+            [] => return Err(AocError::invalid_input("Every body orbits another one.")),
+            roots => {
+                let names: Vec<&str> = roots.iter().map(|&root| bodies[root].as_str()).collect();
+
+                return Err(AocError::invalid_input(&format!(
+                    "Expected a single center of mass but found {}.",
+                    names.join(", ")
+                )));
+            }
+        };
+
+        OrbitCounter::build(bodies, index, parents, children, root)
+    }
 
-            for (parent, child) in relations {
-                parents.insert(child.clone(), parent.clone());
-                result.entry(parent).or_insert_with(Vec::new).push(child);
+    fn build(
+        bodies: Vec<String>,
+        index: HashMap<String, usize>,
+        parents: Vec<Option<usize>>,
+        children: Vec<Vec<usize>>,
+        root: usize,
+    ) -> AocResult<OrbitCounter> {
+        let mut depths = vec![usize::MAX; bodies.len()];
+        let mut order = Vec::with_capacity(bodies.len());
+        let mut queue = VecDeque::new();
+
+        depths[root] = 0;
+        queue.push_back(root);
+
+        while let Some(body) = queue.pop_front() {
+            order.push(body);
+
+            for &child in &children[body] {
+                depths[child] = depths[body] + 1;
+                queue.push_back(child);
             }
+        }
+
+        // with a single root, bodies which cannot be reached from it orbit each other in a cycle
+        if let Some(body) = depths.iter().position(|&depth| depth == usize::MAX) {
+            return Err(AocError::invalid_input(&format!(
+                "{} is part of an orbit cycle.",
+                bodies[body]
+            )));
+        }
+
+        let mut subtree_sizes = vec![1; bodies.len()];
+
+        for &body in order.iter().rev() {
+            if let Some(parent) = parents[body] {
+                subtree_sizes[parent] += subtree_sizes[body];
+            }
+        }
+
+        let max_depth = depths.iter().cloned().max().unwrap_or(0);
+        let mut ancestors = vec![parents
+            .iter()
+            .map(|parent| parent.unwrap_or(root))
+            .collect::<Vec<usize>>()];
+
+        while 1 << ancestors.len() <= max_depth {
+            let previous = &ancestors[ancestors.len() - 1];
+            let next = previous
+                .iter()
+                .map(|&ancestor| previous[ancestor])
+                .collect();
+
+            ancestors.push(next);
+        }
 
-            (result, parents)
+        Ok(OrbitCounter {
+            bodies,
+            index,
+            children,
+            depths,
+            subtree_sizes,
+            ancestors,
+            root,
         })
     }
 
     pub fn count_orbits(&self) -> u32 {
-        self.count_orbits_from("COM").0
+        self.depths.iter().sum::<usize>() as u32
+    }
+
+    /// Number of orbital transfers needed to move from the body `src` orbits to the body `dst`
+    /// orbits.
+    pub fn distance_between(&self, src: &str, dst: &str) -> Option<u32> {
+        let path = self.path(src, dst)?;
+
+        Some(path.len().saturating_sub(3) as u32)
+    }
+
+    pub fn root(&self) -> &str {
+        &self.bodies[self.root]
+    }
+
+    pub fn depth(&self, body: &str) -> Option<usize> {
+        self.index.get(body).map(|&body| self.depths[body])
+    }
+
+    /// Number of bodies in the subtree of `body`, including itself.
+    pub fn subtree_size(&self, body: &str) -> Option<usize> {
+        self.index.get(body).map(|&body| self.subtree_sizes[body])
+    }
+
+    /// Number of bodies at every depth, starting with the root at depth 0.
+    pub fn depth_histogram(&self) -> Vec<usize> {
+        let max_depth = self.depths.iter().cloned().max().unwrap_or(0);
+        let mut histogram = vec![0; max_depth + 1];
+
+        for &depth in &self.depths {
+            histogram[depth] += 1;
+        }
+
+        histogram
     }
 
-    fn count_orbits_from(&self, start: &str) -> (u32, u32) {
-        match self.orbits.get(start) {
-            None => (0, 1),
-            Some(children) => {
-                let (paths, nodes) = children
-                    .iter()
-                    .map(|child| self.count_orbits_from(child))
-                    .fold((0, 0), |(acc_paths, acc_nodes), (paths, nodes)| {
-                        (acc_paths + paths, acc_nodes + nodes)
-                    });
+    pub fn lowest_common_ancestor(&self, a: &str, b: &str) -> Option<&str> {
+        let (a, b) = (*self.index.get(a)?, *self.index.get(b)?);
+
+        Some(&self.bodies[self.lca(a, b)])
+    }
 
-                (paths + nodes, nodes + 1)
+    /// Bodies on the way from `src` to `dst`, both included.
+    pub fn path(&self, src: &str, dst: &str) -> Option<Vec<&str>> {
+        let (src, dst) = (*self.index.get(src)?, *self.index.get(dst)?);
+        let lca = self.lca(src, dst);
+
+        let mut path = self.path_up(src, lca);
+        let mut down = self.path_up(dst, lca);
+        down.pop();
+        down.reverse();
+        path.extend(down);
+
+        Some(
+            path.into_iter()
+                .map(|body| self.bodies[body].as_str())
+                .collect(),
+        )
+    }
+
+    /// Exports the tree to Graphviz DOT with an edge from every body to its satellites.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph orbits {\n");
+        let mut queue = VecDeque::from(vec![self.root]);
+
+        while let Some(body) = queue.pop_front() {
+            for &child in &self.children[body] {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\";\n",
+                    self.bodies[body], self.bodies[child]
+                ));
+                queue.push_back(child);
             }
         }
+
+        dot.push_str("}\n");
+        dot
     }
 
-    pub fn distance_between(&self, src: &str, dst: &str) -> u32 {
-        let mut path_to_root_from_src = self.path_to_root_from(src);
-        let mut path_to_root_from_dst = self.path_to_root_from(dst);
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        if self.depths[a] < self.depths[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
 
-        path_to_root_from_dst.reverse();
-        path_to_root_from_src.reverse();
+        a = self.ancestor(a, self.depths[a] - self.depths[b]);
 
-        let mut common_prefix_length = 0;
+        if a == b {
+            return a;
+        }
 
-        while path_to_root_from_src[common_prefix_length] == path_to_root_from_dst[common_prefix_length] {
-            common_prefix_length += 1;
+        for level in self.ancestors.iter().rev() {
+            if level[a] != level[b] {
+                a = level[a];
+                b = level[b];
+            }
         }
 
-        (path_to_root_from_dst.len() as i32 + path_to_root_from_src.len() as i32 - 2 - 2 * common_prefix_length as i32) as u32
+        self.ancestors[0][a]
     }
 
-    fn path_to_root_from(&self, src: &str) -> Vec<String> {
-        let mut result = Vec::new();
+    fn ancestor(&self, mut body: usize, levels: usize) -> usize {
+        for (k, level) in self.ancestors.iter().enumerate() {
+            if levels & (1 << k) != 0 {
+                body = level[body];
+            }
+        }
 
-        let mut src = src;
+        body
+    }
 
-        result.push(src.to_string());
+    fn path_up(&self, mut body: usize, ancestor: usize) -> Vec<usize> {
+        let mut path = vec![body];
 
-        while let Some(parent) = self.parents.get(src) {
-            result.push(parent.clone());
-            src = parent;
+        while body != ancestor {
+            body = self.ancestors[0][body];
+            path.push(body);
         }
 
-        result
+        path
     }
 }
 
-pub fn calculate_orbits_from_file(filename: &str) -> Result<u32, String> {
+pub fn calculate_orbits_from_file(filename: &str) -> AocResult<u32> {
     let orbit_counter = create_orbit_counter_from_file(filename)?;
 
     Ok(orbit_counter.count_orbits())
 }
 
-pub fn create_orbit_counter_from_file(filename: &str) -> Result<OrbitCounter, String> {
-    let input =
-        fs::read_to_string(filename).map_err(|err| format!("Could not read file: {}", err))?;
-    let orbit_counter = OrbitCounter::parse_from_string(&input.trim())?;
-    Ok(orbit_counter)
+pub fn create_orbit_counter_from_file(filename: &str) -> AocResult<OrbitCounter> {
+    let input = fs::read_to_string(filename)?;
+
+    OrbitCounter::parse_from_string(&input).map_err(|err| err.in_file(filename))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = OrbitCounter;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<OrbitCounter> {
+        OrbitCounter::parse_from_string(&lines.join("\n"))
+    }
+
+    fn part_one(&self, input: &OrbitCounter) -> AocResult<String> {
+        Ok(input.count_orbits().to_string())
+    }
+
+    fn part_two(&self, input: &OrbitCounter) -> AocResult<String> {
+        input
+            .distance_between("YOU", "SAN")
+            .map(|distance| distance.to_string())
+            .ok_or_else(|| AocError::no_solution("YOU or SAN is not part of the orbit map."))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_DISTANCE: &str = "\
+COM)B
+B)C
+C)D
+D)E
+E)F
+B)G
+G)H
+D)I
+E)J
+J)K
+K)L
+K)YOU
+I)SAN";
+
     #[test]
     fn example_one() {
         let input = "\
@@ -146,23 +384,66 @@ COM)F";
 
     #[test]
     fn example_distance() {
-        let input = "\
-COM)B
-B)C
-C)D
-D)E
-E)F
-B)G
-G)H
-D)I
-E)J
-J)K
-K)L
-K)YOU
-I)SAN";
+        let orbit_counter = OrbitCounter::parse_from_string(EXAMPLE_DISTANCE).unwrap();
 
-        let orbit_counter = OrbitCounter::parse_from_string(input).unwrap();
+        assert_eq!(orbit_counter.distance_between("YOU", "SAN"), Some(4))
+    }
 
-        assert_eq!(orbit_counter.distance_between("YOU", "SAN"), 4)
+    #[test]
+    fn tree_queries() {
+        let orbit_counter = OrbitCounter::parse_from_string(EXAMPLE_DISTANCE).unwrap();
+
+        assert_eq!(orbit_counter.root(), "COM");
+        assert_eq!(
+            orbit_counter.lowest_common_ancestor("YOU", "SAN"),
+            Some("D")
+        );
+        assert_eq!(orbit_counter.lowest_common_ancestor("L", "K"), Some("K"));
+        assert_eq!(
+            orbit_counter.lowest_common_ancestor("H", "COM"),
+            Some("COM")
+        );
+        assert_eq!(
+            orbit_counter.path("YOU", "SAN"),
+            Some(vec!["YOU", "K", "J", "E", "D", "I", "SAN"])
+        );
+        assert_eq!(orbit_counter.path("C", "C"), Some(vec!["C"]));
+        assert_eq!(orbit_counter.path("C", "X"), None);
+        assert_eq!(orbit_counter.depth("L"), Some(7));
+        assert_eq!(orbit_counter.subtree_size("E"), Some(6));
+        assert_eq!(orbit_counter.subtree_size("COM"), Some(14));
+        assert_eq!(
+            orbit_counter.depth_histogram(),
+            vec![1, 1, 2, 2, 2, 3, 1, 2]
+        );
+    }
+
+    #[test]
+    fn to_dot() {
+        let orbit_counter = OrbitCounter::parse_from_string("B)C\nCOM)B\nCOM)D").unwrap();
+
+        assert_eq!(
+            orbit_counter.to_dot(),
+            "digraph orbits {\n    \"COM\" -> \"B\";\n    \"COM\" -> \"D\";\n    \"B\" -> \"C\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn invalid_orbit_maps() {
+        let location = |input: &str| {
+            OrbitCounter::parse_from_string(input)
+                .err()
+                .and_then(|err| {
+                    err.location()
+                        .map(|location| (location.line, location.column))
+                })
+        };
+
+        assert_eq!(location("COM)A\nA-B"), Some((2, 1)));
+        assert_eq!(location("COM)A\nCOM)B\nA)B"), Some((3, 3)));
+        assert!(OrbitCounter::parse_from_string("COM)A\nX)Y").is_err());
+        assert!(OrbitCounter::parse_from_string("COM)A\nB)C\nC)B").is_err());
+        assert!(OrbitCounter::parse_from_string("A)B\nB)A").is_err());
+        assert!(OrbitCounter::parse_from_string("").is_err());
     }
 }
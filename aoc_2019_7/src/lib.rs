@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use aoc_2019_2::{ComputationResult, IntComputer};
 use core::mem;
 use permutohedron::LexicalPermutation;
@@ -255,6 +257,38 @@ impl OutputForwarder {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        aoc_2019_2::parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        let result = find_largest_permutation(input.clone(), vec![0, 1, 2, 3, 4])
+            .map_err(|err| AocError::invalid_input(&err))?;
+
+        Ok(result.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        let result = find_largest_permutation_for_feedback_sequence(input.clone(), (5..=9).collect())
+            .map_err(|err| AocError::invalid_input(&err))?;
+
+        Ok(result.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,19 @@
-use std::{fs, io};
+use aoc_common::solution::Solution;
+use aoc_common::{ocr, AocError, AocResult};
+use std::fs;
 
+const BLACK: u32 = 0;
+const WHITE: u32 = 1;
+const TRANSPARENT: u32 = 2;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LayerStats {
+    pub black: usize,
+    pub white: usize,
+    pub transparent: usize,
+}
+
+#[derive(Debug)]
 pub struct Image {
     width: u32,
     height: u32,
@@ -7,17 +21,57 @@ pub struct Image {
 }
 
 impl Image {
-    pub fn load_from_file(width: u32, height: u32, filename: &str) -> io::Result<Image> {
+    pub fn load_from_file(width: u32, height: u32, filename: &str) -> AocResult<Image> {
         let content = fs::read_to_string(filename)?;
 
-        Image::load_from_str(width, height, &content)
+        Image::load_from_str(width, height, &content).map_err(|err| err.in_file(filename))
     }
 
-    fn load_from_str(width: u32, height: u32, content: &str) -> io::Result<Image> {
-        let content: String = content.chars().filter(|chr| '\n' != *chr).collect();
-        let x = content.trim().chars().map(|chr| chr.to_digit(10).ok_or(io::Error::from(io::ErrorKind::Other)));
-        let digits = x.collect::<io::Result<Vec<u32>>>()?;
-        let layers: Vec<Vec<u32>> = digits.chunks((width * height) as usize).map(|chunk| chunk.to_vec()).collect();
+    /// Parses the digits of an image in the Space Image Format. Line breaks are ignored. Every
+    /// pixel has to be black (0), white (1) or transparent (2) and the digits have to fill a
+    /// whole number of layers.
+    pub fn load_from_str(width: u32, height: u32, content: &str) -> AocResult<Image> {
+        let layer_size = Image::layer_size(width, height)?;
+        let mut digits = Vec::new();
+        let mut positions = Vec::new();
+
+        for (line, text) in content.lines().enumerate() {
+            for (column, chr) in text.trim_end().chars().enumerate() {
+                let digit = chr
+                    .to_digit(10)
+                    .filter(|&digit| digit <= TRANSPARENT)
+                    .ok_or_else(|| {
+                        AocError::parse_at(
+                            line + 1,
+                            column + 1,
+                            &format!("Expected pixel 0, 1 or 2 but found '{}'.", chr),
+                        )
+                    })?;
+
+                digits.push(digit);
+                positions.push((line + 1, column + 1));
+            }
+        }
+
+        if digits.is_empty() {
+            return Err(AocError::parse("The image does not contain any layer."));
+        }
+
+        if digits.len() % layer_size != 0 {
+            let (line, column) = positions[digits.len() - digits.len() % layer_size];
+
+            return Err(AocError::parse_at(
+                line,
+                column,
+                &format!(
+                    "Incomplete layer: {} digits are not a multiple of the layer size {}.",
+                    digits.len(),
+                    layer_size
+                ),
+            ));
+        }
+
+        let layers = digits.chunks(layer_size).map(|chunk| chunk.to_vec()).collect();
 
         Ok(Image {
             width,
@@ -26,6 +80,88 @@ impl Image {
         })
     }
 
+    /// Builds an image from layers given as rows of pixels, the top layer first.
+    pub fn from_layers(layers: &[Vec<Vec<u32>>]) -> AocResult<Image> {
+        let height = layers.first().map_or(0, |layer| layer.len());
+        let width = layers
+            .first()
+            .and_then(|layer| layer.first())
+            .map_or(0, |row| row.len());
+        Image::layer_size(width as u32, height as u32)?;
+
+        let mut flattened = Vec::with_capacity(layers.len());
+
+        for (index, layer) in layers.iter().enumerate() {
+            if layer.len() != height || layer.iter().any(|row| row.len() != width) {
+                return Err(AocError::invalid_input(&format!(
+                    "Layer {} is not {} pixels wide and {} pixels high.",
+                    index, width, height
+                )));
+            }
+
+            let pixels: Vec<u32> = layer.concat();
+
+            if let Some(pixel) = pixels.iter().find(|&&pixel| pixel > TRANSPARENT) {
+                return Err(AocError::invalid_input(&format!(
+                    "Layer {} contains the invalid pixel {}.",
+                    index, pixel
+                )));
+            }
+
+            flattened.push(pixels);
+        }
+
+        Ok(Image {
+            width: width as u32,
+            height: height as u32,
+            layers: flattened,
+        })
+    }
+
+    /// Encodes the image in the Space Image Format so that it can be read by `load_from_str`.
+    pub fn encode(&self) -> String {
+        self.layers
+            .iter()
+            .flatten()
+            .map(|pixel| pixel.to_string())
+            .collect()
+    }
+
+    fn layer_size(width: u32, height: u32) -> AocResult<usize> {
+        if width == 0 || height == 0 {
+            Err(AocError::invalid_input(&format!(
+                "An image of {}x{} pixels has no pixels.",
+                width, height
+            )))
+        } else {
+            Ok((width * height) as usize)
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn number_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Counts the black, white and transparent pixels of every layer.
+    pub fn layer_stats(&self) -> Vec<LayerStats> {
+        self.layers
+            .iter()
+            .map(|layer| LayerStats {
+                black: layer.iter().filter(|&&pixel| pixel == BLACK).count(),
+                white: layer.iter().filter(|&&pixel| pixel == WHITE).count(),
+                transparent: layer.iter().filter(|&&pixel| pixel == TRANSPARENT).count(),
+            })
+            .collect()
+    }
+
     pub fn fold_layer<B, F>(&self, idx: usize, init: B, function: F) -> Option<B> where F: FnMut(B, &u32) -> B {
         self.layers.get(idx).map(|layer| layer.iter().fold(init, function))
     }
@@ -90,23 +226,115 @@ impl Image {
         result
     }
 
-    pub fn print_rendered_image(&self) {
+    pub fn render_to_string(&self) -> String {
         let rendered_image = self.render_image();
+        let mut result = String::new();
 
         for line in rendered_image.chunks(self.width as usize) {
             for &x in line {
                 if x == 0 {
-                    print!(" ");
+                    result.push(' ');
                 } else {
-                    print!("#");
+                    result.push('#');
                 }
             }
-            print!("\n");
+            result.push('\n');
         }
+
+        result
+    }
+
+    /// Reads the message formed by the white pixels.
+    pub fn read_message(&self) -> AocResult<String> {
+        let pixels: Vec<Vec<bool>> = self
+            .render_image()
+            .chunks(self.width as usize)
+            .map(|line| line.iter().map(|&pixel| pixel == 1).collect())
+            .collect();
+
+        ocr::recognize(&pixels)
+    }
+
+    /// Exports the composited image as a plain PGM with every pixel scaled to a `scale`×`scale`
+    /// square. Pixels that stay transparent are drawn gray.
+    pub fn to_pgm(&self, scale: usize) -> String {
+        self.export("P2", scale, |pixel| match pixel {
+            BLACK => "0".to_string(),
+            WHITE => "255".to_string(),
+            _ => "128".to_string(),
+        })
+    }
+
+    /// Exports the composited image as a plain PPM. Pixels that stay transparent are drawn
+    /// magenta so that they stand out.
+    pub fn to_ppm(&self, scale: usize) -> String {
+        self.export("P3", scale, |pixel| match pixel {
+            BLACK => "0 0 0".to_string(),
+            WHITE => "255 255 255".to_string(),
+            _ => "255 0 255".to_string(),
+        })
+    }
+
+    fn export<F>(&self, magic_number: &str, scale: usize, format_pixel: F) -> String
+    where
+        F: Fn(u32) -> String,
+    {
+        let width = self.width as usize;
+        let mut result = format!(
+            "{}\n{} {}\n255\n",
+            magic_number,
+            width * scale,
+            self.height as usize * scale
+        );
+
+        for line in self.render_image().chunks(width) {
+            let row: Vec<String> = line
+                .iter()
+                .flat_map(|&pixel| std::iter::repeat_n(format_pixel(pixel), scale))
+                .collect();
+            let row = row.join(" ");
+
+            for _ in 0..scale {
+                result.push_str(&row);
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+
+    pub fn print_rendered_image(&self) {
+        print!("{}", self.render_to_string());
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Image;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Image> {
+        Image::load_from_str(25, 6, &lines.join("\n"))
+    }
+
+    fn part_one(&self, input: &Image) -> AocResult<String> {
+        let check_sum = input.check_sum().map_err(|err| AocError::invalid_input(&err))?;
+
+        Ok(check_sum.to_string())
+    }
 
+    fn part_two(&self, input: &Image) -> AocResult<String> {
+        input.read_message()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -118,4 +346,59 @@ mod tests {
 
         assert_eq!(image.render_image(), vec![0, 1, 1, 0])
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_read_message() {
+        // the letter F with transparent pixels around it on top of a black layer
+        let f = "111100100000111000100000100000100000";
+        let layers = [f.replace('0', "2"), "0".repeat(36)].concat();
+        let image = Image::load_from_str(6, 6, &layers).unwrap();
+
+        assert_eq!(image.read_message().unwrap(), "F");
+    }
+
+    fn location(err: AocError) -> Option<(usize, usize)> {
+        err.location().map(|location| (location.line, location.column))
+    }
+
+    #[test]
+    fn test_validation_reports_positions() {
+        assert_eq!(location(Image::load_from_str(2, 2, "0120\n12a1").unwrap_err()), Some((2, 3)));
+        assert_eq!(location(Image::load_from_str(2, 2, "01203").unwrap_err()), Some((1, 5)));
+        assert_eq!(location(Image::load_from_str(2, 2, "0120\n120").unwrap_err()), Some((2, 1)));
+        assert!(Image::load_from_str(2, 2, "\n").is_err());
+        assert!(Image::load_from_str(0, 2, "01").is_err());
+    }
+
+    #[test]
+    fn test_encode_from_layers() {
+        let image = Image::from_layers(&[vec![vec![0, 2], vec![2, 2]], vec![vec![1, 1], vec![2, 0]]]).unwrap();
+
+        assert_eq!((image.width(), image.height(), image.number_layers()), (2, 2, 2));
+        assert_eq!(image.encode(), "02221120");
+        assert_eq!(Image::load_from_str(2, 2, &image.encode()).unwrap().render_image(), vec![0, 1, 2, 0]);
+        assert!(Image::from_layers(&[vec![vec![0, 1]], vec![vec![0]]]).is_err());
+        assert!(Image::from_layers(&[vec![vec![0, 3]]]).is_err());
+    }
+
+    #[test]
+    fn test_layer_stats() {
+        let image = Image::load_from_str(3, 2, "001122222220").unwrap();
+
+        assert_eq!(
+            image.layer_stats(),
+            vec![
+                LayerStats { black: 2, white: 2, transparent: 2 },
+                LayerStats { black: 1, white: 0, transparent: 5 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let image = Image::load_from_str(2, 1, "12").unwrap();
+
+        assert_eq!(image.to_pgm(1), "P2\n2 1\n255\n255 128\n");
+        assert_eq!(image.to_ppm(2), "P3\n4 2\n255\n255 255 255 255 255 255 255 0 255 255 0 255\n255 255 255 255 255 255 255 0 255 255 0 255\n");
+    }
+}
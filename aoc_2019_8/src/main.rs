@@ -1,3 +1,5 @@
+use std::{env, fs};
+
 fn main() {
     let image = aoc_2019_8::Image::load_from_file(25, 6, "input.txt").unwrap();
 
@@ -6,4 +8,11 @@ fn main() {
     println!("Checksum {}", check_sum);
 
     image.print_rendered_image();
+
+    if let Some(path) = env::args().nth(1) {
+        match fs::write(&path, image.to_ppm(10)) {
+            Ok(()) => println!("Wrote image to {}", path),
+            Err(err) => eprintln!("Could not write image to {}: {}", path, err),
+        }
+    }
 }
@@ -0,0 +1,35 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
+
+fn run_boost_program(memory: &[i64], input: i64) -> AocResult<String> {
+    aoc_2019_2::compute_with_inputs(memory.to_vec(), &[input])?
+        .last()
+        .map(|value| value.to_string())
+        .ok_or_else(|| AocError::no_solution("BOOST program did not produce any output."))
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i64>;
+
+    fn year(&self) -> u16 {
+        2019
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i64>> {
+        aoc_2019_2::parse_memory(lines)
+    }
+
+    fn part_one(&self, input: &Vec<i64>) -> AocResult<String> {
+        run_boost_program(input, 1)
+    }
+
+    fn part_two(&self, input: &Vec<i64>) -> AocResult<String> {
+        run_boost_program(input, 2)
+    }
+}
@@ -1,3 +1,6 @@
+use aoc_common::parse::{parse_at, signed};
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::Path;
@@ -41,6 +44,44 @@ pub fn read_lines(path: &str) -> io::Result<Vec<String>> {
     reader.lines().collect()
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i32>;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i32>> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let column = line.len() - line.trim_start().len() + 1;
+
+                Ok(parse_at(signed(), line.trim(), idx + 1, column)?)
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<i32>) -> AocResult<String> {
+        let product = find_two_numbers(input).map_err(|err| AocError::no_solution(&err))?;
+
+        Ok(product.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i32>) -> AocResult<String> {
+        let product = find_three_numbers(input).map_err(|err| AocError::no_solution(&err))?;
+
+        Ok(product.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -29,6 +29,6 @@ fn read_password_lines() -> Vec<PasswordLine> {
     let password_lines: Vec<PasswordLine> = lines
         .into_iter()
         .map(|line| line.parse::<aoc_2020_2::PasswordLine>())
-        .collect::<Result<Vec<PasswordLine>, aoc_2020_2::GenericError>>().unwrap();
+        .collect::<Result<Vec<PasswordLine>, aoc_common::AocError>>().unwrap();
     password_lines
 }
\ No newline at end of file
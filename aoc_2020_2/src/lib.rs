@@ -1,11 +1,14 @@
 #[macro_use] extern crate lazy_static;
 
+use aoc_common::parse::{parse_at, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use aoc_common::AocError;
 use regex::Regex;
 use std::fmt::Formatter;
-use std::error::Error;
 use std::io::{BufReader, BufRead};
 
 pub fn read_lines(path: &str) -> std::io::Result<Vec<String>> {
@@ -52,13 +55,13 @@ impl PasswordLine {
 }
 
 impl FromStr for PasswordLine {
-    type Err = GenericError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut splits = s.split(":");
 
-        let policy: Policy = splits.next().ok_or("Could not find policy.")?.trim().parse()?;
-        let password = splits.next().ok_or("Could not find password.")?.trim();
+        let policy: Policy = splits.next().ok_or_else(|| AocError::parse("Could not find policy."))?.trim().parse()?;
+        let password = splits.next().ok_or_else(|| AocError::parse("Could not find password."))?.trim();
 
         Ok(PasswordLine::new(policy, password.to_string()))
     }
@@ -104,12 +107,12 @@ impl Policy {
 }
 
 impl FromStr for Policy {
-    type Err = GenericError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut splits = s.split(' ');
-        let range: Range = splits.next().ok_or("Could not find range.")?.trim().parse()?;
-        let characters = splits.next().ok_or("Could not find characters.")?.trim();
+        let range: Range = splits.next().ok_or_else(|| AocError::parse("Could not find range."))?.trim().parse()?;
+        let characters = splits.next().ok_or_else(|| AocError::parse("Could not find characters."))?.trim();
 
         let mut policies = HashMap::new();
 
@@ -141,7 +144,7 @@ impl Range {
 }
 
 impl FromStr for Range {
-    type Err = GenericError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
@@ -149,17 +152,59 @@ impl FromStr for Range {
         }
 
         if let Some(captures) = RE.captures(s) {
-            let min = captures[1].parse()?;
-            let max = captures[2].parse()?;
+            let bound = |index| {
+                let m = captures.get(index).unwrap();
+                parse_at(unsigned(), m.as_str(), 1, m.start() + 1)
+            };
+            let min = bound(1)?;
+            let max = bound(2)?;
 
             Ok(Range::new(min, max))
         } else {
-            Err(format!("Could not parse Range from {}.", s).into())
+            Err(AocError::parse(&format!("Could not parse Range from {}.", s)))
         }
     }
 }
 
-pub type GenericError = Box<dyn std::error::Error>;
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<PasswordLine>;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<PasswordLine>> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| line.parse().map_err(|err: AocError| err.offset_by(idx + 1, 1)))
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<PasswordLine>) -> AocResult<String> {
+        let result = input
+            .iter()
+            .filter(|password_line| password_line.is_valid(ValidationMode::Count))
+            .count();
+
+        Ok(result.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<PasswordLine>) -> AocResult<String> {
+        let result = input
+            .iter()
+            .filter(|password_line| password_line.is_valid(ValidationMode::Position))
+            .count();
+
+        Ok(result.to_string())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -1,4 +1,5 @@
-use aoc_common::GenericResult;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::convert::{TryInto, TryFrom};
 use std::fmt;
 use std::fmt::Formatter;
@@ -44,19 +45,22 @@ impl Map {
         self.map.len()
     }
 
-    pub fn parse_from_file(path: &str) -> GenericResult<Map> {
+    pub fn parse_from_file(path: &str) -> AocResult<Map> {
         let file_lines = aoc_common::read_raw_file_content(path)?;
 
-        let parsed_lines = file_lines.into_iter().map(|line| Map::parse_line(line))
-            .collect::<GenericResult<Vec<Vec<MapElement>>>>()?;
+        Map::parse(&file_lines)
+    }
+
+    pub fn parse(lines: &[String]) -> AocResult<Map> {
+        let parsed_lines = lines.iter().enumerate().map(|(idx, line)| Map::parse_line(line, idx + 1))
+            .collect::<AocResult<Vec<Vec<MapElement>>>>()?;
 
         Ok(Map::new(parsed_lines))
     }
 
-    fn parse_line(line: String) -> GenericResult<Vec<MapElement>> {
-        line.chars().into_iter().map(|char| {
-            let result = char.try_into().map_err(|err: String| err.into());
-            result
+    fn parse_line(line: &str, line_number: usize) -> AocResult<Vec<MapElement>> {
+        line.chars().enumerate().map(|(idx, char)| {
+            char.try_into().map_err(|err: String| AocError::parse_at(line_number, idx + 1, &err))
         }).collect()
     }
 }
@@ -103,6 +107,35 @@ impl TryFrom<char> for MapElement {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Map;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Map> {
+        Map::parse(lines)
+    }
+
+    fn part_one(&self, input: &Map) -> AocResult<String> {
+        Ok(solve_slope(Point(3, 1), input).to_string())
+    }
+
+    fn part_two(&self, input: &Map) -> AocResult<String> {
+        let slopes = vec![Point(1, 1), Point(3, 1), Point(5, 1), Point(7, 1), Point(1, 2)];
+        let result: usize = slopes.into_iter().map(|slope| solve_slope(slope, input)).product();
+
+        Ok(result.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
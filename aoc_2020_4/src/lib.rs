@@ -1,9 +1,10 @@
 #[macro_use] extern crate lazy_static;
 
+use aoc_common::solution::Solution;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 
-use aoc_common::GenericResult;
+use aoc_common::{AocError, AocResult};
 
 use regex::Regex;
 
@@ -13,13 +14,13 @@ pub struct Passport {
 }
 
 pub trait Validator {
-    fn validate_passport(&self, passport: &Passport) -> GenericResult<bool>;
+    fn validate_passport(&self, passport: &Passport) -> AocResult<bool>;
 }
 
 pub struct SimpleValidator {}
 
 impl Validator for SimpleValidator {
-    fn validate_passport(&self, passport: &Passport) -> GenericResult<bool> {
+    fn validate_passport(&self, passport: &Passport) -> AocResult<bool> {
         Ok(passport.fields.len() == 8 ||
             (passport.fields.len() == 7 && !passport.fields.contains_key(&PassportFields::CountryId)))
     }
@@ -34,8 +35,8 @@ impl SimpleValidator {
 pub struct Part2Validator {}
 
 impl Validator for Part2Validator {
-    fn validate_passport(&self, passport: &Passport) -> GenericResult<bool> {
-        let fields_validations: Vec<(PassportFields, fn(&Part2Validator, &String) -> GenericResult<bool>)> = vec![
+    fn validate_passport(&self, passport: &Passport) -> AocResult<bool> {
+        let fields_validations: Vec<(PassportFields, fn(&Part2Validator, &String) -> AocResult<bool>)> = vec![
             (PassportFields::Height, Part2Validator::validate_height),
             (PassportFields::BirthYear, Part2Validator::validate_birth_year),
             (PassportFields::IssueYear, Part2Validator::validate_issue_year),
@@ -44,7 +45,7 @@ impl Validator for Part2Validator {
             (PassportFields::EyeColor, Part2Validator::validate_eye_color),
             (PassportFields::PassportId, Part2Validator::validate_passport_id)];
 
-        let results: GenericResult<Vec<bool>> = fields_validations.iter()
+        let results: AocResult<Vec<bool>> = fields_validations.iter()
             .map(|(key, validator)| passport.fields
                 .get(key)
                 .map(|value| {
@@ -65,11 +66,11 @@ impl Part2Validator {
         Part2Validator {}
     }
 
-    fn validate_height(&self, value: &String) -> GenericResult<bool> {
+    fn validate_height(&self, value: &String) -> AocResult<bool> {
         if value.ends_with("in") || value.ends_with("cm") {
             let (height_string, unit) = value.split_at(value.len() - 2);
 
-            let height = height_string.parse::<u32>()?;
+            let height = parse_field_number::<u32>(height_string)?;
 
             let result =
             if unit == "in" {
@@ -84,9 +85,9 @@ impl Part2Validator {
         }
     }
 
-    fn validate_birth_year(&self, value: &String) -> GenericResult<bool> {
+    fn validate_birth_year(&self, value: &String) -> AocResult<bool> {
         if value.len() == 4 {
-            let year: i32 = value.parse()?;
+            let year: i32 = parse_field_number(value)?;
 
             let result = Ok(1920 <= year && year <= 2002);
 
@@ -96,9 +97,9 @@ impl Part2Validator {
         }
     }
 
-    fn validate_issue_year(&self, value: &String) -> GenericResult<bool> {
+    fn validate_issue_year(&self, value: &String) -> AocResult<bool> {
         if value.len() == 4 {
-            let year: i32 = value.parse()?;
+            let year: i32 = parse_field_number(value)?;
 
             Ok(2010 <= year && year <= 2020)
         } else {
@@ -106,9 +107,9 @@ impl Part2Validator {
         }
     }
 
-    fn validate_expiration_year(&self, value: &String) -> GenericResult<bool> {
+    fn validate_expiration_year(&self, value: &String) -> AocResult<bool> {
         if value.len() == 4 {
-            let year: i32 = value.parse()?;
+            let year: i32 = parse_field_number(value)?;
 
             Ok(2020 <= year && year <= 2030)
         } else {
@@ -116,7 +117,7 @@ impl Part2Validator {
         }
     }
 
-    fn validate_hair_color(&self, value: &String) -> GenericResult<bool> {
+    fn validate_hair_color(&self, value: &String) -> AocResult<bool> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"^#[0-9a-f]{6}$").unwrap();
         }
@@ -125,7 +126,7 @@ impl Part2Validator {
         Ok(is_match)
     }
 
-    fn validate_eye_color(&self, value: &String) -> GenericResult<bool> {
+    fn validate_eye_color(&self, value: &String) -> AocResult<bool> {
         match value.as_str() {
             "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth" => {
                 Ok(true)
@@ -134,7 +135,7 @@ impl Part2Validator {
         }
     }
 
-    fn validate_passport_id(&self, value: &String) -> GenericResult<bool> {
+    fn validate_passport_id(&self, value: &String) -> AocResult<bool> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"^[0-9]{9}$").unwrap();
         }
@@ -145,6 +146,12 @@ impl Part2Validator {
     }
 }
 
+fn parse_field_number<T: std::str::FromStr>(value: &str) -> AocResult<T> {
+    value
+        .parse()
+        .map_err(|_| AocError::invalid_input(&format!("Passport field {} is not a number.", value)))
+}
+
 impl Passport {
     pub fn new(fields: HashMap<PassportFields, String>) -> Passport {
         Passport {
@@ -152,29 +159,30 @@ impl Passport {
         }
     }
 
-    pub fn parse_passport(key_value_pairs: &str) -> GenericResult<Passport> {
+    pub fn parse_passport(key_value_pairs: &str) -> AocResult<Passport> {
         let splits = key_value_pairs.split(" ");
         let mut fields = HashMap::new();
 
         for split in splits {
             let mut key_value = split.split(":");
 
-            let key = key_value.next().ok_or("Did not find key.")?.trim();
-            let value = key_value.next().ok_or("Did not find value.")?.trim();
+            let key = key_value.next().ok_or_else(|| AocError::parse("Did not find key."))?.trim();
+            let value = key_value.next().ok_or_else(|| AocError::parse("Did not find value."))?.trim();
 
-            fields.insert(key.try_into()?, value.to_string());
+            let key = key.try_into().map_err(|err: String| AocError::parse(&err))?;
+            fields.insert(key, value.to_string());
         }
 
         Ok(Passport::new(fields))
     }
 }
 
-pub fn parse_passports(path: &str) -> GenericResult<Vec<Passport>> {
+pub fn parse_passports(path: &str) -> AocResult<Vec<Passport>> {
     let lines = aoc_common::read_raw_file_content(path)?;
     parse_passports_from_lines(&lines)
 }
 
-fn parse_passports_from_lines(lines: &Vec<String>) -> GenericResult<Vec<Passport>> {
+fn parse_passports_from_lines(lines: &Vec<String>) -> AocResult<Vec<Passport>> {
     let mut first_line = 0;
     let mut passports = Vec::new();
 
@@ -224,6 +232,44 @@ impl TryFrom<&str> for PassportFields {
     }
 }
 
+fn count_valid_passports<V: Validator>(passports: &[Passport], validator: &V) -> AocResult<String> {
+    let mut result = 0;
+
+    for passport in passports {
+        if validator.validate_passport(passport)? {
+            result += 1;
+        }
+    }
+
+    Ok(result.to_string())
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<Passport>;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<Passport>> {
+        parse_passports_from_lines(&lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<Passport>) -> AocResult<String> {
+        count_valid_passports(input, &SimpleValidator::new())
+    }
+
+    fn part_two(&self, input: &Vec<Passport>) -> AocResult<String> {
+        count_valid_passports(input, &Part2Validator::new())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
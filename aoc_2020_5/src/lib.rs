@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::str::FromStr;
 
 #[derive(Eq, PartialEq, Debug)]
@@ -52,6 +54,61 @@ impl FromStr for BoardingPass {
     }
 }
 
+/// Finds the free seat whose neighbouring seat ids are both taken.
+pub fn find_free_seat_id(seat_ids: &[usize]) -> Option<usize> {
+    let mut seat_ids = seat_ids.to_vec();
+    seat_ids.sort_unstable();
+
+    seat_ids
+        .windows(2)
+        .find(|window| window[1] - window[0] > 1)
+        .map(|window| window[0] + 1)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<usize>;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<usize>> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let boarding_pass = line
+                    .parse::<BoardingPass>()
+                    .map_err(|err| AocError::parse_at(idx + 1, 1, &err))?;
+
+                Ok(boarding_pass.seat_id())
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<usize>) -> AocResult<String> {
+        let max_seat_id = input
+            .iter()
+            .max()
+            .ok_or_else(|| AocError::invalid_input("No boarding passes."))?;
+
+        Ok(max_seat_id.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<usize>) -> AocResult<String> {
+        let seat_id = find_free_seat_id(input)
+            .ok_or_else(|| AocError::no_solution("Could not find a free seat."))?;
+
+        Ok(seat_id.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::BoardingPass;
@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use std::collections::HashSet;
 
 pub struct Group {
@@ -76,6 +78,39 @@ pub fn count_distinct_votes(input: &String) -> usize {
     distinct_votes.len()
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        let result: usize = group_groups(input).iter().map(count_distinct_votes).sum();
+
+        Ok(result.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        let result: usize = create_groups(input)
+            .iter()
+            .map(|group| group.count_unanimous_votes())
+            .sum();
+
+        Ok(result.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
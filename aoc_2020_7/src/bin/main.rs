@@ -2,9 +2,12 @@ use aoc_2020_7::Bag;
 
 fn main() {
     let input = aoc_common::read_raw_file_content("input.txt").unwrap();
-    let bagGraph = aoc_2020_7::parse_bag_graph(&input);
+    let bag_graph = aoc_2020_7::parse_bag_graph(&input).unwrap();
+    let shiny_gold = Bag("shiny gold".to_string());
 
-    let part_1 = bagGraph.can_contain(&Bag("shiny gold".to_string()));
+    let part_1 = bag_graph.can_contain(&shiny_gold);
+    let part_2 = bag_graph.contained_bags(&shiny_gold);
 
     println!("Result part 1: {}", part_1);
-}
\ No newline at end of file
+    println!("Result part 2: {}", part_2);
+}
@@ -1,41 +1,231 @@
-use std::collections::HashMap;
+use aoc_common::parse::{
+    literal, map, optional, pair, parse_at, separated_list, terminated, unsigned, word, Cursor,
+    Parser,
+};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
+use std::collections::{HashMap, HashSet};
 
 pub struct BagGraph {
     contains: HashMap<Bag, Vec<BagQuantity>>,
     is_contained: HashMap<Bag, Vec<Bag>>,
 }
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-pub struct Bag(String);
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Bag(pub String);
 
+#[derive(Debug, PartialEq)]
 pub struct BagQuantity(Bag, usize);
 
 impl BagGraph {
-    pub fn new(contains: HashMap<Bag, Vec<BagQuantity>>, is_contained: HashMap<Bag, Vec<Bag>>) -> BagGraph {
-        BagGraph{
+    pub fn new(
+        contains: HashMap<Bag, Vec<BagQuantity>>,
+        is_contained: HashMap<Bag, Vec<Bag>>,
+    ) -> BagGraph {
+        BagGraph {
             contains,
             is_contained,
         }
     }
 
+    /// Number of different bags which eventually contain at least one `bag`.
     pub fn can_contain(&self, bag: &Bag) -> usize {
-        0
+        let mut containing_bags = HashSet::new();
+        let mut candidates = vec![bag];
+
+        while let Some(candidate) = candidates.pop() {
+            for container in self.is_contained.get(candidate).into_iter().flatten() {
+                if containing_bags.insert(container) {
+                    candidates.push(container);
+                }
+            }
+        }
+
+        containing_bags.len()
+    }
+
+    /// Number of bags which are required inside of `bag`.
+    pub fn contained_bags(&self, bag: &Bag) -> usize {
+        self.count_contained_bags(bag, &mut HashMap::new())
+    }
+
+    fn count_contained_bags<'a>(
+        &'a self,
+        bag: &'a Bag,
+        counts: &mut HashMap<&'a Bag, usize>,
+    ) -> usize {
+        if let Some(&count) = counts.get(bag) {
+            return count;
+        }
+
+        let count = self
+            .contains
+            .get(bag)
+            .into_iter()
+            .flatten()
+            .map(|BagQuantity(inner, quantity)| {
+                quantity * (1 + self.count_contained_bags(inner, counts))
+            })
+            .sum();
+        counts.insert(bag, count);
+
+        count
+    }
+}
+
+fn bag() -> impl Parser<Bag> {
+    map(
+        terminated(
+            pair(
+                terminated(word(), literal(" ")),
+                terminated(word(), literal(" bag")),
+            ),
+            optional(literal("s")),
+        ),
+        |(adjective, color)| Bag(format!("{} {}", adjective, color)),
+    )
+}
+
+fn bag_quantity() -> impl Parser<BagQuantity> {
+    map(
+        pair(terminated(unsigned(), literal(" ")), bag()),
+        |(quantity, bag)| BagQuantity(bag, quantity),
+    )
+}
+
+fn contents() -> impl Parser<Vec<BagQuantity>> {
+    let bag_quantities = separated_list(bag_quantity(), literal(", "));
+
+    move |cursor: &mut Cursor| {
+        if optional(literal("no other bags")).parse(cursor)?.is_some() {
+            Ok(Vec::new())
+        } else {
+            bag_quantities.parse(cursor)
+        }
+    }
+}
+
+fn rule() -> impl Parser<(Bag, Vec<BagQuantity>)> {
+    pair(
+        terminated(bag(), literal(" contain ")),
+        terminated(contents(), literal(".")),
+    )
+}
+
+pub fn parse_bag_graph(input: &[String]) -> AocResult<BagGraph> {
+    let mut contains = HashMap::new();
+    let mut is_contained: HashMap<Bag, Vec<Bag>> = HashMap::new();
+
+    for (idx, line) in input.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (bag, bag_quantities) = parse_at(rule(), line, idx + 1, 1)?;
+
+        for BagQuantity(inner, _) in &bag_quantities {
+            is_contained
+                .entry(inner.clone())
+                .or_default()
+                .push(bag.clone());
+        }
+
+        contains.insert(bag, bag_quantities);
     }
+
+    Ok(BagGraph::new(contains, is_contained))
+}
+
+fn shiny_gold() -> Bag {
+    Bag("shiny gold".to_string())
 }
 
-pub fn parse_bag_graph(input: &Vec<String>) -> BagGraph {
-    for line in input {
-        let mut splits = line.split("contain");
+pub struct Puzzle;
 
-        splits.next()
+impl Solution for Puzzle {
+    type Input = BagGraph;
+
+    fn year(&self) -> u16 {
+        2020
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<BagGraph> {
+        parse_bag_graph(lines)
+    }
+
+    fn part_one(&self, input: &BagGraph) -> AocResult<String> {
+        Ok(input.can_contain(&shiny_gold()).to_string())
+    }
+
+    fn part_two(&self, input: &BagGraph) -> AocResult<String> {
+        Ok(input.contained_bags(&shiny_gold()).to_string())
     }
-    BagGraph::new(HashMap::new(), HashMap::new())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn it_works() {
         assert_eq!(2 + 2, 4);
     }
+
+    fn parse(input: &str) -> BagGraph {
+        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
+
+        parse_bag_graph(&lines).unwrap()
+    }
+
+    #[test]
+    fn bags_containing_shiny_gold() {
+        let graph = parse(
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.",
+        );
+
+        assert_eq!(graph.can_contain(&shiny_gold()), 4);
+        assert_eq!(graph.contained_bags(&shiny_gold()), 32);
+    }
+
+    #[test]
+    fn bags_inside_shiny_gold() {
+        let graph = parse(
+            "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.",
+        );
+
+        assert_eq!(graph.contained_bags(&shiny_gold()), 126);
+    }
+
+    #[test]
+    fn parse_errors_have_positions() {
+        let lines = vec![
+            "".to_string(),
+            "faded blue bags contain 2 dark bags.".to_string(),
+        ];
+        let err = parse_bag_graph(&lines).err().unwrap();
+
+        assert_eq!(
+            err.location()
+                .map(|location| (location.line, location.column)),
+            Some((2, 36))
+        );
+    }
 }
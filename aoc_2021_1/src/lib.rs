@@ -0,0 +1,65 @@
+use aoc_common::parse::{parse_at, signed};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
+
+pub fn count_increases(numbers: &[i32]) -> usize {
+    numbers
+        .iter()
+        .zip(numbers.iter().skip(1))
+        .filter(|(a, b)| a < b)
+        .count()
+}
+
+pub fn count_window_increases(numbers: &[i32]) -> usize {
+    let window_sums: Vec<i32> = numbers.windows(3).map(|window| window.iter().sum()).collect();
+
+    count_increases(&window_sums)
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<i32>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<i32>> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                let column = line.len() - line.trim_start().len() + 1;
+
+                Ok(parse_at(signed(), line.trim(), idx + 1, column)?)
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Vec<i32>) -> AocResult<String> {
+        Ok(count_increases(input).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<i32>) -> AocResult<String> {
+        Ok(count_window_increases(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let numbers = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+        assert_eq!(count_increases(&numbers), 7);
+        assert_eq!(count_window_increases(&numbers), 5);
+    }
+}
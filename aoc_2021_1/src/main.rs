@@ -13,28 +13,9 @@ fn main() {
 }
 
 fn solve_part_two(numbers: &Vec<i32>) {
-    let mut previous: i32 = numbers[0..3].iter().sum();
-    let mut result = 0;
-
-    for i in 1..(numbers.len() - 2) {
-        let next = numbers[i..(i + 3)].iter().sum();
-
-        if previous < next {
-            result += 1;
-        }
-
-        previous = next;
-    }
-
-    println!("Second part: {}", result);
+    println!("Second part: {}", aoc_2021_1::count_window_increases(numbers));
 }
 
 fn solve_part_one(numbers: &Vec<i32>) {
-    let number_increases = numbers
-        .iter()
-        .zip(numbers.iter().skip(1))
-        .filter(|(a, b)| a < b)
-        .count();
-
-    println!("First part: {}", number_increases);
+    println!("First part: {}", aoc_2021_1::count_increases(numbers));
 }
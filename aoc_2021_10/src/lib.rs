@@ -1,12 +1,13 @@
+use aoc_common::solution::Solution;
 use std::collections::HashSet;
-use aoc_common::GenericResult;
+use aoc_common::AocResult;
 
 enum ParseResult {
     Ok(Vec<char>),
     Error(char),
 }
 
-pub fn score_input(input: &Vec<String>) -> GenericResult<u32> {
+pub fn score_input(input: &Vec<String>) -> AocResult<u32> {
     let result = input.iter().map(|line| score(line)).map(|result| {
         match result {
             ParseResult::Ok(_) => 0,
@@ -73,7 +74,7 @@ fn get_value(ch: char) -> u32 {
     }
 }
 
-pub fn score_auto_completion(input: &Vec<String>) -> GenericResult<usize> {
+pub fn score_auto_completion(input: &Vec<String>) -> AocResult<usize> {
     let mut result: Vec<usize> = input.iter().map(|line| score(line)).flat_map(|result| {
         match result {
             ParseResult::Ok(completion) => Some(score_completion(&completion)),
@@ -106,6 +107,32 @@ fn value(ch: char) -> usize {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(score_input(input)?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(score_auto_completion(input)?.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,8 +1,9 @@
+use aoc_common::solution::Solution;
 use aoc_common::math::Point;
-use aoc_common::GenericResult;
+use aoc_common::parse::parse_grid;
+use aoc_common::AocResult;
 use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter};
-use std::str::FromStr;
 
 type FieldType = u8;
 
@@ -22,17 +23,11 @@ impl Map {
         }
     }
 
-    fn parse(input: &Vec<String>) -> GenericResult<Map> {
-        let numbers = input
+    fn parse(input: &Vec<String>) -> AocResult<Map> {
+        let numbers = parse_grid(input, |chr| chr.to_digit(10).map(|digit| digit as FieldType))?
             .into_iter()
-            .flat_map(|line| {
-                line.trim()
-                    .split("")
-                    .skip(1)
-                    .take(line.len())
-                    .map(|chr| FieldType::from_str(chr))
-            })
-            .collect::<Result<_, _>>()?;
+            .flatten()
+            .collect();
 
         let height = input.len();
         let width = input[0].len();
@@ -122,7 +117,7 @@ impl Display for Simulator {
 }
 
 impl Simulator {
-    pub fn parse(input: &Vec<String>) -> GenericResult<Simulator> {
+    pub fn parse(input: &Vec<String>) -> AocResult<Simulator> {
         let map = Map::parse(input)?;
 
         Ok(Simulator {
@@ -207,6 +202,37 @@ impl Simulator {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        let mut simulator = Simulator::parse(input)?;
+        simulator.simulate(100);
+
+        Ok(simulator.get_num_flashes().to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        let mut simulator = Simulator::parse(input)?;
+
+        Ok(simulator.find_first_step_all_flash().to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use aoc_common::solution::Solution;
+use std::collections::HashMap;
 use std::str::FromStr;
-use aoc_common::GenericResult;
+use aoc_common::{AocError, AocResult};
+use aoc_collections::BitSet;
+
+/// Small caves are numbered in the order they are encountered while parsing.
+type VisitedCaves = BitSet<64>;
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 enum MapEntry {
@@ -46,29 +51,48 @@ impl FromStr for MapEntry {
 
 pub struct Map {
     neighbours: HashMap<MapEntry, Vec<MapEntry>>,
+    small_caves: HashMap<MapEntry, usize>,
 }
 
 
 impl Map {
-    fn new(neighbours: HashMap<MapEntry, Vec<MapEntry>>) -> Map {
+    fn new(neighbours: HashMap<MapEntry, Vec<MapEntry>>, small_caves: HashMap<MapEntry, usize>) -> Map {
         Map {
-            neighbours
+            neighbours,
+            small_caves,
         }
     }
 
-    pub fn parse(content: &Vec<String>) -> GenericResult<Map> {
+    pub fn parse(content: &Vec<String>) -> AocResult<Map> {
         let mut neighbours = HashMap::new();
-
-        for line in content {
-            let mut splits = line.split("-");
-            let left: MapEntry = splits.next().ok_or("Could not find left element.")?.parse()?;
-            let right: MapEntry = splits.next().ok_or("Could not find right element.")?.parse()?;
+        let mut small_caves: HashMap<MapEntry, usize> = HashMap::new();
+
+        for (idx, line) in content.iter().enumerate() {
+            let separator = line
+                .find('-')
+                .ok_or_else(|| AocError::parse_at(idx + 1, 1, "Expected two caves separated by '-'."))?;
+            let left: MapEntry = line[..separator]
+                .parse()
+                .map_err(|err: String| AocError::parse_at(idx + 1, 1, &err))?;
+            let right: MapEntry = line[separator + 1..]
+                .parse()
+                .map_err(|err: String| AocError::parse_at(idx + 1, separator + 2, &err))?;
+
+            for entry in [&left, &right] {
+                if entry.is_small_cave() && !small_caves.contains_key(entry) {
+                    small_caves.insert(entry.clone(), small_caves.len());
+                }
+            }
 
             neighbours.entry(left.clone()).or_insert(Vec::new()).push(right.clone());
             neighbours.entry(right).or_insert(Vec::new()).push(left)
         }
 
-        Ok(Map::new(neighbours))
+        if small_caves.len() > 64 {
+            return Err(AocError::invalid_input(&format!("Found {} small caves but can only track 64.", small_caves.len())));
+        }
+
+        Ok(Map::new(neighbours, small_caves))
     }
 
     pub fn count_distinct_paths(&self) -> usize {
@@ -81,7 +105,7 @@ impl Map {
             } else {
                 if let Some(next_entries) = self.neighbours.get(&state.entry) {
                     for next_entry in next_entries {
-                        if let Some(next_state) = Map::go(&state, next_entry) {
+                        if let Some(next_state) = self.go(&state, next_entry) {
                             states.push(next_state);
                         }
                     }
@@ -92,13 +116,22 @@ impl Map {
         distinct_paths
     }
 
-    fn go(state: &State, next_entry: &MapEntry) -> Option<State> {
-        if next_entry.is_small_cave() && state.visited_entries.contains(next_entry) || *next_entry == MapEntry::Start {
+    fn go(&self, state: &State, next_entry: &MapEntry) -> Option<State> {
+        if self.is_visited(state, next_entry) || *next_entry == MapEntry::Start {
             None
         } else {
-            let mut new_visited_entries = state.visited_entries.clone();
-            new_visited_entries.insert(next_entry.clone());
-            Some(State::new(next_entry.clone(), new_visited_entries, false))
+            Some(State::new(next_entry.clone(), self.visit(state, next_entry), false))
+        }
+    }
+
+    fn is_visited(&self, state: &State, entry: &MapEntry) -> bool {
+        self.small_caves.get(entry).map_or(false, |&index| state.visited_entries.contains(index))
+    }
+
+    fn visit(&self, state: &State, entry: &MapEntry) -> VisitedCaves {
+        match self.small_caves.get(entry) {
+            Some(&index) => state.visited_entries.with(index),
+            None => state.visited_entries,
         }
     }
 
@@ -112,7 +145,7 @@ impl Map {
             } else {
                 if let Some(next_entries) = self.neighbours.get(&state.entry) {
                     for next_entry in next_entries {
-                        if let Some(next_state) = Map::go_with_rep(&state,next_entry) {
+                        if let Some(next_state) = self.go_with_rep(&state, next_entry) {
                             states.push(next_state);
                         }
                     }
@@ -123,19 +156,17 @@ impl Map {
         distinct_paths
     }
 
-    fn go_with_rep(state: &State, next_entry: &MapEntry) -> Option<State> {
+    fn go_with_rep(&self, state: &State, next_entry: &MapEntry) -> Option<State> {
         if *next_entry == MapEntry::Start {
             None
-        } else if next_entry.is_small_cave() && state.visited_entries.contains(next_entry) {
+        } else if self.is_visited(state, next_entry) {
             if state.small_cave_twice {
                 None
             } else {
-                Some(State::new(next_entry.clone(), state.visited_entries.clone(), true))
+                Some(State::new(next_entry.clone(), state.visited_entries, true))
             }
         } else {
-            let mut new_visited_entries = state.visited_entries.clone();
-            new_visited_entries.insert(next_entry.clone());
-            Some(State::new(next_entry.clone(), new_visited_entries, state.small_cave_twice))
+            Some(State::new(next_entry.clone(), self.visit(state, next_entry), state.small_cave_twice))
         }
     }
 }
@@ -143,22 +174,20 @@ impl Map {
 #[derive(Debug)]
 struct State {
     entry: MapEntry,
-    visited_entries: HashSet<MapEntry>,
+    visited_entries: VisitedCaves,
     small_cave_twice: bool,
 }
 
 impl State {
     fn start() -> State {
-        let mut visited_entries = HashSet::new();
-        visited_entries.insert(MapEntry::Start);
         State {
             entry: MapEntry::Start,
-            visited_entries,
+            visited_entries: VisitedCaves::new(),
             small_cave_twice: false,
         }
     }
 
-    fn new(entry: MapEntry, visited_entries: HashSet<MapEntry>, small_cave_twice: bool) -> State {
+    fn new(entry: MapEntry, visited_entries: VisitedCaves, small_cave_twice: bool) -> State {
         State {
             entry,
             visited_entries,
@@ -167,6 +196,32 @@ impl State {
     }
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Map;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        12
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Map> {
+        Map::parse(&lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Map) -> AocResult<String> {
+        Ok(input.count_distinct_paths().to_string())
+    }
+
+    fn part_two(&self, input: &Map) -> AocResult<String> {
+        Ok(input.count_distinct_paths_with_rep().to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
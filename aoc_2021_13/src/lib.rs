@@ -1,10 +1,13 @@
 #[macro_use]
 extern crate lazy_static;
 
+use aoc_common::solution::Solution;
 use regex::Regex;
 
 use aoc_common::math::Point;
-use aoc_common::GenericResult;
+use aoc_common::ocr;
+use aoc_common::parse::{literal, pair, parse_all, parse_at, signed, terminated, unsigned};
+use aoc_common::{AocError, AocResult};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -32,11 +35,17 @@ impl Paper {
         self.points.len()
     }
 
-    fn parse(input: &[String]) -> GenericResult<Paper> {
+    /// Reads the code formed by the dots.
+    pub fn read_code(&self) -> AocResult<String> {
+        ocr::recognize_points(self.points.iter().cloned())
+    }
+
+    fn parse(input: &[String]) -> AocResult<Paper> {
         let points = input
             .iter()
-            .map(|line| parse_point(line))
-            .collect::<GenericResult<_>>()?;
+            .enumerate()
+            .map(|(idx, line)| parse_point(line).map_err(|err| err.offset_by(idx + 1, 1)))
+            .collect::<AocResult<_>>()?;
 
         Ok(Paper::new(points))
     }
@@ -66,18 +75,8 @@ impl Display for Paper {
     }
 }
 
-fn parse_point(line: &String) -> GenericResult<Point> {
-    let mut splits = line.split(',');
-
-    let x = splits
-        .next()
-        .ok_or("Point needs to have the format x,y")?
-        .parse()?;
-
-    let y = splits
-        .next()
-        .ok_or("Point needs to have the format x,y")?
-        .parse()?;
+fn parse_point(line: &String) -> AocResult<Point> {
+    let (x, y) = parse_all(pair(terminated(signed(), literal(",")), signed()), line)?;
 
     Ok(Point(x, y))
 }
@@ -135,18 +134,19 @@ impl FromStr for Axis {
     }
 }
 
-pub fn parse_input(input: &Vec<String>) -> GenericResult<(Paper, Vec<FoldingInstruction>)> {
+pub fn parse_input(input: &Vec<String>) -> AocResult<(Paper, Vec<FoldingInstruction>)> {
     let empty_line_index = input
         .iter()
         .enumerate()
         .find(|(idx, line)| line.is_empty())
         .map(|(idx, _)| idx)
-        .ok_or("Could not find empty line.")?;
+        .ok_or_else(|| AocError::parse("Could not find empty line."))?;
 
     let (paper_input, folding_instructions_input) = input.split_at(empty_line_index);
 
     let paper = Paper::parse(paper_input)?;
-    let folding_instructions = parse_folding_instructions(&folding_instructions_input[1..])?;
+    let folding_instructions =
+        parse_folding_instructions(&folding_instructions_input[1..], empty_line_index + 2)?;
 
     Ok((paper, folding_instructions))
 }
@@ -155,29 +155,94 @@ lazy_static! {
     static ref RE: Regex = Regex::new(r"fold along (x|y)=(\d+)").unwrap();
 }
 
-fn parse_folding_instructions(input: &[String]) -> GenericResult<Vec<FoldingInstruction>> {
+fn parse_folding_instructions(
+    input: &[String],
+    first_line: usize,
+) -> AocResult<Vec<FoldingInstruction>> {
     input
         .iter()
-        .map(|line| parse_folding_instruction(line))
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_folding_instruction(line).map_err(|err| err.offset_by(first_line + idx, 1))
+        })
         .collect()
 }
 
-fn parse_folding_instruction(instruction: &String) -> GenericResult<FoldingInstruction> {
+fn parse_folding_instruction(instruction: &String) -> AocResult<FoldingInstruction> {
     if let Some(captures) = RE.captures(instruction) {
-        let axis = captures.get(1).unwrap().as_str().parse()?;
-        let value = captures.get(2).unwrap().as_str().parse()?;
+        let axis = captures.get(1).unwrap();
+        let value = captures.get(2).unwrap();
+        let axis = axis
+            .as_str()
+            .parse()
+            .map_err(|err: String| AocError::parse_at(1, axis.start() + 1, &err))?;
+        let value = parse_at(unsigned(), value.as_str(), 1, value.start() + 1)?;
 
         Ok(FoldingInstruction::new(axis, value))
     } else {
-        Err(format!("Instruction did not follow syntax: {}", instruction).into())
+        Err(AocError::parse(&format!(
+            "Instruction did not follow syntax: {}",
+            instruction
+        )))
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = (Paper, Vec<FoldingInstruction>);
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<(Paper, Vec<FoldingInstruction>)> {
+        parse_input(&lines.to_vec())
+    }
+
+    fn part_one(&self, input: &(Paper, Vec<FoldingInstruction>)) -> AocResult<String> {
+        let (paper, instructions) = input;
+        let instruction = instructions
+            .first()
+            .ok_or_else(|| AocError::invalid_input("No folding instructions."))?;
+
+        Ok(paper.fold(instruction).count_points().to_string())
+    }
+
+    fn part_two(&self, input: &(Paper, Vec<FoldingInstruction>)) -> AocResult<String> {
+        let (paper, instructions) = input;
+        let mut folded_paper: Option<Paper> = None;
+
+        for instruction in instructions {
+            folded_paper = Some(folded_paper.as_ref().unwrap_or(paper).fold(instruction));
+        }
+
+        folded_paper.as_ref().unwrap_or(paper).read_code()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn read_code_after_folding() {
+        // the letter L drawn mirrored to the right of the fold line
+        let points = "#\n#\n#\n#\n#\n####"
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|&(_, chr)| chr == '#')
+                    .map(move |(x, _)| Point(10 - x as isize, y as isize))
+            })
+            .collect();
+        let paper = Paper::new(points).fold(&FoldingInstruction::new(Axis::X, 5));
+
+        assert_eq!(paper.read_code().unwrap(), "L");
     }
 }
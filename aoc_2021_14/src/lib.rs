@@ -1,4 +1,6 @@
-use aoc_common::GenericResult;
+use aoc_common::memo::Memo;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -38,11 +40,16 @@ pub struct PolymerRules {
 }
 
 impl PolymerRules {
-    fn parse(input: &[String]) -> GenericResult<PolymerRules> {
+    fn parse(input: &[String], first_line: usize) -> AocResult<PolymerRules> {
         let rules = input
             .iter()
-            .map(|line| line.parse().map(|Rule(key, value)| (key, value)))
-            .collect::<Result<_, _>>()?;
+            .enumerate()
+            .map(|(idx, line)| {
+                line.parse()
+                    .map(|Rule(key, value)| (key, value))
+                    .map_err(|err: String| AocError::parse_at(first_line + idx, 1, &err))
+            })
+            .collect::<AocResult<_>>()?;
 
         Ok(PolymerRules { rules })
     }
@@ -74,9 +81,13 @@ impl Polymer {
     }
 }
 
-pub fn parse_input(content: &Vec<String>) -> GenericResult<(Polymer, PolymerRules)> {
-    let polymer = Polymer::new(content.get(0).ok_or("No polymer line available.")?);
-    let rules = PolymerRules::parse(&content[2..])?;
+pub fn parse_input(content: &Vec<String>) -> AocResult<(Polymer, PolymerRules)> {
+    let polymer = Polymer::new(
+        content
+            .get(0)
+            .ok_or_else(|| AocError::parse("No polymer line available."))?,
+    );
+    let rules = PolymerRules::parse(&content[2..], 3)?;
 
     Ok((polymer, rules))
 }
@@ -109,11 +120,134 @@ fn evolve_step(polymer: &Polymer, rules: &PolymerRules) -> Polymer {
     Polymer { polymer: result }
 }
 
+/// Counts the elements of the polymer after `steps` insertion steps without building it. Every
+/// pair evolves independently, so the elements inserted between a pair only depend on the pair
+/// and the number of remaining steps.
+pub fn count_elements_after(
+    polymer: &Polymer,
+    rules: &PolymerRules,
+    steps: usize,
+) -> HashMap<char, usize> {
+    let mut memo = Memo::new();
+    let mut counts = polymer.count_elements();
+
+    for pair in polymer.polymer.windows(2) {
+        let inserted =
+            memo.compute(
+                ([pair[0], pair[1]], steps),
+                &|inserted, &(pair, steps)| match rules.get(&pair) {
+                    Some(&insert) if steps > 0 => {
+                        let mut counts: HashMap<char, usize> =
+                            inserted(([pair[0], insert], steps - 1));
+
+                        for (element, count) in inserted(([insert, pair[1]], steps - 1)) {
+                            *counts.entry(element).or_insert(0) += count;
+                        }
+
+                        *counts.entry(insert).or_insert(0) += 1;
+                        counts
+                    }
+                    _ => HashMap::new(),
+                },
+            );
+
+        for (element, count) in inserted {
+            *counts.entry(element).or_insert(0) += count;
+        }
+    }
+
+    counts
+}
+
+/// Difference between the most and the least common element.
+fn spread(elements: &HashMap<char, usize>) -> usize {
+    let min_element = elements.values().min().cloned().unwrap_or(0);
+    let max_element = elements.values().max().cloned().unwrap_or(0);
+
+    max_element - min_element
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = (Polymer, PolymerRules);
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<(Polymer, PolymerRules)> {
+        parse_input(&lines.to_vec())
+    }
+
+    fn part_one(&self, input: &(Polymer, PolymerRules)) -> AocResult<String> {
+        let (polymer, rules) = input;
+        let evolved_polymer = evolve(polymer, rules, 10)
+            .ok_or_else(|| AocError::no_solution("Polymer could not be evolved."))?;
+
+        Ok(spread(&evolved_polymer.count_elements()).to_string())
+    }
+
+    fn part_two(&self, input: &(Polymer, PolymerRules)) -> AocResult<String> {
+        let (polymer, rules) = input;
+
+        Ok(spread(&count_elements_after(polymer, rules, 40)).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    fn example() -> (Polymer, PolymerRules) {
+        let input: Vec<String> = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C"
+            .split('\n')
+            .map(|line| line.to_string())
+            .collect();
+
+        parse_input(&input).unwrap()
+    }
+
     #[test]
-    fn it_works() {
-        let result = 2 + 2;
-        assert_eq!(result, 4);
+    fn counting_agrees_with_evolving() {
+        let (polymer, rules) = example();
+        let evolved = evolve(&polymer, &rules, 10).unwrap();
+
+        assert_eq!(
+            count_elements_after(&polymer, &rules, 10),
+            evolved.count_elements()
+        );
+        assert_eq!(spread(&evolved.count_elements()), 1588);
+    }
+
+    #[test]
+    fn count_after_forty_steps() {
+        let (polymer, rules) = example();
+
+        assert_eq!(
+            spread(&count_elements_after(&polymer, &rules, 40)),
+            2188189693529
+        );
     }
 }
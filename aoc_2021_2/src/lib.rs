@@ -1,3 +1,5 @@
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::ops::AddAssign;
 use std::str::FromStr;
 
@@ -74,6 +76,50 @@ fn calculate_position(commands: &Vec<Command>) -> Position {
     position
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        let mut commands = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            let line = line.trim();
+
+            if !line.is_empty() {
+                line.parse::<Command>()
+                    .map_err(|err| AocError::parse_at(idx + 1, 1, &err))?;
+                commands.push(line.to_string());
+            }
+        }
+
+        Ok(commands)
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        let position = simulate_submarine(&input.iter().map(AsRef::as_ref).collect())
+            .map_err(|err| AocError::parse(&err))?;
+
+        Ok((position.x * position.y).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        let position = simulate_submarine_aim(&input.iter().map(AsRef::as_ref).collect())
+            .map_err(|err| AocError::parse(&err))?;
+
+        Ok((position.x * position.y).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -1,3 +1,6 @@
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
+
 pub fn calculate_power_consumption(input: &Vec<&str>) -> u32 {
     let number_inputs = input.len();
     let counts_per_digit = count_per_digit(input);
@@ -85,6 +88,36 @@ fn map_to_int(chr: char) -> u32 {
     u32::from(chr) - u32::from('0')
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines
+            .iter()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(calculate_power_consumption(&input.iter().map(AsRef::as_ref).collect()).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(calculate_life_support(&input.iter().map(AsRef::as_ref).collect()).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
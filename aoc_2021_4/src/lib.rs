@@ -1,5 +1,7 @@
+use aoc_common::parse::{literal, parse_at, separated_list, tokens, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use std::collections::HashSet;
-use std::num::ParseIntError;
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct BingoBoard {
@@ -14,14 +16,14 @@ impl BingoBoard {
         BingoBoard { board, sum }
     }
 
-    fn parse_from_str(input: &[&str]) -> Result<BingoBoard, ParseIntError> {
-        let joined_result: String = input.join(" ");
+    fn parse_from_str(input: &[&str], first_line: usize) -> AocResult<BingoBoard> {
+        let mut board = Vec::new();
 
-        let board = joined_result
-            .split(" ")
-            .filter(|word| !word.is_empty())
-            .map(|word| word.parse::<u32>())
-            .collect::<Result<Vec<u32>, ParseIntError>>()?;
+        for (idx, line) in input.iter().enumerate() {
+            for (column, word) in tokens(line) {
+                board.push(parse_at(unsigned(), word, first_line + idx, column)?);
+            }
+        }
 
         Ok(BingoBoard::new(board))
     }
@@ -81,12 +83,10 @@ impl NumberDrawing {
         NumberDrawing { numbers }
     }
 
-    fn parse_from_str(input: &str) -> Result<NumberDrawing, ParseIntError> {
-        let numbers = input
-            .trim()
-            .split(",")
-            .map(|word| word.parse::<u32>())
-            .collect::<Result<Vec<u32>, ParseIntError>>()?;
+    fn parse_from_str(input: &str) -> AocResult<NumberDrawing> {
+        let column = input.len() - input.trim_start().len() + 1;
+        let numbers = parse_at(separated_list(unsigned(), literal(",")), input.trim(), 1, column)?;
+
         Ok(NumberDrawing::new(numbers))
     }
 }
@@ -98,13 +98,13 @@ struct Game {
 }
 
 impl Game {
-    fn parse_from_input(input: &Vec<&str>) -> Result<Game, ParseIntError> {
+    fn parse_from_input(input: &Vec<&str>) -> AocResult<Game> {
         let numberDrawing = NumberDrawing::parse_from_str(input[0])?;
 
         let mut boards = Vec::new();
 
         for index in (2..input.len()).step_by(6) {
-            boards.push(BingoBoard::parse_from_str(&input[index..(index + 6)])?);
+            boards.push(BingoBoard::parse_from_str(&input[index..std::cmp::min(index + 6, input.len())], index + 1)?);
         }
 
         Ok(Game {
@@ -122,8 +122,6 @@ impl Game {
             let winning_boards = self.find_winning_boards(&numbers);
 
             if !winning_boards.is_empty() {
-                println!("{:?}", winning_boards);
-                println!("Numbers: {:?}", numbers);
                 assert_eq!(winning_boards.len(), 1);
 
                 return number * winning_boards[0].calculate_winning_score(&numbers);
@@ -153,8 +151,6 @@ impl Game {
             boards.retain(|board| !board.is_winning(&numbers));
 
             if boards.is_empty() {
-                println!("{:?}", winning_boards);
-                println!("Numbers: {:?}", numbers);
                 assert_eq!(winning_boards.len(), 1);
 
                 return number * winning_boards[0].calculate_winning_score(&numbers);
@@ -172,18 +168,44 @@ impl Game {
     }
 }
 
-pub fn play_game(input: &Vec<&str>) -> Result<u32, ParseIntError> {
+pub fn play_game(input: &Vec<&str>) -> AocResult<u32> {
     let game = Game::parse_from_input(input)?;
 
     Ok(game.play())
 }
 
-pub fn play_losing_game(input: &Vec<&str>) -> Result<u32, ParseIntError> {
+pub fn play_losing_game(input: &Vec<&str>) -> AocResult<u32> {
     let game = Game::parse_from_input(input)?;
 
     Ok(game.find_last_board())
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(play_game(&input.iter().map(AsRef::as_ref).collect())?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(play_losing_game(&input.iter().map(AsRef::as_ref).collect())?.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +220,6 @@ mod tests {
             "1 12 20 15 19",
         ];
 
-        BingoBoard::parse_from_str(&input).unwrap();
+        BingoBoard::parse_from_str(&input, 1).unwrap();
     }
 }
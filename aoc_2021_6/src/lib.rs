@@ -1,74 +1,64 @@
-#[derive(Debug)]
-struct Fishes {
-    fishes: [usize; 9],
-    day: usize,
-}
-
-impl Fishes {
-    fn new(initial_fishes: &Vec<u32>) -> Fishes {
-        let mut fishes = [0; 9];
+use aoc_common::memo::table;
+use aoc_common::parse::{delimited, literal, parse_lines, separated_list, spaces, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 
-        for &fish in initial_fishes {
-            assert!(fish <= 8);
-            fishes[fish as usize] += 1;
-        }
+/// Days until a fish which just spawned spawns again.
+const SPAWN_INTERVAL: usize = 7;
+/// Days until a newborn fish spawns for the first time.
+const FIRST_SPAWN_INTERVAL: usize = 9;
 
-        Fishes { fishes, day: 0 }
-    }
+pub fn calculate_fishes_for(initial_fishes: &Vec<u32>, days: u32) -> usize {
+    // descendants[d] is the number of fish a single fish with timer 0 has turned into after d
+    // days, counting the fish itself
+    let descendants = table(days as usize + 1, |descendants, day| {
+        if day == 0 {
+            1
+        } else {
+            let lookup =
+                |interval: usize| day.checked_sub(interval).map_or(1, |day| descendants[day]);
 
-    fn simulate_single_day(&mut self) {
-        let fishes_7_day = self.getFishes(7);
-        let fishes_0_day = self.getFishes(0);
+            lookup(SPAWN_INTERVAL) + lookup(FIRST_SPAWN_INTERVAL)
+        }
+    });
+
+    initial_fishes
+        .iter()
+        .map(|&timer| {
+            (days as usize)
+                .checked_sub(timer as usize)
+                .map_or(1, |day| descendants[day])
+        })
+        .sum()
+}
 
-        self.next_day();
+pub struct Puzzle;
 
-        self.addFishes(6, fishes_7_day);
-        self.setFishes(8, fishes_0_day);
-    }
+impl Solution for Puzzle {
+    type Input = Vec<u32>;
 
-    fn simulate_days(&mut self, days: u32) {
-        for _ in 0..days {
-            self.simulate_single_day();
-        }
+    fn year(&self) -> u16 {
+        2021
     }
 
-    fn number_fishes(&self) -> usize {
-        self.fishes.iter().sum()
+    fn day(&self) -> u8 {
+        6
     }
 
-    fn getFishes(&self, day: usize) -> usize {
-        let index = self.get_index(day);
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<u32>> {
+        let number = delimited(spaces(), unsigned(), spaces());
+        let numbers = parse_lines(separated_list(number, literal(",")), lines)?;
 
-        self.fishes[index]
+        Ok(numbers.into_iter().flatten().collect())
     }
 
-    fn addFishes(&mut self, day: usize, increment: usize) {
-        let index = self.get_index(day);
-
-        self.fishes[index] += increment;
-    }
-
-    fn setFishes(&mut self, day: usize, new_value: usize) {
-        let index = self.get_index(day);
-        self.fishes[index] = new_value;
+    fn part_one(&self, input: &Vec<u32>) -> AocResult<String> {
+        Ok(calculate_fishes_for(input, 80).to_string())
     }
 
-    fn get_index(&self, day: usize) -> usize {
-        if day <= 6 {
-            (self.day + day) % 7
-        } else {
-            (self.day + day) % 2 + 7
-        }
+    fn part_two(&self, input: &Vec<u32>) -> AocResult<String> {
+        Ok(calculate_fishes_for(input, 256).to_string())
     }
-    fn next_day(&mut self) {
-        self.day += 1;
-    }
-}
-
-pub fn calculate_fishes_for(initial_fishes: &Vec<u32>, days: u32) -> usize {
-    let mut fishes = Fishes::new(initial_fishes);
-    fishes.simulate_days(days);
-    fishes.number_fishes()
 }
 
 #[cfg(test)]
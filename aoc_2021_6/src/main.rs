@@ -1,10 +1,9 @@
-use aoc_common::GenericResult;
-use std::num::ParseIntError;
+use aoc_common::solution::Solution;
 
 fn main() {
     let content = aoc_common::read_raw_file_content("input.txt").unwrap();
 
-    let initial_fishes = parse_content(&content).unwrap();
+    let initial_fishes = aoc_2021_6::Puzzle.parse(&content).unwrap();
 
     solve_part_one(&initial_fishes);
     solve_part_two(&initial_fishes);
@@ -21,12 +20,3 @@ fn solve_part_two(initial_fishes: &Vec<u32>) {
 
     println!("Part two: {}.", result);
 }
-
-fn parse_content(input: &Vec<String>) -> GenericResult<Vec<u32>> {
-    input
-        .iter()
-        .flat_map(|line| line.split(','))
-        .map(|number| number.parse::<u32>())
-        .collect::<Result<Vec<u32>, ParseIntError>>()
-        .map_err(|err| err.into())
-}
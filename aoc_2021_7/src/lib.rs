@@ -1,3 +1,6 @@
+use aoc_common::parse::{delimited, literal, parse_lines, separated_list, spaces, unsigned};
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
 use std::cmp::min;
 
 struct Crabs {
@@ -94,6 +97,35 @@ pub fn calculate_minimal_fuel_with_increasing_rate(initial_positions: &Vec<u32>)
     crabs.calculate_minimal_fuel_with_increasing_rate()
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<u32>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<u32>> {
+        let number = delimited(spaces(), unsigned(), spaces());
+        let numbers = parse_lines(separated_list(number, literal(",")), lines)?;
+
+        Ok(numbers.into_iter().flatten().collect())
+    }
+
+    fn part_one(&self, input: &Vec<u32>) -> AocResult<String> {
+        Ok(calculate_minimal_fuel(input).to_string())
+    }
+
+    fn part_two(&self, input: &Vec<u32>) -> AocResult<String> {
+        Ok(calculate_minimal_fuel_with_increasing_rate(input).to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
-use aoc_common::GenericResult;
+use aoc_common::solution::Solution;
+use aoc_common::{AocError, AocResult};
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::str::FromStr;
@@ -44,11 +45,15 @@ struct Input {
 }
 
 impl Input {
-    fn parse(input: &Vec<String>) -> GenericResult<Input> {
+    fn parse(input: &Vec<String>) -> AocResult<Input> {
         let lines = input
             .iter()
-            .map(|line| line.parse())
-            .collect::<Result<_, _>>()?;
+            .enumerate()
+            .map(|(idx, line)| {
+                line.parse()
+                    .map_err(|err: String| AocError::parse_at(idx + 1, 1, &err))
+            })
+            .collect::<AocResult<_>>()?;
         Ok(Input { lines })
     }
 }
@@ -173,7 +178,7 @@ impl<'a> Decoder<'a> {
     }
 }
 
-pub fn find_1_4_7_8_digits(input: &Vec<String>) -> GenericResult<u32> {
+pub fn find_1_4_7_8_digits(input: &Vec<String>) -> AocResult<u32> {
     let input = Input::parse(input)?;
 
     let mut result = 0;
@@ -186,7 +191,7 @@ pub fn find_1_4_7_8_digits(input: &Vec<String>) -> GenericResult<u32> {
     Ok(result)
 }
 
-pub fn decode_results(input: &Vec<String>) -> GenericResult<u32> {
+pub fn decode_results(input: &Vec<String>) -> AocResult<u32> {
     let input = Input::parse(input)?;
 
     let mut result = 0;
@@ -199,6 +204,32 @@ pub fn decode_results(input: &Vec<String>) -> GenericResult<u32> {
     Ok(result)
 }
 
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(find_1_4_7_8_digits(input)?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(decode_results(input)?.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,6 @@
-use aoc_common::GenericResult;
+use aoc_common::solution::Solution;
+use aoc_common::AocResult;
+use aoc_collections::Regions;
 use std::iter::Enumerate;
 use std::slice::Iter;
 
@@ -10,7 +12,7 @@ struct Map<T> {
 }
 
 impl Map<u8> {
-    fn parse(input: &Vec<String>) -> GenericResult<Map<u8>> {
+    fn parse(input: &Vec<String>) -> AocResult<Map<u8>> {
         let height = input.len();
         let width = input.get(0).map(|line| line.len()).unwrap_or(0);
 
@@ -23,15 +25,7 @@ impl Map<u8> {
     }
 }
 
-impl<T> Map<T>
-where
-    T: Default + Clone,
-{
-    fn new(width: usize, height: usize) -> Map<T> {
-        let map = vec![T::default(); width * height];
-
-        Map { width, height, map }
-    }
+impl<T> Map<T> {
     fn get(&self, x: isize, y: isize) -> Option<&T> {
         self.index(x, y).map(|index| self.map.get(index)).flatten()
     }
@@ -44,10 +38,6 @@ where
         }
     }
 
-    pub fn set(&mut self, x: isize, y: isize, value: T) {
-        self.index(x, y).map(|index| self.map[index] = value);
-    }
-
     fn entry_iter(&self) -> EntryIterator<T> {
         EntryIterator {
             iter: self.map.iter().enumerate(),
@@ -86,7 +76,7 @@ where
     }
 }
 
-pub fn find_danger_points(input: &Vec<String>) -> GenericResult<u32> {
+pub fn find_danger_points(input: &Vec<String>) -> AocResult<u32> {
     let map = Map::parse(input)?;
 
     let mut danger_points = Vec::new();
@@ -107,45 +97,44 @@ pub fn find_danger_points(input: &Vec<String>) -> GenericResult<u32> {
     Ok(danger_points.iter().sum())
 }
 
-pub fn find_largest_basins(input: &Vec<String>) -> GenericResult<u32> {
+pub fn find_largest_basins(input: &Vec<String>) -> AocResult<u32> {
     let map = Map::parse(input)?;
-    let mut basin_map: Map<bool> = Map::new(map.width(), map.height());
-    let mut basin_sizes = Vec::new();
-
-    let directions = vec![(1, 0), (-1, 0), (0, 1), (0, -1)];
-
-    for (x, y, value) in map.entry_iter() {
-        if is_free_basin_field(x as isize, y as isize, &map, &basin_map) {
-            let mut basin_size = 0;
-            let mut positions = vec![(x as isize, y as isize)];
-
-            while let Some((x, y)) = positions.pop() {
-                if is_free_basin_field(x, y, &map, &basin_map) {
-                    basin_size += 1;
-                    basin_map.set(x, y, true);
-
-                    directions
-                        .iter()
-                        .map(|&(x_diff, y_diff)| (x + x_diff, y + y_diff))
-                        .filter(|&(x, y)| is_free_basin_field(x, y, &map, &basin_map))
-                        .for_each(|position| positions.push(position));
-                }
-            }
-
-            basin_sizes.push(basin_size);
-        }
-    }
+    let basins = Regions::label(map.width(), map.height(), |x, y| {
+        map.get(x as isize, y as isize).map(|&value| value < 9).unwrap_or(false)
+    });
 
+    let mut basin_sizes = basins.sizes().to_vec();
     basin_sizes.sort_by(|a, b| b.cmp(a));
 
     let result = basin_sizes.iter().take(3).fold(1, |a, b| a * b);
 
-    Ok(result)
+    Ok(result as u32)
 }
 
-fn is_free_basin_field(x: isize, y: isize, map: &Map<u8>, basin_map: &Map<bool>) -> bool {
-    map.get(x, y).map(|&value| value < 9).unwrap_or(false)
-        && !basin_map.get(x, y).cloned().unwrap_or(true)
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Input = Vec<String>;
+
+    fn year(&self) -> u16 {
+        2021
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn parse(&self, lines: &[String]) -> AocResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part_one(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(find_danger_points(input)?.to_string())
+    }
+
+    fn part_two(&self, input: &Vec<String>) -> AocResult<String> {
+        Ok(find_largest_basins(input)?.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,130 @@
+//! Compares the heaps on Dijkstra's algorithm over a grid with random edge weights. Run with
+//! `cargo bench --bench heaps`.
+extern crate aoc_collections;
+
+use aoc_collections::{IndexedHeap, RadixHeap};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 400;
+const ITERATIONS: usize = 5;
+
+struct Grid {
+    weights: Vec<u32>,
+}
+
+impl Grid {
+    fn random(seed: u64) -> Grid {
+        let mut state = seed;
+        let weights = (0..SIZE * SIZE)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                1 + (state >> 33) as u32 % 9
+            })
+            .collect();
+
+        Grid { weights }
+    }
+
+    fn neighbours(&self, node: usize) -> impl Iterator<Item = (usize, u32)> + '_ {
+        let (x, y) = (node % SIZE, node / SIZE);
+        let candidates = [
+            (x > 0, node.wrapping_sub(1)),
+            (x + 1 < SIZE, node + 1),
+            (y > 0, node.wrapping_sub(SIZE)),
+            (y + 1 < SIZE, node + SIZE),
+        ];
+
+        IntoIterator::into_iter(candidates)
+            .filter(|&(valid, _)| valid)
+            .map(move |(_, next)| (next, self.weights[next]))
+    }
+}
+
+fn binary_heap(grid: &Grid) -> u32 {
+    let mut distances = vec![u32::MAX; SIZE * SIZE];
+    let mut heap = BinaryHeap::new();
+
+    distances[0] = 0;
+    heap.push(Reverse((0, 0)));
+
+    while let Some(Reverse((distance, node))) = heap.pop() {
+        if distance > distances[node] {
+            continue;
+        }
+
+        for (next, weight) in grid.neighbours(node) {
+            if distance + weight < distances[next] {
+                distances[next] = distance + weight;
+                heap.push(Reverse((distance + weight, next)));
+            }
+        }
+    }
+
+    distances[SIZE * SIZE - 1]
+}
+
+fn indexed_heap(grid: &Grid) -> u32 {
+    let mut settled = vec![false; SIZE * SIZE];
+    let mut heap = IndexedHeap::new();
+    let mut distances = vec![0; SIZE * SIZE];
+
+    heap.push(0, 0);
+
+    while let Some((node, distance)) = heap.pop() {
+        settled[node] = true;
+        distances[node] = distance;
+
+        for (next, weight) in grid.neighbours(node).filter(|&(next, _)| !settled[next]) {
+            heap.push(next, distance + weight);
+        }
+    }
+
+    distances[SIZE * SIZE - 1]
+}
+
+fn radix_heap(grid: &Grid) -> u32 {
+    let mut settled = vec![false; SIZE * SIZE];
+    let mut heap = RadixHeap::new();
+    let mut distances = vec![0; SIZE * SIZE];
+
+    heap.push(0, 0u32);
+
+    while let Some((node, distance)) = heap.pop() {
+        settled[node] = true;
+        distances[node] = distance;
+
+        for (next, weight) in grid.neighbours(node).filter(|&(next, _)| !settled[next]) {
+            heap.push(next, distance + weight);
+        }
+    }
+
+    distances[SIZE * SIZE - 1]
+}
+
+fn measure<F: Fn(&Grid) -> u32>(name: &str, grid: &Grid, search: F) -> u32 {
+    let mut durations: Vec<Duration> = Vec::new();
+    let mut result = 0;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        result = search(grid);
+        durations.push(start.elapsed());
+    }
+
+    durations.sort();
+    println!("{:<12} {:>10.2?}", name, durations[durations.len() / 2]);
+
+    result
+}
+
+fn main() {
+    let grid = Grid::random(42);
+
+    let expected = measure("BinaryHeap", &grid, binary_heap);
+    assert_eq!(measure("IndexedHeap", &grid, indexed_heap), expected);
+    assert_eq!(measure("RadixHeap", &grid, radix_heap), expected);
+}
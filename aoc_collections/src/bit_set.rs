@@ -0,0 +1,441 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// A set of the integers `0..N` stored inline in a single `u128`, so `N` may be at most 128.
+/// Being `Copy`, `Hash` and `Ord` it is cheap to use as part of search states.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet<const N: usize> {
+    bits: u128,
+}
+
+impl<const N: usize> BitSet<N> {
+    const CAPACITY_CHECK: () = assert!(N <= 128, "BitSet supports at most 128 elements.");
+
+    pub fn new() -> BitSet<N> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CAPACITY_CHECK;
+
+        BitSet { bits: 0 }
+    }
+
+    pub fn full() -> BitSet<N> {
+        BitSet::from_bits(u128::MAX)
+    }
+
+    /// Bit `i` of `bits` determines whether `i` is contained. Bits beyond `N` are ignored.
+    pub fn from_bits(bits: u128) -> BitSet<N> {
+        let mut result = BitSet::new();
+        result.bits = bits & Self::mask();
+        result
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        value < N && self.bits & (1 << value) != 0
+    }
+
+    /// Returns whether the value was newly inserted. Panics if `value` is not less than `N`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(
+            value < N,
+            "Value {} does not fit into BitSet<{}>.",
+            value,
+            N
+        );
+
+        let inserted = !self.contains(value);
+        self.bits |= 1 << value;
+        inserted
+    }
+
+    /// Returns whether the value was contained.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let removed = self.contains(value);
+
+        if removed {
+            self.bits &= !(1 << value);
+        }
+
+        removed
+    }
+
+    /// Returns a copy of the set which additionally contains `value`.
+    pub fn with(mut self, value: usize) -> BitSet<N> {
+        self.insert(value);
+        self
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(127 - self.bits.leading_zeros() as usize)
+        }
+    }
+
+    /// Number of elements smaller than `value`.
+    pub fn rank(&self, value: usize) -> usize {
+        if value >= 128 {
+            self.len()
+        } else {
+            (self.bits & ((1 << value) - 1)).count_ones() as usize
+        }
+    }
+
+    pub fn iter(&self) -> BitSetIter {
+        BitSetIter { bits: self.bits }
+    }
+
+    pub fn union(&self, other: &BitSet<N>) -> BitSet<N> {
+        BitSet {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    pub fn intersection(&self, other: &BitSet<N>) -> BitSet<N> {
+        BitSet {
+            bits: self.bits & other.bits,
+        }
+    }
+
+    pub fn difference(&self, other: &BitSet<N>) -> BitSet<N> {
+        BitSet {
+            bits: self.bits & !other.bits,
+        }
+    }
+
+    pub fn symmetric_difference(&self, other: &BitSet<N>) -> BitSet<N> {
+        BitSet {
+            bits: self.bits ^ other.bits,
+        }
+    }
+
+    pub fn is_subset(&self, other: &BitSet<N>) -> bool {
+        self.bits & !other.bits == 0
+    }
+
+    pub fn is_superset(&self, other: &BitSet<N>) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &BitSet<N>) -> bool {
+        self.bits & other.bits == 0
+    }
+
+    fn mask() -> u128 {
+        if N >= 128 {
+            u128::MAX
+        } else {
+            (1 << N) - 1
+        }
+    }
+}
+
+/// Iterates over the elements of a `BitSet` in ascending order.
+pub struct BitSetIter {
+    bits: u128,
+}
+
+impl Iterator for BitSetIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bits == 0 {
+            None
+        } else {
+            let value = self.bits.trailing_zeros() as usize;
+            self.bits &= self.bits - 1;
+            Some(value)
+        }
+    }
+}
+
+/// Goes through `new` so that the capacity is checked at compile time.
+impl<const N: usize> Default for BitSet<N> {
+    fn default() -> Self {
+        BitSet::new()
+    }
+}
+
+impl<const N: usize> IntoIterator for BitSet<N> {
+    type Item = usize;
+    type IntoIter = BitSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const N: usize> FromIterator<usize> for BitSet<N> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = BitSet::new();
+
+        for value in iter {
+            result.insert(value);
+        }
+
+        result
+    }
+}
+
+impl<const N: usize> fmt::Debug for BitSet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A set of integers which grows with its largest element. Trailing empty words are dropped so
+/// that equal sets compare and hash equally regardless of their history.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct GrowableBitSet {
+    words: Vec<u64>,
+}
+
+impl GrowableBitSet {
+    const WORD_BITS: usize = 64;
+
+    pub fn new() -> GrowableBitSet {
+        GrowableBitSet { words: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (word, bit) = GrowableBitSet::position(value);
+
+        self.words
+            .get(word)
+            .is_some_and(|word| word & (1 << bit) != 0)
+    }
+
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = GrowableBitSet::position(value);
+
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let inserted = self.words[word] & (1 << bit) == 0;
+        self.words[word] |= 1 << bit;
+        inserted
+    }
+
+    pub fn remove(&mut self, value: usize) -> bool {
+        let removed = self.contains(value);
+
+        if removed {
+            let (word, bit) = GrowableBitSet::position(value);
+            self.words[word] &= !(1 << bit);
+            self.trim();
+        }
+
+        removed
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        self.words.last().map(|word| {
+            self.words.len() * GrowableBitSet::WORD_BITS - 1 - word.leading_zeros() as usize
+        })
+    }
+
+    /// Number of elements smaller than `value`.
+    pub fn rank(&self, value: usize) -> usize {
+        let (word, bit) = GrowableBitSet::position(value);
+
+        self.words
+            .iter()
+            .take(word)
+            .map(|word| word.count_ones() as usize)
+            .sum::<usize>()
+            + self
+                .words
+                .get(word)
+                .map_or(0, |word| (word & ((1 << bit) - 1)).count_ones() as usize)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            BitSetIter { bits: word as u128 }
+                .map(move |bit| index * GrowableBitSet::WORD_BITS + bit)
+        })
+    }
+
+    /// Extracts the `W` bits starting at `start` as a `BitSet`. Positions outside of the set,
+    /// including negative ones, count as not contained.
+    pub fn window<const W: usize>(&self, start: isize) -> BitSet<W> {
+        (0..W)
+            .filter(|&offset| {
+                let position = start + offset as isize;
+                position >= 0 && self.contains(position as usize)
+            })
+            .collect()
+    }
+
+    pub fn union(&self, other: &GrowableBitSet) -> GrowableBitSet {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+
+        for (word, other_word) in words.iter_mut().zip(shorter.words.iter()) {
+            *word |= other_word;
+        }
+
+        GrowableBitSet { words }
+    }
+
+    pub fn intersection(&self, other: &GrowableBitSet) -> GrowableBitSet {
+        GrowableBitSet::trimmed(
+            self.words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(word, other_word)| word & other_word)
+                .collect(),
+        )
+    }
+
+    pub fn difference(&self, other: &GrowableBitSet) -> GrowableBitSet {
+        GrowableBitSet::trimmed(
+            self.words
+                .iter()
+                .enumerate()
+                .map(|(index, word)| word & !other.words.get(index).unwrap_or(&0))
+                .collect(),
+        )
+    }
+
+    pub fn symmetric_difference(&self, other: &GrowableBitSet) -> GrowableBitSet {
+        let length = self.words.len().max(other.words.len());
+
+        GrowableBitSet::trimmed(
+            (0..length)
+                .map(|index| {
+                    self.words.get(index).unwrap_or(&0) ^ other.words.get(index).unwrap_or(&0)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn is_subset(&self, other: &GrowableBitSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &GrowableBitSet) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &GrowableBitSet) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    fn position(value: usize) -> (usize, usize) {
+        (
+            value / GrowableBitSet::WORD_BITS,
+            value % GrowableBitSet::WORD_BITS,
+        )
+    }
+
+    fn trimmed(words: Vec<u64>) -> GrowableBitSet {
+        let mut result = GrowableBitSet { words };
+        result.trim();
+        result
+    }
+
+    fn trim(&mut self) {
+        while self.words.last() == Some(&0) {
+            self.words.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for GrowableBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut result = GrowableBitSet::new();
+
+        for value in iter {
+            result.insert(value);
+        }
+
+        result
+    }
+}
+
+impl fmt::Debug for GrowableBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+macro_rules! set_operators {
+    ($t:ty, [$($generic:tt)*]) => {
+        impl $($generic)* BitOr for &$t {
+            type Output = $t;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                self.union(rhs)
+            }
+        }
+
+        impl $($generic)* BitAnd for &$t {
+            type Output = $t;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                self.intersection(rhs)
+            }
+        }
+
+        impl $($generic)* BitXor for &$t {
+            type Output = $t;
+
+            fn bitxor(self, rhs: Self) -> Self::Output {
+                self.symmetric_difference(rhs)
+            }
+        }
+
+        impl $($generic)* Sub for &$t {
+            type Output = $t;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                self.difference(rhs)
+            }
+        }
+    };
+}
+
+set_operators!(BitSet<N>, [<const N: usize>]);
+set_operators!(GrowableBitSet, []);
@@ -89,7 +89,7 @@ impl<'a, T> CursorMut<'a, T> {
                 new_node.next = Some(next);
                 new_node.prev = Some(prev);
 
-                let mut new_node = Box::into_raw_non_null(Box::new(new_node));
+                let mut new_node = NonNull::new_unchecked(Box::into_raw(Box::new(new_node)));
 
                 next.as_mut().prev = Some(new_node);
                 prev.as_mut().next = Some(new_node);
@@ -182,7 +182,7 @@ impl<T> CircularLinkedList<T> {
                     None => panic!("Invalid state where only head is set but not tail."),
                     Some(mut tail) => {
                         let mut new_node = unsafe {
-                            Box::into_raw_non_null(Box::new(Node::new(value)))
+                            NonNull::new_unchecked(Box::into_raw(Box::new(Node::new(value))))
                         };
 
                         unsafe {
@@ -206,7 +206,7 @@ impl<T> CircularLinkedList<T> {
 
     fn create_first(&mut self, value: T) -> NonNull<Node<T>> {
         let mut first_node = unsafe {
-            Box::into_raw_non_null(Box::new(Node::new(value)))
+            NonNull::new_unchecked(Box::into_raw(Box::new(Node::new(value))))
         };
 
         unsafe {
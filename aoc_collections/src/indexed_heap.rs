@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A binary min-heap which knows where every item is stored, so that priorities of queued items
+/// can be changed instead of pushing duplicates.
+#[derive(Clone, Debug)]
+pub struct IndexedHeap<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    pub fn new() -> IndexedHeap<K, P> {
+        IndexedHeap {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains(&self, item: &K) -> bool {
+        self.positions.contains_key(item)
+    }
+
+    pub fn priority(&self, item: &K) -> Option<&P> {
+        self.positions
+            .get(item)
+            .map(|&position| &self.heap[position].1)
+    }
+
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(item, priority)| (item, priority))
+    }
+
+    /// Queues `item` or lowers its priority if it is already queued with a higher one. Returns
+    /// whether the heap changed.
+    pub fn push(&mut self, item: K, priority: P) -> bool {
+        if self.contains(&item) {
+            self.decrease_key(&item, priority)
+        } else {
+            let position = self.heap.len();
+
+            self.positions.insert(item.clone(), position);
+            self.heap.push((item, priority));
+            self.sift_up(position);
+
+            true
+        }
+    }
+
+    /// Lowers the priority of a queued item. Returns whether the priority was lowered.
+    pub fn decrease_key(&mut self, item: &K, priority: P) -> bool {
+        match self.positions.get(item) {
+            Some(&position) if priority < self.heap[position].1 => {
+                self.heap[position].1 = priority;
+                self.sift_up(position);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets the priority of a queued item, no matter whether it increases or decreases. Returns
+    /// the previous priority.
+    pub fn change_priority(&mut self, item: &K, priority: P) -> Option<P> {
+        let position = *self.positions.get(item)?;
+        let previous = std::mem::replace(&mut self.heap[position].1, priority);
+
+        let position = self.sift_up(position);
+        self.sift_down(position);
+
+        Some(previous)
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+
+    pub fn remove(&mut self, item: &K) -> Option<P> {
+        let position = *self.positions.get(item)?;
+
+        self.remove_at(position).map(|(_, priority)| priority)
+    }
+
+    fn remove_at(&mut self, position: usize) -> Option<(K, P)> {
+        if position >= self.heap.len() {
+            return None;
+        }
+
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+
+        let (item, priority) = self.heap.pop()?;
+        self.positions.remove(&item);
+
+        if position < self.heap.len() {
+            let position = self.sift_up(position);
+            self.sift_down(position);
+        }
+
+        Some((item, priority))
+    }
+
+    /// Returns the final position of the moved item.
+    fn sift_up(&mut self, mut position: usize) -> usize {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+
+            if self.heap[position].1 < self.heap[parent].1 {
+                self.swap(position, parent);
+                position = parent;
+            } else {
+                break;
+            }
+        }
+
+        position
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let smallest = [2 * position + 1, 2 * position + 2]
+                .iter()
+                .cloned()
+                .filter(|&child| child < self.heap.len())
+                .fold(position, |smallest, child| {
+                    if self.heap[child].1 < self.heap[smallest].1 {
+                        child
+                    } else {
+                        smallest
+                    }
+                });
+
+            if smallest == position {
+                break;
+            }
+
+            self.swap(position, smallest);
+            position = smallest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+
+        for &position in &[a, b] {
+            if let Some(stored) = self.positions.get_mut(&self.heap[position].0) {
+                *stored = position;
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        IndexedHeap::new()
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use num_traits::PrimInt;
+
+use crate::IntervalSet;
+
+/// Maps disjoint half-open ranges to values. Adjacent ranges with equal values are merged so
+/// that every map has a unique representation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalMap<T, V> {
+    ranges: BTreeMap<T, (T, V)>,
+}
+
+impl<T: Copy + Ord, V: Clone + PartialEq> IntervalMap<T, V> {
+    pub fn new() -> IntervalMap<T, V> {
+        IntervalMap {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Number of maximal ranges with the same value.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn get(&self, point: T) -> Option<&V> {
+        self.ranges
+            .range(..=point)
+            .next_back()
+            .filter(|(_, (end, _))| point < *end)
+            .map(|(_, (_, value))| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Range<T>, &V)> {
+        self.ranges
+            .iter()
+            .map(|(&start, (end, value))| (start..*end, value))
+    }
+
+    pub fn insert(&mut self, range: Range<T>, value: V) {
+        self.update(range, |_| Some(value.clone()));
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        self.update(range, |_| None);
+    }
+
+    /// Replaces the values within `range` by the result of `f`. `f` is called once for every
+    /// stored range and every gap within `range`, receiving `None` for the gaps. Returning
+    /// `None` leaves the corresponding part unmapped.
+    pub fn update<F: FnMut(Option<&V>) -> Option<V>>(&mut self, range: Range<T>, mut f: F) {
+        let Range { start, end } = range;
+
+        if start >= end {
+            return;
+        }
+
+        self.split_at(start);
+        self.split_at(end);
+
+        let existing: Vec<(T, T, V)> = self
+            .ranges
+            .range(start..end)
+            .map(|(&start, (end, value))| (start, *end, value.clone()))
+            .collect();
+
+        let mut cursor = start;
+
+        for (piece_start, piece_end, value) in existing {
+            self.ranges.remove(&piece_start);
+
+            if cursor < piece_start {
+                if let Some(new_value) = f(None) {
+                    self.ranges.insert(cursor, (piece_start, new_value));
+                }
+            }
+
+            if let Some(new_value) = f(Some(&value)) {
+                self.ranges.insert(piece_start, (piece_end, new_value));
+            }
+
+            cursor = piece_end;
+        }
+
+        if cursor < end {
+            if let Some(new_value) = f(None) {
+                self.ranges.insert(cursor, (end, new_value));
+            }
+        }
+
+        self.merge_between(start, end);
+    }
+
+    fn split_at(&mut self, at: T) {
+        let split = match self.ranges.range_mut(..at).next_back() {
+            Some((_, (end, value))) if at < *end => {
+                let upper = (*end, value.clone());
+                *end = at;
+                Some(upper)
+            }
+            _ => None,
+        };
+
+        if let Some(upper) = split {
+            self.ranges.insert(at, upper);
+        }
+    }
+
+    fn merge_between(&mut self, start: T, end: T) {
+        let keys: Vec<T> = self
+            .ranges
+            .range(..start)
+            .next_back()
+            .map(|(&key, _)| key)
+            .into_iter()
+            .chain(self.ranges.range(start..=end).map(|(&key, _)| key))
+            .collect();
+
+        let mut keys = keys.into_iter();
+        let mut current = match keys.next() {
+            Some(key) => key,
+            None => return,
+        };
+
+        for key in keys {
+            let (current_end, current_value) = &self.ranges[&current];
+            let (next_end, next_value) = &self.ranges[&key];
+
+            if *current_end == key && current_value == next_value {
+                let next_end = *next_end;
+                self.ranges.remove(&key);
+
+                if let Some((end, _)) = self.ranges.get_mut(&current) {
+                    *end = next_end;
+                }
+            } else {
+                current = key;
+            }
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalMap<T, usize> {
+    /// Counts for every point by how many of the `ranges` it is covered.
+    pub fn coverage<I: IntoIterator<Item = Range<T>>>(ranges: I) -> IntervalMap<T, usize> {
+        let mut events: Vec<(T, isize)> = ranges
+            .into_iter()
+            .filter(|range| range.start < range.end)
+            .flat_map(|range| vec![(range.start, 1), (range.end, -1)])
+            .collect();
+        events.sort_unstable();
+
+        let mut result = IntervalMap::new();
+        let mut coverage: isize = 0;
+        let mut previous: Option<T> = None;
+
+        for (point, delta) in events {
+            if let Some(previous) = previous {
+                if coverage > 0 && previous < point {
+                    result.insert(previous..point, coverage as usize);
+                }
+            }
+
+            coverage += delta;
+            previous = Some(point);
+        }
+
+        result
+    }
+
+    /// The points which are covered at least `count` times.
+    pub fn at_least(&self, count: usize) -> IntervalSet<T> {
+        self.iter()
+            .filter(|&(_, &coverage)| coverage >= count)
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+impl<T: PrimInt, V: Clone + PartialEq> IntervalMap<T, V> {
+    /// Total length of all mapped ranges.
+    pub fn covered_length(&self) -> T {
+        self.iter().fold(T::zero(), |length, (range, _)| {
+            length + (range.end - range.start)
+        })
+    }
+}
+
+impl<T: Copy + Ord, V: Clone + PartialEq> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        IntervalMap::new()
+    }
+}
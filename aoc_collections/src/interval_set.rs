@@ -0,0 +1,145 @@
+use std::iter::FromIterator;
+use std::ops::Range;
+
+use num_traits::PrimInt;
+
+use crate::IntervalMap;
+
+/// A set of points stored as disjoint, non-adjacent half-open ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: IntervalMap<T, ()>,
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet {
+            ranges: IntervalMap::new(),
+        }
+    }
+
+    /// Number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.ranges.get(point).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(range, _)| range)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        self.ranges.insert(range, ());
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        self.ranges.remove(range);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+
+        for range in other.iter() {
+            result.insert(range);
+        }
+
+        result
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+
+        for range in other.iter() {
+            result.remove(range);
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        let mut ranges = self.iter().peekable();
+        let mut other_ranges = other.iter().peekable();
+
+        while let (Some(range), Some(other_range)) =
+            (ranges.peek().cloned(), other_ranges.peek().cloned())
+        {
+            let start = range.start.max(other_range.start);
+            let end = range.end.min(other_range.end);
+
+            if start < end {
+                result.insert(start..end);
+            }
+
+            if range.end < other_range.end {
+                ranges.next();
+            } else {
+                other_ranges.next();
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    /// Number of points in the set.
+    pub fn covered_length(&self) -> T {
+        self.ranges.covered_length()
+    }
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut result = IntervalSet::new();
+
+        for range in iter {
+            result.insert(range);
+        }
+
+        result
+    }
+}
+
+/// Area of the union of axis aligned rectangles given as their x and y ranges.
+pub fn union_area<T: PrimInt>(rectangles: &[(Range<T>, Range<T>)]) -> T {
+    area_covered_at_least(rectangles, 1)
+}
+
+/// Area which is covered by at least `count` of the rectangles. The plane is swept in slabs
+/// between consecutive x coordinates, so no grid is allocated.
+pub fn area_covered_at_least<T: PrimInt>(rectangles: &[(Range<T>, Range<T>)], count: usize) -> T {
+    let mut xs: Vec<T> = rectangles
+        .iter()
+        .flat_map(|(x, _)| vec![x.start, x.end])
+        .collect();
+    xs.sort_unstable();
+    xs.dedup();
+
+    xs.windows(2).fold(T::zero(), |area, slab| {
+        let (left, right) = (slab[0], slab[1]);
+        let height = IntervalMap::coverage(
+            rectangles
+                .iter()
+                .filter(|(x, _)| x.start <= left && right <= x.end)
+                .map(|(_, y)| y.clone()),
+        )
+        .at_least(count)
+        .covered_length();
+
+        area + height * (right - left)
+    })
+}
@@ -1,7 +1,17 @@
-#![feature(box_into_raw_non_null)]
-
+pub mod bit_set;
 pub mod circular_linked_list;
+pub mod indexed_heap;
+pub mod interval_map;
+pub mod interval_set;
+pub mod radix_heap;
 pub mod radix_tree;
+pub mod union_find;
 
+pub use self::bit_set::{BitSet, GrowableBitSet};
 pub use self::circular_linked_list::CircularLinkedList;
+pub use self::indexed_heap::IndexedHeap;
+pub use self::interval_map::IntervalMap;
+pub use self::interval_set::IntervalSet;
+pub use self::radix_heap::RadixHeap;
 pub use self::radix_tree::RadixTree;
+pub use self::union_find::{Regions, UnionFind};
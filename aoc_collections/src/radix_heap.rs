@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use num_traits::{PrimInt, Unsigned};
+
+/// A min-heap for unsigned integer priorities which never fall below the last popped priority,
+/// as is the case in Dijkstra's algorithm. Items are distributed into buckets by the highest bit
+/// in which their priority differs from the last popped one, so every item is moved at most
+/// once per bit of the priority type.
+///
+/// Lowering the priority of a queued item leaves a stale entry behind which is skipped when it
+/// is reached.
+#[derive(Clone, Debug)]
+pub struct RadixHeap<K, P> {
+    buckets: Vec<Vec<(K, P)>>,
+    priorities: HashMap<K, P>,
+    last: P,
+}
+
+impl<K: Hash + Eq + Clone, P: PrimInt + Unsigned> RadixHeap<K, P> {
+    pub fn new() -> RadixHeap<K, P> {
+        RadixHeap {
+            buckets: vec![Vec::new(); RadixHeap::<K, P>::bits() + 1],
+            priorities: HashMap::new(),
+            last: P::zero(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.priorities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priorities.is_empty()
+    }
+
+    pub fn contains(&self, item: &K) -> bool {
+        self.priorities.contains_key(item)
+    }
+
+    pub fn priority(&self, item: &K) -> Option<P> {
+        self.priorities.get(item).cloned()
+    }
+
+    /// Queues `item` or lowers its priority if it is already queued with a higher one. Returns
+    /// whether the heap changed. Panics if `priority` is smaller than the last popped priority.
+    pub fn push(&mut self, item: K, priority: P) -> bool {
+        assert!(
+            priority >= self.last,
+            "Priorities of a RadixHeap must not fall below the last popped priority."
+        );
+
+        match self.priorities.get(&item) {
+            Some(&queued) if queued <= priority => false,
+            _ => {
+                let bucket = self.bucket(priority);
+
+                self.priorities.insert(item.clone(), priority);
+                self.buckets[bucket].push((item, priority));
+                true
+            }
+        }
+    }
+
+    /// Lowers the priority of a queued item. Returns whether the priority was lowered.
+    pub fn decrease_key(&mut self, item: &K, priority: P) -> bool {
+        if self.contains(item) {
+            self.push(item.clone(), priority)
+        } else {
+            false
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        loop {
+            if self.buckets[0].is_empty() {
+                self.redistribute()?;
+            }
+
+            let (item, priority) = self.buckets[0].pop()?;
+
+            if self.priorities.get(&item) == Some(&priority) {
+                self.priorities.remove(&item);
+                return Some((item, priority));
+            }
+        }
+    }
+
+    /// Moves the entries of the first non-empty bucket into lower buckets, relative to the
+    /// smallest priority among them.
+    fn redistribute(&mut self) -> Option<()> {
+        let index = self.buckets.iter().position(|bucket| !bucket.is_empty())?;
+        let entries = std::mem::take(&mut self.buckets[index]);
+
+        self.last = entries.iter().map(|&(_, priority)| priority).min()?;
+
+        for (item, priority) in entries {
+            let bucket = self.bucket(priority);
+            self.buckets[bucket].push((item, priority));
+        }
+
+        Some(())
+    }
+
+    fn bucket(&self, priority: P) -> usize {
+        RadixHeap::<K, P>::bits() - (priority ^ self.last).leading_zeros() as usize
+    }
+
+    fn bits() -> usize {
+        P::zero().count_zeros() as usize
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: PrimInt + Unsigned> Default for RadixHeap<K, P> {
+    fn default() -> Self {
+        RadixHeap::new()
+    }
+}
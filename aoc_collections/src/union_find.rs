@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+/// Disjoint sets over the elements `0..len()` with path compression and union by rank.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    number_components: usize,
+}
+
+impl UnionFind {
+    /// Every one of the `size` elements starts in its own component.
+    pub fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..size).collect(),
+            ranks: vec![0; size],
+            sizes: vec![1; size],
+            number_components: size,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Adds a new element in its own component and returns it.
+    pub fn add(&mut self) -> usize {
+        let element = self.parents.len();
+
+        self.parents.push(element);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.number_components += 1;
+
+        element
+    }
+
+    /// The representative of the component containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut current = element;
+
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the components of `a` and `b`. Returns whether they were distinct before.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        let (parent, child) = if self.ranks[a] >= self.ranks[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        if self.ranks[parent] == self.ranks[child] {
+            self.ranks[parent] += 1;
+        }
+
+        self.parents[child] = parent;
+        self.sizes[parent] += self.sizes[child];
+        self.number_components -= 1;
+
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    pub fn number_components(&self) -> usize {
+        self.number_components
+    }
+
+    /// The sizes of all components in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&element| self.parents[element] == element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+
+    /// All components with their elements in ascending order, ordered by their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut indices: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<usize>> = Vec::new();
+
+        for element in 0..self.len() {
+            let root = self.find(element);
+            let index = *indices.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+
+            components[index].push(element);
+        }
+
+        components
+    }
+}
+
+/// Connected regions of a grid, where two horizontally or vertically adjacent cells belong to the
+/// same region if both satisfy the predicate.
+#[derive(Clone, Debug)]
+pub struct Regions {
+    width: usize,
+    labels: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+}
+
+impl Regions {
+    /// Labels the cells of a `width` × `height` grid for which `predicate(x, y)` holds. Regions
+    /// are numbered in the order their first cell appears in row major order.
+    pub fn label<F: FnMut(usize, usize) -> bool>(
+        width: usize,
+        height: usize,
+        mut predicate: F,
+    ) -> Regions {
+        let included: Vec<bool> = (0..width * height)
+            .map(|index| predicate(index % width, index / width))
+            .collect();
+        let mut union_find = UnionFind::new(included.len());
+
+        for index in (0..included.len()).filter(|&index| included[index]) {
+            if index % width + 1 < width && included[index + 1] {
+                union_find.union(index, index + 1);
+            }
+
+            if index + width < included.len() && included[index + width] {
+                union_find.union(index, index + width);
+            }
+        }
+
+        let mut region_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut sizes = Vec::new();
+        let labels = (0..included.len())
+            .map(|index| {
+                if included[index] {
+                    let root = union_find.find(index);
+                    let region = *region_of_root.entry(root).or_insert_with(|| {
+                        sizes.push(0);
+                        sizes.len() - 1
+                    });
+                    sizes[region] += 1;
+
+                    Some(region)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        Regions {
+            width,
+            labels,
+            sizes,
+        }
+    }
+
+    /// Number of regions.
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+
+    pub fn region(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width {
+            self.labels.get(x + y * self.width).cloned().flatten()
+        } else {
+            None
+        }
+    }
+
+    /// Number of cells of every region, indexed by the region label.
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+}
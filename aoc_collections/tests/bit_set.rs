@@ -0,0 +1,107 @@
+extern crate aoc_collections;
+
+use aoc_collections::{BitSet, GrowableBitSet};
+use std::collections::HashSet;
+
+#[test]
+fn test_bit_set_insert_remove_and_rank() {
+    let mut set: BitSet<100> = BitSet::new();
+
+    assert!(set.insert(3));
+    assert!(set.insert(99));
+    assert!(set.insert(64));
+    assert!(!set.insert(3));
+    assert!(set.remove(64));
+    assert!(!set.remove(64));
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 99]);
+    assert_eq!(set.first(), Some(3));
+    assert_eq!(set.last(), Some(99));
+    assert_eq!(set.rank(3), 0);
+    assert_eq!(set.rank(50), 1);
+    assert_eq!(set.rank(200), 2);
+    assert!(!set.contains(100));
+    assert_eq!(BitSet::<128>::full().len(), 128);
+    assert_eq!(BitSet::<5>::from_bits(0xff).len(), 5);
+}
+
+#[test]
+fn test_bit_set_default_is_empty() {
+    let set: BitSet<128> = BitSet::default();
+
+    assert!(set.is_empty());
+    assert_eq!(set, BitSet::new());
+}
+
+#[test]
+#[should_panic]
+fn test_bit_set_insert_out_of_capacity() {
+    BitSet::<26>::new().insert(26);
+}
+
+#[test]
+fn test_bit_set_algebra() {
+    let a: BitSet<8> = vec![0, 1, 2, 5].into_iter().collect();
+    let b: BitSet<8> = vec![2, 5, 7].into_iter().collect();
+
+    assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![0, 1, 2, 5, 7]);
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![2, 5]);
+    assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![0, 1, 7]);
+    assert!((&a & &b).is_subset(&a));
+    assert!(a.is_superset(&(&a - &b)));
+    assert!((&a - &b).is_disjoint(&b));
+    assert_eq!(format!("{:?}", b), "{2, 5, 7}");
+}
+
+#[test]
+fn test_bit_set_as_search_key() {
+    let mut seen = HashSet::new();
+
+    assert!(seen.insert((0, BitSet::<26>::new().with(4).with(1))));
+    assert!(!seen.insert((0, BitSet::<26>::new().with(1).with(4))));
+    assert!(BitSet::<26>::new().with(1) < BitSet::<26>::new().with(2));
+}
+
+#[test]
+fn test_growable_bit_set_normalizes_representation() {
+    let mut set: GrowableBitSet = vec![1, 200].into_iter().collect();
+
+    assert!(set.remove(200));
+    assert_eq!(set, vec![1].into_iter().collect());
+    assert_eq!(set.last(), Some(1));
+
+    set.insert(130);
+    set.insert(64);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 64, 130]);
+    assert_eq!(set.rank(64), 1);
+    assert_eq!(set.rank(1000), 3);
+    assert_eq!(set.last(), Some(130));
+}
+
+#[test]
+fn test_growable_bit_set_algebra() {
+    let a: GrowableBitSet = vec![0, 70, 150].into_iter().collect();
+    let b: GrowableBitSet = vec![70, 80].into_iter().collect();
+
+    assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![0, 70, 80, 150]);
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![70]);
+    assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![0, 150]);
+    assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![0, 80, 150]);
+    assert_eq!(&b - &a, vec![80].into_iter().collect());
+    assert!(!a.is_disjoint(&b));
+    assert!((&a & &b).is_subset(&b));
+}
+
+#[test]
+fn test_growable_bit_set_window() {
+    let set: GrowableBitSet = vec![0, 2, 63, 64, 66].into_iter().collect();
+
+    assert_eq!(set.window::<5>(-2).iter().collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(
+        set.window::<5>(62).iter().collect::<Vec<_>>(),
+        vec![1, 2, 4]
+    );
+    assert!(set.window::<5>(100).is_empty());
+}
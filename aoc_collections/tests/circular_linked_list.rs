@@ -1,6 +1,6 @@
-extern crate aoc_common;
+extern crate aoc_collections;
 
-use aoc_common::collections::CircularLinkedList;
+use aoc_collections::CircularLinkedList;
 use core::borrow::Borrow;
 
 #[test]
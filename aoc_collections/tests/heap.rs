@@ -0,0 +1,109 @@
+extern crate aoc_collections;
+
+use aoc_collections::{IndexedHeap, RadixHeap};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+fn drain_indexed(mut heap: IndexedHeap<char, u32>) -> Vec<(char, u32)> {
+    std::iter::from_fn(|| heap.pop()).collect()
+}
+
+fn drain_radix(mut heap: RadixHeap<char, u32>) -> Vec<(char, u32)> {
+    std::iter::from_fn(|| heap.pop()).collect()
+}
+
+#[test]
+fn test_indexed_heap_decrease_key() {
+    let mut heap = IndexedHeap::new();
+
+    assert!(heap.push('a', 5));
+    assert!(heap.push('b', 3));
+    assert!(heap.push('c', 8));
+    assert!(!heap.push('a', 7));
+    assert!(heap.push('c', 1));
+    assert!(!heap.decrease_key(&'d', 0));
+
+    assert_eq!(heap.len(), 3);
+    assert!(heap.contains(&'a'));
+    assert_eq!(heap.priority(&'a'), Some(&5));
+    assert_eq!(heap.peek(), Some((&'c', &1)));
+    assert_eq!(drain_indexed(heap), vec![('c', 1), ('b', 3), ('a', 5)]);
+}
+
+#[test]
+fn test_indexed_heap_change_priority_and_remove() {
+    let mut heap = IndexedHeap::new();
+
+    for (item, priority) in "abcdefg".chars().zip(0..) {
+        heap.push(item, priority);
+    }
+
+    assert_eq!(heap.change_priority(&'a', 10), Some(0));
+    assert_eq!(heap.remove(&'d'), Some(3));
+    assert_eq!(heap.remove(&'d'), None);
+    assert!(!heap.contains(&'d'));
+
+    assert_eq!(
+        drain_indexed(heap),
+        vec![('b', 1), ('c', 2), ('e', 4), ('f', 5), ('g', 6), ('a', 10)]
+    );
+}
+
+#[test]
+fn test_radix_heap_decrease_key() {
+    let mut heap = RadixHeap::new();
+
+    assert!(heap.push('a', 5));
+    assert!(heap.push('b', 1000));
+    assert!(heap.push('c', 8));
+    assert!(!heap.push('a', 7));
+    assert!(heap.decrease_key(&'b', 6));
+    assert!(!heap.decrease_key(&'d', 6));
+
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.priority(&'b'), Some(6));
+    assert_eq!(heap.pop(), Some(('a', 5)));
+
+    heap.push('a', 5);
+
+    assert_eq!(drain_radix(heap), vec![('a', 5), ('b', 6), ('c', 8)]);
+}
+
+#[test]
+#[should_panic]
+fn test_radix_heap_rejects_smaller_priorities() {
+    let mut heap = RadixHeap::new();
+
+    heap.push('a', 5u32);
+    heap.pop();
+    heap.push('b', 4);
+}
+
+#[test]
+fn test_heaps_agree_with_binary_heap() {
+    let mut indexed = IndexedHeap::new();
+    let mut radix = RadixHeap::new();
+    let mut binary = BinaryHeap::new();
+    let mut seed: u64 = 17;
+    let mut floor = 0;
+
+    for round in 0..2000 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let priority = floor + (seed >> 33) % 100;
+        let item = round;
+
+        indexed.push(item, priority);
+        radix.push(item, priority);
+        binary.push(Reverse((priority, item)));
+
+        if round % 3 == 0 {
+            let Reverse((expected, _)) = binary.pop().unwrap();
+
+            assert_eq!(indexed.pop().map(|(_, priority)| priority), Some(expected));
+            assert_eq!(radix.pop().map(|(_, priority)| priority), Some(expected));
+            floor = expected;
+        }
+    }
+}
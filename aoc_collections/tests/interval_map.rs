@@ -0,0 +1,55 @@
+extern crate aoc_collections;
+
+use aoc_collections::IntervalMap;
+use std::ops::Range;
+
+fn entries(map: &IntervalMap<i32, usize>) -> Vec<(Range<i32>, usize)> {
+    map.iter().map(|(range, &value)| (range, value)).collect()
+}
+
+#[test]
+fn test_insert_overwrites_and_merges() {
+    let mut map = IntervalMap::new();
+
+    map.insert(0..10, 1);
+    map.insert(3..5, 2);
+    map.insert(8..12, 1);
+
+    assert_eq!(entries(&map), vec![(0..3, 1), (3..5, 2), (5..12, 1)]);
+    assert_eq!(map.get(4), Some(&2));
+    assert_eq!(map.get(12), None);
+
+    map.insert(3..5, 1);
+
+    assert_eq!(entries(&map), vec![(0..12, 1)]);
+}
+
+#[test]
+fn test_update_fills_gaps() {
+    let mut map = IntervalMap::new();
+
+    map.insert(2..4, 1);
+    map.insert(6..8, 3);
+    map.update(0..7, |value| Some(value.map_or(0, |value| value * 10)));
+
+    assert_eq!(
+        entries(&map),
+        vec![(0..2, 0), (2..4, 10), (4..6, 0), (6..7, 30), (7..8, 3)]
+    );
+
+    map.remove(1..7);
+
+    assert_eq!(entries(&map), vec![(0..1, 0), (7..8, 3)]);
+}
+
+#[test]
+fn test_coverage() {
+    let coverage = IntervalMap::coverage(vec![0..4, 2..6, 3..5, 8..9]);
+
+    assert_eq!(
+        entries(&coverage),
+        vec![(0..2, 1), (2..3, 2), (3..4, 3), (4..5, 2), (5..6, 1), (8..9, 1)]
+    );
+    assert_eq!(coverage.at_least(2).iter().collect::<Vec<_>>(), vec![2..5]);
+    assert_eq!(coverage.covered_length(), 7);
+}
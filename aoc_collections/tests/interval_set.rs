@@ -0,0 +1,54 @@
+extern crate aoc_collections;
+
+use aoc_collections::interval_set::{area_covered_at_least, union_area};
+use aoc_collections::IntervalSet;
+use std::ops::Range;
+
+fn ranges(set: &IntervalSet<i32>) -> Vec<Range<i32>> {
+    set.iter().collect()
+}
+
+#[test]
+fn test_insert_merges_overlapping_and_adjacent_ranges() {
+    let set: IntervalSet<i32> = vec![5..8, 1..3, 3..4, 7..10, 12..12]
+        .into_iter()
+        .collect();
+
+    assert_eq!(ranges(&set), vec![1..4, 5..10]);
+    assert_eq!(set.covered_length(), 8);
+    assert!(set.contains(3));
+    assert!(!set.contains(4));
+    assert!(!set.contains(10));
+}
+
+#[test]
+fn test_remove_splits_ranges() {
+    let mut set = IntervalSet::new();
+
+    set.insert(0..10);
+    set.remove(3..5);
+    set.remove(9..20);
+
+    assert_eq!(ranges(&set), vec![0..3, 5..9]);
+}
+
+#[test]
+fn test_set_operations() {
+    let a: IntervalSet<i32> = vec![0..5, 10..15].into_iter().collect();
+    let b: IntervalSet<i32> = vec![3..12, 14..20].into_iter().collect();
+
+    assert_eq!(ranges(&a.union(&b)), vec![0..20]);
+    assert_eq!(ranges(&a.intersection(&b)), vec![3..5, 10..12, 14..15]);
+    assert_eq!(ranges(&a.difference(&b)), vec![0..3, 12..14]);
+    assert!(a.intersection(&IntervalSet::new()).is_empty());
+}
+
+#[test]
+fn test_rectangle_areas() {
+    let rectangles = vec![(1..5, 3..7), (3..7, 1..5), (5..7, 5..7)];
+
+    assert_eq!(union_area(&rectangles), 16 + 16 + 4 - 4);
+    assert_eq!(area_covered_at_least(&rectangles, 2), 4);
+    assert_eq!(area_covered_at_least(&rectangles, 3), 0);
+    assert_eq!(union_area::<i32>(&[]), 0);
+}
@@ -0,0 +1,55 @@
+extern crate aoc_collections;
+
+use aoc_collections::{Regions, UnionFind};
+
+#[test]
+fn test_union_merges_components() {
+    let mut union_find = UnionFind::new(6);
+
+    assert!(union_find.union(0, 1));
+    assert!(union_find.union(4, 3));
+    assert!(union_find.union(1, 3));
+    assert!(!union_find.union(0, 4));
+
+    assert!(union_find.connected(0, 4));
+    assert!(!union_find.connected(0, 5));
+    assert_eq!(union_find.component_size(3), 4);
+    assert_eq!(union_find.number_components(), 3);
+
+    let mut sizes = union_find.component_sizes();
+    sizes.sort_unstable();
+    assert_eq!(sizes, vec![1, 1, 4]);
+    assert_eq!(
+        union_find.components(),
+        vec![vec![0, 1, 3, 4], vec![2], vec![5]]
+    );
+}
+
+#[test]
+fn test_add_element() {
+    let mut union_find = UnionFind::new(0);
+
+    assert!(union_find.is_empty());
+
+    let a = union_find.add();
+    let b = union_find.add();
+    union_find.union(a, b);
+
+    assert_eq!(union_find.len(), 2);
+    assert_eq!(union_find.number_components(), 1);
+}
+
+#[test]
+fn test_label_grid_regions() {
+    let grid = ["##..#", "#..##", "..#..", "###.#"];
+    let regions = Regions::label(5, 4, |x, y| grid[y].as_bytes()[x] == b'#');
+
+    assert_eq!(regions.len(), 4);
+    assert_eq!(regions.sizes(), &[3, 3, 4, 1]);
+    assert_eq!(regions.region(0, 0), Some(0));
+    assert_eq!(regions.region(4, 0), Some(1));
+    assert_eq!(regions.region(2, 3), Some(2));
+    assert_eq!(regions.region(4, 3), Some(3));
+    assert_eq!(regions.region(2, 0), None);
+    assert_eq!(regions.region(5, 0), None);
+}
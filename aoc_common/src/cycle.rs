@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states of an iterated function repeat every `length` steps once step `start` has been
+/// reached.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step which has the same state as `step`.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare algorithm. It only keeps two states in memory but applies `step`
+/// roughly three times as often as there are states before the cycle closes.
+///
+/// Does not terminate if the states never repeat.
+pub fn floyd<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Brent's algorithm. Like `floyd` it only keeps two states in memory but it needs fewer
+/// applications of `step`.
+///
+/// Does not terminate if the states never repeat.
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// All states up to the point where the first state repeats.
+#[derive(Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(step)]
+    }
+
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Detects the cycle by remembering the step at which every state occurred first. This applies
+/// `step` exactly once per distinct state at the cost of keeping all of them.
+///
+/// Does not terminate if the states never repeat.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> History<S>
+where
+    S: Clone + Hash + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut first_occurrences: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = first_occurrences.get(&state) {
+            let length = states.len() - start;
+
+            return History {
+                cycle: Cycle { start, length },
+                states,
+            };
+        }
+
+        first_occurrences.insert(state.clone(), states.len());
+
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Calculates `measure` of the simulation after `target` steps of `advance`. The simulation
+/// only runs until `key` returns a value it returned before. From then on the states are
+/// assumed to repeat with the measure changing by the same amount in every cycle, which covers
+/// both plain cycles and patterns drifting at a constant speed.
+///
+/// Does not terminate before `target` if the keys never repeat.
+pub fn extrapolate<S, F, N, K, M>(
+    mut state: S,
+    target: usize,
+    mut advance: F,
+    mut key: N,
+    mut measure: M,
+) -> i64
+where
+    F: FnMut(&mut S),
+    N: FnMut(&S) -> K,
+    K: Hash + Eq,
+    M: FnMut(&S) -> i64,
+{
+    let mut first_occurrences: HashMap<K, usize> = HashMap::new();
+    let mut measures = Vec::new();
+    let mut current = 0;
+
+    loop {
+        let value = measure(&state);
+
+        if current == target {
+            return value;
+        }
+
+        measures.push(value);
+
+        if let Some(start) = first_occurrences.insert(key(&state), current) {
+            let length = current - start;
+            let drift = value - measures[start];
+            let remaining = target - start;
+
+            return measures[start + remaining % length] + (remaining / length) as i64 * drift;
+        }
+
+        advance(&mut state);
+        current += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts from 0 up to 10 and then visits 10..=16 in steps of 3 modulo 7
+    fn step(value: &u32) -> u32 {
+        if *value < 10 {
+            value + 1
+        } else {
+            10 + (value - 10 + 3) % 7
+        }
+    }
+
+    #[test]
+    fn all_detectors_agree() {
+        let expected = Cycle {
+            start: 10,
+            length: 7,
+        };
+
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_cycle(0, step).cycle, expected);
+        assert_eq!(
+            brent(12, step),
+            Cycle {
+                start: 0,
+                length: 7
+            }
+        );
+    }
+
+    #[test]
+    fn history_looks_up_late_states() {
+        let history = find_cycle(0u32, step);
+
+        assert_eq!(history.states().len(), 17);
+        assert_eq!(*history.state_at(5), 5);
+        assert_eq!(*history.state_at(1_000_000), 10 + (1_000_000 - 10) * 3 % 7);
+    }
+
+    #[test]
+    fn extrapolate_cycles_and_drift() {
+        let cyclic = extrapolate(
+            0u32,
+            1_000_000,
+            |value| *value = step(value),
+            |&v| v,
+            |&v| v as i64,
+        );
+
+        assert_eq!(cyclic, 10 + (1_000_000 - 10) * 3 % 7);
+
+        // alternates between two shapes while moving one to the right in every step
+        let drifting = extrapolate(
+            (0i64, 0u8),
+            50_000_000_000,
+            |(offset, phase)| {
+                *phase = (*phase + 1) % 2;
+                *offset += 1;
+            },
+            |&(_, phase)| phase,
+            |&(offset, phase)| 3 * offset + phase as i64,
+        );
+
+        assert_eq!(drifting, 150_000_000_000);
+    }
+}
@@ -0,0 +1,238 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+
+use crate::parse::ParseError;
+
+pub type AocResult<T> = Result<T, AocError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Parse {
+        message: String,
+        location: Option<SourceLocation>,
+    },
+    InvalidInput(String),
+    NoSolution(String),
+}
+
+impl AocError {
+    pub fn parse(message: &str) -> AocError {
+        AocError::Parse {
+            message: message.to_string(),
+            location: None,
+        }
+    }
+
+    pub fn parse_at(line: usize, column: usize, message: &str) -> AocError {
+        AocError::Parse {
+            message: message.to_string(),
+            location: Some(SourceLocation {
+                file: None,
+                line,
+                column,
+            }),
+        }
+    }
+
+    pub fn invalid_input(message: &str) -> AocError {
+        AocError::InvalidInput(message.to_string())
+    }
+
+    pub fn no_solution(message: &str) -> AocError {
+        AocError::NoSolution(message.to_string())
+    }
+
+    /// Attaches the file name to a parse error that carries a location. Other errors are
+    /// returned unchanged.
+    pub fn in_file(self, file: &str) -> AocError {
+        match self {
+            AocError::Parse {
+                message,
+                location: Some(location),
+            } => AocError::Parse {
+                message,
+                location: Some(SourceLocation {
+                    file: Some(file.to_string()),
+                    ..location
+                }),
+            },
+            other => other,
+        }
+    }
+
+    /// Moves a parse error which was raised for a fragment of the input to the input itself.
+    /// The fragment starts at `line` and `column`. Parse errors without a location point to the
+    /// start of the fragment afterwards.
+    pub fn offset_by(self, line: usize, column: usize) -> AocError {
+        match self {
+            AocError::Parse { message, location } => {
+                let location = match location {
+                    Some(location) if location.line == 1 => SourceLocation {
+                        line,
+                        column: column + location.column - 1,
+                        ..location
+                    },
+                    Some(location) => SourceLocation {
+                        line: line + location.line - 1,
+                        ..location
+                    },
+                    None => SourceLocation {
+                        file: None,
+                        line,
+                        column,
+                    },
+                };
+
+                AocError::Parse {
+                    message,
+                    location: Some(location),
+                }
+            }
+            other => other,
+        }
+    }
+
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            AocError::Parse { location, .. } => location.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl error::Error for AocError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            AocError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AocError::Io(err) => write!(f, "I/O error: {}", err),
+            AocError::Parse {
+                message,
+                location: Some(location),
+            } => write!(f, "Parse error at {}: {}", location, message),
+            AocError::Parse {
+                message,
+                location: None,
+            } => write!(f, "Parse error: {}", message),
+            AocError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            AocError::NoSolution(message) => write!(f, "No solution found: {}", message),
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        AocError::Io(err)
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(err: ParseIntError) -> Self {
+        AocError::parse(&err.to_string())
+    }
+}
+
+impl From<regex::Error> for AocError {
+    fn from(err: regex::Error) -> Self {
+        AocError::invalid_input(&format!("Invalid regular expression: {}", err))
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(err: ParseError) -> Self {
+        AocError::parse_at(err.line(), err.column(), err.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_keeps_location() {
+        let err: AocError = ParseError::new(3, 7, "Expected a number.").into();
+        let err = err.in_file("input.txt");
+
+        assert_eq!(
+            err.location(),
+            Some(&SourceLocation {
+                file: Some("input.txt".to_string()),
+                line: 3,
+                column: 7,
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "Parse error at input.txt:3:7: Expected a number."
+        );
+    }
+
+    #[test]
+    fn offset_errors_of_fragments() {
+        let location = |err: AocError| {
+            err.location()
+                .map(|location| (location.line, location.column))
+        };
+
+        assert_eq!(
+            location(AocError::parse_at(1, 4, "Expected a number.").offset_by(5, 10)),
+            Some((5, 13))
+        );
+        assert_eq!(
+            location(AocError::parse_at(2, 4, "Expected a number.").offset_by(5, 10)),
+            Some((6, 4))
+        );
+        assert_eq!(
+            location(AocError::parse("Unknown entry.").offset_by(5, 1)),
+            Some((5, 1))
+        );
+        assert!(AocError::invalid_input("Empty.")
+            .offset_by(5, 1)
+            .location()
+            .is_none());
+    }
+
+    #[test]
+    fn convert_from_standard_errors() {
+        let err: AocError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+
+        assert!(matches!(err, AocError::Io(_)));
+
+        let err: AocError = "abc".parse::<u32>().unwrap_err().into();
+
+        assert!(matches!(err, AocError::Parse { location: None, .. }));
+        assert_eq!(
+            err.offset_by(2, 5)
+                .location()
+                .map(|location| (location.line, location.column)),
+            Some((2, 5))
+        );
+    }
+}
@@ -1,33 +1,16 @@
-use std::error;
-use std::fmt;
 use std::fs;
 use std::io;
 use std::io::BufRead;
 
+pub mod cycle;
+mod error;
 pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod solution;
 
-pub type GenericResult<T> = Result<T, Box<dyn error::Error>>;
-
-#[derive(Debug)]
-pub struct GenericError {
-    message: String,
-}
-
-impl GenericError {
-    pub fn new(message: &str) -> GenericError {
-        GenericError {
-            message: message.to_string(),
-        }
-    }
-}
-
-impl error::Error for GenericError {}
-
-impl fmt::Display for GenericError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Generic error: {}", self.message)
-    }
-}
+pub use self::error::{AocError, AocResult, SourceLocation};
 
 pub fn read_raw_file_content(path: &str) -> io::Result<Vec<String>> {
     let file = fs::File::open(path)?;
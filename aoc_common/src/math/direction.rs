@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+
+use super::number_theory::gcd;
+use super::Point;
+
+/// A direction in the plane stored as the integer vector pointing into it, reduced by the gcd
+/// of its components. Two points which lie on the same ray from an origin therefore have equal
+/// directions without any floating point comparison.
+///
+/// Directions are ordered by their clockwise angle starting in the north, using the same y
+/// down orientation as `Point`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Direction {
+    dx: isize,
+    dy: isize,
+}
+
+impl Direction {
+    /// Returns `None` for the zero vector which does not have a direction.
+    pub fn new(dx: isize, dy: isize) -> Option<Direction> {
+        if dx == 0 && dy == 0 {
+            None
+        } else {
+            let divisor = gcd(dx.abs(), dy.abs());
+
+            Some(Direction {
+                dx: dx / divisor,
+                dy: dy / divisor,
+            })
+        }
+    }
+
+    pub fn between(from: Point, to: Point) -> Option<Direction> {
+        let Point(dx, dy) = to - from;
+
+        Direction::new(dx, dy)
+    }
+
+    pub fn dx(&self) -> isize {
+        self.dx
+    }
+
+    pub fn dy(&self) -> isize {
+        self.dy
+    }
+
+    pub fn as_point(&self) -> Point {
+        Point(self.dx, self.dy)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        Direction {
+            dx: -self.dx,
+            dy: -self.dy,
+        }
+    }
+
+    /// 0 for directions from north (inclusive) to south (exclusive) going clockwise, 1 for the
+    /// other half.
+    fn half(&self) -> u8 {
+        if self.dx > 0 || (self.dx == 0 && self.dy < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.half().cmp(&other.half()).then_with(|| {
+            // within the same half both directions are less than 180° apart so the sign of
+            // the cross product tells which one comes first
+            0.cmp(&self.as_point().cross(other.as_point()))
+        })
+    }
+}
+
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions_are_reduced() {
+        assert_eq!(Direction::new(4, -6), Direction::new(2, -3));
+        assert_ne!(Direction::new(4, -6), Direction::new(-2, 3));
+        assert_eq!(Direction::new(0, 5).unwrap().as_point(), Point::SOUTH);
+        assert_eq!(Direction::new(0, 0), None);
+        assert_eq!(
+            Direction::between(Point(1, 1), Point(-2, 7)),
+            Direction::new(-1, 2)
+        );
+    }
+
+    #[test]
+    fn clockwise_ordering_from_north() {
+        let mut directions: Vec<Direction> = vec![
+            Point::NORTH_WEST,
+            Point::SOUTH,
+            Point(1, -3),
+            Point::EAST,
+            Point(-3, 1),
+            Point::NORTH,
+            Point(2, 1),
+            Point::WEST,
+        ]
+        .into_iter()
+        .map(|Point(dx, dy)| Direction::new(dx, dy).unwrap())
+        .collect();
+
+        directions.sort();
+
+        let sorted: Vec<Point> = directions.iter().map(Direction::as_point).collect();
+
+        assert_eq!(
+            sorted,
+            vec![
+                Point::NORTH,
+                Point(1, -3),
+                Point::EAST,
+                Point(2, 1),
+                Point::SOUTH,
+                Point(-3, 1),
+                Point::WEST,
+                Point::NORTH_WEST,
+            ]
+        );
+    }
+}
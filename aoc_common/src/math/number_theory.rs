@@ -0,0 +1,475 @@
+use num_traits::{PrimInt, Signed};
+
+const SEGMENT_SIZE: u64 = 1 << 16;
+const SMALL_PRIMES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Non-negative greatest common divisor of `a` and `b`.
+///
+/// Panics if the result is not representable, which only happens for `gcd(MIN, MIN)` or
+/// `gcd(MIN, 0)` of a signed type.
+pub fn gcd<I: PrimInt>(a: I, b: I) -> I {
+    let (mut a, mut b) = (a, b);
+
+    while b != I::zero() {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+
+    if a < I::zero() {
+        I::zero()
+            .checked_sub(&a)
+            .expect("gcd does not fit into the integer type")
+    } else {
+        a
+    }
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b)` and `a * x + b * y = g`.
+pub fn extended_gcd<I: PrimInt + Signed>(a: I, b: I) -> (I, I, I) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (I::one(), I::zero());
+    let (mut old_y, mut y) = (I::zero(), I::one());
+
+    while r != I::zero() {
+        let quotient = old_r / r;
+        let next_r = old_r - quotient * r;
+        old_r = r;
+        r = next_r;
+        let next_x = old_x - quotient * x;
+        old_x = x;
+        x = next_x;
+        let next_y = old_y - quotient * y;
+        old_y = y;
+        y = next_y;
+    }
+
+    if old_r < I::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Least common multiple of `a` and `b` or `None` if it does not fit into `I`.
+pub fn checked_lcm<I: PrimInt>(a: I, b: I) -> Option<I> {
+    if a == I::zero() || b == I::zero() {
+        return Some(I::zero());
+    }
+
+    // the magnitude of any multiple of a signed minimum is too large
+    if a == I::min_value() || b == I::min_value() {
+        return None;
+    }
+
+    let result = (a / gcd(a, b)).checked_mul(&b)?;
+
+    if result < I::zero() {
+        I::zero().checked_sub(&result)
+    } else {
+        Some(result)
+    }
+}
+
+pub fn mod_mul(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let mut result = 1;
+    let mut base = base % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mod_mul(result, base, modulus);
+        }
+
+        base = mod_mul(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// Multiplicative inverse of `a` modulo `modulus` if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(i128::from(a % modulus), i128::from(modulus));
+
+    if g == 1 {
+        Some(x.rem_euclid(i128::from(modulus)) as u64)
+    } else {
+        None
+    }
+}
+
+/// Solves the system `x = residue (mod modulus)` for all given pairs. The moduli do not need to
+/// be coprime. Returns the smallest non-negative solution together with the least common
+/// multiple of the moduli, or `None` if the system has no solution or the result overflows.
+pub fn chinese_remainder(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for &(residue, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+
+        let (x, m) = result;
+        let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+        let (g, p, _) = extended_gcd(m, modulus);
+        let difference = residue - x;
+
+        if difference % g != 0 {
+            return None;
+        }
+
+        let lcm = m / g * modulus;
+
+        if lcm > i128::from(i64::MAX) {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (difference / g % step) * (p % step) % step;
+        result = ((x + m * k).rem_euclid(lcm), lcm);
+    }
+
+    Some((result.0 as i64, result.1 as i64))
+}
+
+/// Deterministic Miller–Rabin test which is exact for all `u64` values.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+
+    for &prime in SMALL_PRIMES.iter() {
+        if n.is_multiple_of(prime) {
+            return n == prime;
+        }
+    }
+
+    let shift = (n - 1).trailing_zeros();
+    let d = (n - 1) >> shift;
+
+    'witness: for &a in SMALL_PRIMES.iter() {
+        let mut x = mod_pow(a, d, n);
+
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+
+        for _ in 1..shift {
+            x = mod_mul(x, x, n);
+
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Prime factorization of `n` as ascending `(prime, exponent)` pairs, using Pollard's rho
+/// algorithm for everything which is not divisible by a small prime.
+pub fn prime_factors(n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    let mut n = n;
+
+    for &prime in SMALL_PRIMES.iter() {
+        while n > 0 && n.is_multiple_of(prime) {
+            factors.push(prime);
+            n /= prime;
+        }
+    }
+
+    let mut remaining = vec![n];
+
+    while let Some(number) = remaining.pop() {
+        if number <= 1 {
+            continue;
+        }
+
+        if is_prime(number) {
+            factors.push(number);
+        } else {
+            let divisor = pollard_rho(number);
+            remaining.push(divisor);
+            remaining.push(number / divisor);
+        }
+    }
+
+    factors.sort_unstable();
+
+    let mut result: Vec<(u64, u32)> = Vec::new();
+
+    for factor in factors {
+        match result.last_mut() {
+            Some((prime, exponent)) if *prime == factor => *exponent += 1,
+            _ => result.push((factor, 1)),
+        }
+    }
+
+    result
+}
+
+/// Finds a non-trivial divisor of the odd composite `n`.
+fn pollard_rho(n: u64) -> u64 {
+    for c in 1..n {
+        let step =
+            |x: u64| ((u128::from(x) * u128::from(x) + u128::from(c)) % u128::from(n)) as u64;
+        let (mut x, mut y, mut divisor) = (2, 2, 1);
+
+        while divisor == 1 {
+            x = step(x);
+            y = step(step(y));
+            divisor = gcd(x.abs_diff(y), n);
+        }
+
+        if divisor != n {
+            return divisor;
+        }
+    }
+
+    n
+}
+
+fn integer_sqrt(n: u64) -> u64 {
+    let mut root = (n as f64).sqrt() as u64;
+
+    while root > 0 && root.checked_mul(root).is_none_or(|square| square > n) {
+        root -= 1;
+    }
+
+    while (root + 1)
+        .checked_mul(root + 1)
+        .is_some_and(|square| square <= n)
+    {
+        root += 1;
+    }
+
+    root
+}
+
+fn simple_sieve(limit: u64) -> Vec<u64> {
+    let mut sieve = vec![true; limit as usize + 1];
+    let mut primes = Vec::new();
+
+    for i in 2..=limit as usize {
+        if sieve[i] {
+            primes.push(i as u64);
+
+            for j in (i * i..=limit as usize).step_by(i) {
+                sieve[j] = false;
+            }
+        }
+    }
+
+    primes
+}
+
+/// Primes in `[low, high)`. The range is sieved in fixed size segments so that memory usage
+/// only depends on the square root of `high`.
+pub fn primes_in_range(low: u64, high: u64) -> Vec<u64> {
+    if high <= 2 || low >= high {
+        return Vec::new();
+    }
+
+    let base_primes = simple_sieve(integer_sqrt(high - 1));
+    let mut primes = Vec::new();
+    let mut segment_start = low.max(2);
+
+    while segment_start < high {
+        let segment_end = high.min(segment_start + SEGMENT_SIZE);
+        let mut is_prime = vec![true; (segment_end - segment_start) as usize];
+
+        for &prime in base_primes.iter() {
+            if prime * prime >= segment_end {
+                break;
+            }
+
+            let first_multiple = (prime * prime).max(segment_start.div_ceil(prime) * prime);
+
+            for multiple in (first_multiple..segment_end).step_by(prime as usize) {
+                is_prime[(multiple - segment_start) as usize] = false;
+            }
+        }
+
+        primes.extend(
+            is_prime
+                .iter()
+                .enumerate()
+                .filter(|(_, &is_prime)| is_prime)
+                .map(|(offset, _)| segment_start + offset as u64),
+        );
+
+        segment_start = segment_end;
+    }
+
+    primes
+}
+
+/// All primes below a fixed limit with constant time access by index.
+#[derive(Debug, Clone)]
+pub struct PrimeSieve {
+    limit: u64,
+    primes: Vec<u64>,
+}
+
+impl PrimeSieve {
+    pub fn new(limit: u64) -> PrimeSieve {
+        PrimeSieve {
+            limit,
+            primes: primes_in_range(0, limit),
+        }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    pub fn primes(&self) -> &[u64] {
+        &self.primes
+    }
+
+    pub fn len(&self) -> usize {
+        self.primes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.primes.is_empty()
+    }
+
+    pub fn nth(&self, index: usize) -> Option<u64> {
+        self.primes.get(index).copied()
+    }
+
+    pub fn index_of(&self, prime: u64) -> Option<usize> {
+        self.primes.binary_search(&prime).ok()
+    }
+
+    /// Returns `None` if `n` lies outside of the sieved range.
+    pub fn is_prime(&self, n: u64) -> Option<bool> {
+        if n < self.limit {
+            Some(self.index_of(n).is_some())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(1, 1), 1);
+        assert_eq!(gcd(4, 2), 2);
+        assert_eq!(gcd(7, 5), 1);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0u64, 5), 5);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd does not fit into the integer type")]
+    fn gcd_of_min_is_rejected() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240i64, 46);
+
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_checked_lcm() {
+        assert_eq!(checked_lcm(14, 6), Some(42));
+        assert_eq!(checked_lcm(-4, 6), Some(12));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(checked_lcm(i64::MIN, 2), None);
+        assert_eq!(checked_lcm(1, i64::MIN), None);
+        assert_eq!(checked_lcm(i64::MIN, 0), Some(0));
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX - 1, u64::MAX, u64::MAX), u64::MAX - 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(chinese_remainder(&[(0, 4), (1, 6)]), None);
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes: Vec<u64> = (0..50).filter(|&n| is_prime(n)).collect();
+
+        assert_eq!(
+            primes,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+        );
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn test_prime_factors() {
+        assert_eq!(prime_factors(1), vec![]);
+        assert_eq!(prime_factors(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(
+            prime_factors(600_851_475_143),
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]
+        );
+        assert_eq!(
+            prime_factors(4_294_967_291 * 4_294_967_279),
+            vec![(4_294_967_279, 1), (4_294_967_291, 1)]
+        );
+    }
+
+    #[test]
+    fn test_segmented_sieve() {
+        assert_eq!(primes_in_range(0, 10), vec![2, 3, 5, 7]);
+        assert_eq!(primes_in_range(90, 110), vec![97, 101, 103, 107, 109]);
+
+        let segmented = primes_in_range(0, 3 * SEGMENT_SIZE + 17);
+        let simple = simple_sieve(3 * SEGMENT_SIZE + 16);
+
+        assert_eq!(segmented, simple);
+    }
+
+    #[test]
+    fn test_prime_sieve() {
+        let sieve = PrimeSieve::new(10);
+
+        assert_eq!(sieve.primes(), &[2, 3, 5, 7]);
+        assert_eq!(sieve.index_of(5), Some(2));
+        assert_eq!(sieve.is_prime(9), Some(false));
+        assert_eq!(sieve.is_prime(11), None);
+    }
+}
@@ -0,0 +1,539 @@
+use std::fmt;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_traits::{Num, Signed, ToPrimitive};
+
+/// A point or vector in the plane. The y axis points downwards, matching the way puzzle
+/// grids are laid out, so `NORTH` is `Point(0, -1)` and clockwise rotation maps `NORTH`
+/// onto `EAST`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, PartialOrd, Ord)]
+pub struct Point<T = isize>(pub T, pub T);
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default, PartialOrd, Ord)]
+pub struct Point3d<T = isize>(pub T, pub T, pub T);
+
+impl<T: Copy + Num> Point<T> {
+    pub fn length_sqrd(&self) -> T {
+        let Point(x, y) = *self;
+
+        x * x + y * y
+    }
+
+    pub fn dot(self, rhs: Point<T>) -> T {
+        self.0 * rhs.0 + self.1 * rhs.1
+    }
+
+    /// The z component of the cross product of both vectors extended into 3d space. It is
+    /// positive if `rhs` lies clockwise of `self`.
+    pub fn cross(self, rhs: Point<T>) -> T {
+        self.0 * rhs.1 - self.1 * rhs.0
+    }
+}
+
+impl<T: Copy + Num + ToPrimitive> Point<T> {
+    pub fn length(&self) -> f64 {
+        self.length_sqrd().to_f64().unwrap_or(f64::NAN).sqrt()
+    }
+}
+
+impl<T: Copy + Signed + Ord> Point<T> {
+    pub fn manhattan(self) -> T {
+        self.0.abs() + self.1.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Point<T>) -> T {
+        (self - other).manhattan()
+    }
+
+    pub fn chebyshev(self) -> T {
+        self.0.abs().max(self.1.abs())
+    }
+
+    pub fn chebyshev_distance(self, other: Point<T>) -> T {
+        (self - other).chebyshev()
+    }
+
+    pub fn rotate_right(self) -> Point<T> {
+        Point(-self.1, self.0)
+    }
+
+    pub fn rotate_left(self) -> Point<T> {
+        Point(self.1, -self.0)
+    }
+
+    /// Rotates clockwise by `quarter_turns` times 90°. Negative values rotate counter clockwise.
+    pub fn rotate(self, quarter_turns: i32) -> Point<T> {
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => self.rotate_right(),
+            2 => -self,
+            _ => self.rotate_left(),
+        }
+    }
+
+    /// The four orthogonally adjacent points in the order north, east, south, west.
+    pub fn neighbours(self) -> impl Iterator<Item = Point<T>> {
+        let (zero, one) = (T::zero(), T::one());
+        let offsets = [
+            Point(zero, -one),
+            Point(one, zero),
+            Point(zero, one),
+            Point(-one, zero),
+        ];
+
+        IntoIterator::into_iter(offsets).map(move |offset| self + offset)
+    }
+
+    /// All eight adjacent points, clockwise starting in the north.
+    pub fn neighbours_with_diagonals(self) -> impl Iterator<Item = Point<T>> {
+        let (zero, one) = (T::zero(), T::one());
+        let offsets = [
+            Point(zero, -one),
+            Point(one, -one),
+            Point(one, zero),
+            Point(one, one),
+            Point(zero, one),
+            Point(-one, one),
+            Point(-one, zero),
+            Point(-one, -one),
+        ];
+
+        IntoIterator::into_iter(offsets).map(move |offset| self + offset)
+    }
+}
+
+impl<T: Copy + Ord> Point<T> {
+    pub fn component_min(self, other: Point<T>) -> Point<T> {
+        Point(self.0.min(other.0), self.1.min(other.1))
+    }
+
+    pub fn component_max(self, other: Point<T>) -> Point<T> {
+        Point(self.0.max(other.0), self.1.max(other.1))
+    }
+
+    /// Returns the smallest and largest corner of the axis aligned box containing all points
+    /// or `None` if there are no points.
+    pub fn bounding_box<I: IntoIterator<Item = Point<T>>>(
+        points: I,
+    ) -> Option<(Point<T>, Point<T>)> {
+        points.into_iter().fold(None, |bounds, point| match bounds {
+            None => Some((point, point)),
+            Some((min, max)) => Some((min.component_min(point), max.component_max(point))),
+        })
+    }
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point(0, 0);
+    pub const NORTH: Point = Point(0, -1);
+    pub const NORTH_EAST: Point = Point(1, -1);
+    pub const EAST: Point = Point(1, 0);
+    pub const SOUTH_EAST: Point = Point(1, 1);
+    pub const SOUTH: Point = Point(0, 1);
+    pub const SOUTH_WEST: Point = Point(-1, 1);
+    pub const WEST: Point = Point(-1, 0);
+    pub const NORTH_WEST: Point = Point(-1, -1);
+
+    pub const CARDINAL_DIRECTIONS: [Point; 4] =
+        [Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST];
+}
+
+impl Point3d {
+    pub const ORIGIN: Point3d = Point3d(0, 0, 0);
+    pub const UNIT_X: Point3d = Point3d(1, 0, 0);
+    pub const UNIT_Y: Point3d = Point3d(0, 1, 0);
+    pub const UNIT_Z: Point3d = Point3d(0, 0, 1);
+}
+
+impl<T: Copy + Num> Point3d<T> {
+    pub fn length_sqrd(&self) -> T {
+        self.dot(*self)
+    }
+
+    pub fn dot(self, rhs: Point3d<T>) -> T {
+        self.0 * rhs.0 + self.1 * rhs.1 + self.2 * rhs.2
+    }
+
+    pub fn cross(self, rhs: Point3d<T>) -> Point3d<T> {
+        let Point3d(x, y, z) = self;
+        let Point3d(r_x, r_y, r_z) = rhs;
+
+        Point3d(y * r_z - z * r_y, z * r_x - x * r_z, x * r_y - y * r_x)
+    }
+}
+
+impl<T: Copy + Num + ToPrimitive> Point3d<T> {
+    pub fn length(&self) -> f64 {
+        self.length_sqrd().to_f64().unwrap_or(f64::NAN).sqrt()
+    }
+}
+
+impl<T: Copy + Signed + Ord> Point3d<T> {
+    pub fn manhattan(self) -> T {
+        self.0.abs() + self.1.abs() + self.2.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Point3d<T>) -> T {
+        (self - other).manhattan()
+    }
+
+    pub fn chebyshev(self) -> T {
+        self.0.abs().max(self.1.abs()).max(self.2.abs())
+    }
+
+    pub fn chebyshev_distance(self, other: Point3d<T>) -> T {
+        (self - other).chebyshev()
+    }
+
+    /// Rotates by `quarter_turns` times 90° around the x axis (right hand rule).
+    pub fn rotate_x(self, quarter_turns: i32) -> Point3d<T> {
+        let Point3d(x, y, z) = self;
+
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Point3d(x, -z, y),
+            2 => Point3d(x, -y, -z),
+            _ => Point3d(x, z, -y),
+        }
+    }
+
+    /// Rotates by `quarter_turns` times 90° around the y axis (right hand rule).
+    pub fn rotate_y(self, quarter_turns: i32) -> Point3d<T> {
+        let Point3d(x, y, z) = self;
+
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Point3d(z, y, -x),
+            2 => Point3d(-x, y, -z),
+            _ => Point3d(-z, y, x),
+        }
+    }
+
+    /// Rotates by `quarter_turns` times 90° around the z axis (right hand rule).
+    pub fn rotate_z(self, quarter_turns: i32) -> Point3d<T> {
+        let Point3d(x, y, z) = self;
+
+        match quarter_turns.rem_euclid(4) {
+            0 => self,
+            1 => Point3d(-y, x, z),
+            2 => Point3d(-x, -y, z),
+            _ => Point3d(y, -x, z),
+        }
+    }
+
+    /// The six points which share a face with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Point3d<T>> {
+        let (zero, one) = (T::zero(), T::one());
+        let offsets = [
+            Point3d(one, zero, zero),
+            Point3d(-one, zero, zero),
+            Point3d(zero, one, zero),
+            Point3d(zero, -one, zero),
+            Point3d(zero, zero, one),
+            Point3d(zero, zero, -one),
+        ];
+
+        IntoIterator::into_iter(offsets).map(move |offset| self + offset)
+    }
+
+    /// All 26 points which share a face, an edge or a corner with this one.
+    pub fn neighbours_with_diagonals(self) -> impl Iterator<Item = Point3d<T>> {
+        let steps = [-T::one(), T::zero(), T::one()];
+
+        IntoIterator::into_iter(steps)
+            .flat_map(move |x| {
+                IntoIterator::into_iter(steps).flat_map(move |y| {
+                    IntoIterator::into_iter(steps).map(move |z| Point3d(x, y, z))
+                })
+            })
+            .filter(|offset| *offset != Point3d(T::zero(), T::zero(), T::zero()))
+            .map(move |offset| self + offset)
+    }
+}
+
+impl<T: Copy + Ord> Point3d<T> {
+    pub fn component_min(self, other: Point3d<T>) -> Point3d<T> {
+        Point3d(
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    pub fn component_max(self, other: Point3d<T>) -> Point3d<T> {
+        Point3d(
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+
+    pub fn bounding_box<I: IntoIterator<Item = Point3d<T>>>(
+        points: I,
+    ) -> Option<(Point3d<T>, Point3d<T>)> {
+        points.into_iter().fold(None, |bounds, point| match bounds {
+            None => Some((point, point)),
+            Some((min, max)) => Some((min.component_min(point), max.component_max(point))),
+        })
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Point<T>) -> Self::Output {
+        let Point(x, y) = self;
+        let Point(r_x, r_y) = rhs;
+
+        Point(x + r_x, y + r_y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Point<T>) -> Self::Output {
+        let Point(x, y) = self;
+        let Point(r_x, r_y) = rhs;
+
+        Point(x - r_x, y - r_y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let Point(x, y) = self;
+
+        Point(x * rhs, y * rhs)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let Point(x, y) = self;
+
+        Point(x / rhs, y / rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        let Point(x, y) = self;
+
+        Point(-x, -y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Point<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Point<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Point<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl<T: Copy + DivAssign> DivAssign<T> for Point<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.0 /= rhs;
+        self.1 /= rhs;
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Point({},{})", self.0, self.1)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3d<T> {
+    type Output = Point3d<T>;
+
+    fn add(self, rhs: Point3d<T>) -> Self::Output {
+        let Point3d(x, y, z) = self;
+        let Point3d(r_x, r_y, r_z) = rhs;
+
+        Point3d(x + r_x, y + r_y, z + r_z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3d<T> {
+    type Output = Point3d<T>;
+
+    fn sub(self, rhs: Point3d<T>) -> Self::Output {
+        let Point3d(x, y, z) = self;
+        let Point3d(r_x, r_y, r_z) = rhs;
+
+        Point3d(x - r_x, y - r_y, z - r_z)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3d<T> {
+    type Output = Point3d<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let Point3d(x, y, z) = self;
+
+        Point3d(x * rhs, y * rhs, z * rhs)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Point3d<T> {
+    type Output = Point3d<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let Point3d(x, y, z) = self;
+
+        Point3d(x / rhs, y / rhs, z / rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3d<T> {
+    type Output = Point3d<T>;
+
+    fn neg(self) -> Self::Output {
+        let Point3d(x, y, z) = self;
+
+        Point3d(-x, -y, -z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point3d<T> {
+    fn add_assign(&mut self, rhs: Point3d<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+        self.2 += rhs.2;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point3d<T> {
+    fn sub_assign(&mut self, rhs: Point3d<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+        self.2 -= rhs.2;
+    }
+}
+
+impl<T: Copy + MulAssign> MulAssign<T> for Point3d<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+        self.2 *= rhs;
+    }
+}
+
+impl<T: Copy + DivAssign> DivAssign<T> for Point3d<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.0 /= rhs;
+        self.1 /= rhs;
+        self.2 /= rhs;
+    }
+}
+
+impl<T: Display> Display for Point3d<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Point3d({},{},{})", self.0, self.1, self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_operators() {
+        let mut point = Point(3, -4);
+
+        assert_eq!(point * 2, Point(6, -8));
+        assert_eq!(point / 2, Point(1, -2));
+        assert_eq!(-point, Point(-3, 4));
+
+        point += Point(1, 1);
+        point *= 3;
+        point -= Point::EAST;
+        point /= 2;
+
+        assert_eq!(point, Point(5, -4));
+        assert_eq!(Point3d(1, 2, 3) * 2 - Point3d::UNIT_Z, Point3d(2, 4, 5));
+    }
+
+    #[test]
+    fn distances() {
+        let point: Point<i64> = Point(3, -4);
+
+        assert_eq!(point.length(), 5.0);
+        assert_eq!(point.manhattan(), 7);
+        assert_eq!(point.chebyshev(), 4);
+        assert_eq!(Point(1, 1).manhattan_distance(Point(-2, 3)), 5);
+        assert_eq!(Point3d(1, 2, 3).manhattan_distance(Point3d(0, 0, 0)), 6);
+        assert_eq!(Point3d(1, -7, 3).chebyshev(), 7);
+        assert_eq!(Point3d(2, 3, 6).length(), 7.0);
+    }
+
+    #[test]
+    fn dot_and_cross_product() {
+        assert_eq!(Point(1, 2).dot(Point(3, 4)), 11);
+        assert!(Point::NORTH.cross(Point::EAST) > 0);
+        assert_eq!(Point3d::UNIT_X.cross(Point3d::UNIT_Y), Point3d::UNIT_Z);
+        assert_eq!(Point3d(1, 2, 3).dot(Point3d(4, 5, 6)), 32);
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Point::NORTH.rotate_right(), Point::EAST);
+        assert_eq!(Point::NORTH.rotate_left(), Point::WEST);
+        assert_eq!(Point(2, 1).rotate(2), Point(-2, -1));
+        assert_eq!(Point(2, 1).rotate(-1), Point(2, 1).rotate(3));
+        assert_eq!(Point3d::UNIT_X.rotate_z(1), Point3d::UNIT_Y);
+        assert_eq!(Point3d::UNIT_Y.rotate_x(1), Point3d::UNIT_Z);
+        assert_eq!(Point3d::UNIT_Z.rotate_y(1), Point3d::UNIT_X);
+        assert_eq!(Point3d(1, 2, 3).rotate_y(-1).rotate_y(1), Point3d(1, 2, 3));
+    }
+
+    #[test]
+    fn neighbours() {
+        let neighbours: Vec<Point> = Point(1, 1).neighbours().collect();
+
+        assert_eq!(
+            neighbours,
+            vec![Point(1, 0), Point(2, 1), Point(1, 2), Point(0, 1)]
+        );
+        assert_eq!(Point(0, 0).neighbours_with_diagonals().count(), 8);
+        assert_eq!(Point3d(0, 0, 0).neighbours().count(), 6);
+        assert_eq!(Point3d(0, 0, 0).neighbours_with_diagonals().count(), 26);
+    }
+
+    #[test]
+    fn bounding_box() {
+        let points = vec![Point(3, -1), Point(-2, 4), Point(0, 0)];
+
+        assert_eq!(
+            Point::bounding_box(points),
+            Some((Point(-2, -1), Point(3, 4)))
+        );
+        assert_eq!(Point::<isize>::bounding_box(vec![]), None);
+        assert_eq!(
+            Point3d(1, 5, 2).component_min(Point3d(4, 0, 2)),
+            Point3d(1, 0, 2)
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use super::Point;
+
+/// A lattice segment which is horizontal, vertical or diagonal at 45°, so that every point
+/// between `start` and `end` is reached in steps of length one. A segment may consist of a
+/// single point.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Segment {
+    start: Point,
+    end: Point,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Intersection {
+    None,
+    Point(Point),
+    Overlap(Segment),
+}
+
+impl Segment {
+    /// Returns `None` if the segment is neither axis aligned nor diagonal.
+    pub fn new(start: Point, end: Point) -> Option<Segment> {
+        let Point(dx, dy) = end - start;
+
+        if dx == 0 || dy == 0 || dx.abs() == dy.abs() {
+            Some(Segment { start, end })
+        } else {
+            None
+        }
+    }
+
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    pub fn end(&self) -> Point {
+        self.end
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.0 == self.end.0
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        !self.is_horizontal() && !self.is_vertical()
+    }
+
+    /// The unit step leading from `start` towards `end`. It is the origin for single points.
+    pub fn step(&self) -> Point {
+        let Point(dx, dy) = self.end - self.start;
+
+        Point(dx.signum(), dy.signum())
+    }
+
+    /// Number of steps from `start` to `end`.
+    pub fn length(&self) -> isize {
+        self.start.chebyshev_distance(self.end)
+    }
+
+    pub fn is_point(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn reversed(&self) -> Segment {
+        Segment {
+            start: self.end,
+            end: self.start,
+        }
+    }
+
+    /// All lattice points of the segment from `start` to `end`.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (start, step) = (self.start, self.step());
+
+        (0..=self.length()).map(move |index| start + step * index)
+    }
+
+    /// Number of steps from `start` to `point` or `None` if the point is not on the segment.
+    pub fn steps_to(&self, point: Point) -> Option<isize> {
+        let offset = point - self.start;
+        let steps = offset.chebyshev();
+
+        if steps <= self.length() && self.step() * steps == offset {
+            Some(steps)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.steps_to(point).is_some()
+    }
+
+    /// Calculates the lattice points both segments share. Crossing diagonals which only meet
+    /// between lattice points do not intersect.
+    pub fn intersection(&self, other: &Segment) -> Intersection {
+        if self.is_point() || other.is_point() {
+            let (point, segment) = if self.is_point() {
+                (self.start, other)
+            } else {
+                (other.start, self)
+            };
+
+            return if segment.contains(point) {
+                Intersection::Point(point)
+            } else {
+                Intersection::None
+            };
+        }
+
+        let (u, v) = (self.step(), other.step());
+        let offset = other.start - self.start;
+        let denominator = u.cross(v);
+
+        if denominator == 0 {
+            self.collinear_overlap(other)
+        } else {
+            let s = offset.cross(v);
+            let t = offset.cross(u);
+
+            if s % denominator != 0 || t % denominator != 0 {
+                return Intersection::None;
+            }
+
+            let (s, t) = (s / denominator, t / denominator);
+
+            if (0..=self.length()).contains(&s) && (0..=other.length()).contains(&t) {
+                Intersection::Point(self.start + u * s)
+            } else {
+                Intersection::None
+            }
+        }
+    }
+
+    fn collinear_overlap(&self, other: &Segment) -> Intersection {
+        let step = self.step();
+
+        if step.cross(other.start - self.start) != 0 {
+            return Intersection::None;
+        }
+
+        // both segments lie on the same line, measure the other one in steps along self
+        let position = |point: Point| {
+            let offset = point - self.start;
+            offset.chebyshev() * offset.dot(step).signum()
+        };
+        let (a, b) = (position(other.start), position(other.end));
+        let low = a.min(b).max(0);
+        let high = a.max(b).min(self.length());
+
+        if low > high {
+            Intersection::None
+        } else if low == high {
+            Intersection::Point(self.start + step * low)
+        } else {
+            Intersection::Overlap(Segment {
+                start: self.start + step * low,
+                end: self.start + step * high,
+            })
+        }
+    }
+
+    fn x_range(&self) -> (isize, isize) {
+        (self.start.0.min(self.end.0), self.start.0.max(self.end.0))
+    }
+
+    /// The range of y values covered in column `x` which has to lie within the segment.
+    fn y_range_at(&self, x: isize) -> (isize, isize) {
+        if self.is_vertical() {
+            (self.start.1.min(self.end.1), self.start.1.max(self.end.1))
+        } else {
+            let Point(step_x, step_y) = self.step();
+            let y = self.start.1 + step_y * (x - self.start.0) * step_x;
+
+            (y, y)
+        }
+    }
+}
+
+/// Counts the lattice points which are covered by at least two segments. The segments are
+/// swept column by column so that memory only depends on the number of segments and not on
+/// the area they span.
+pub fn count_overlapping_points(segments: &[Segment]) -> usize {
+    let mut segments: Vec<&Segment> = segments.iter().collect();
+    segments.sort_by_key(|segment| segment.x_range().0);
+
+    let mut active: Vec<&Segment> = Vec::new();
+    let mut events: Vec<(isize, isize)> = Vec::new();
+    let mut next = 0;
+    let mut x = isize::MIN;
+    let mut count = 0;
+
+    loop {
+        if active.is_empty() {
+            match segments.get(next) {
+                Some(segment) => x = x.max(segment.x_range().0),
+                None => return count,
+            }
+        }
+
+        while next < segments.len() && segments[next].x_range().0 == x {
+            active.push(segments[next]);
+            next += 1;
+        }
+
+        events.clear();
+
+        for segment in active.iter() {
+            let (low, high) = segment.y_range_at(x);
+            events.push((low, 1));
+            events.push((high + 1, -1));
+        }
+
+        events.sort_unstable();
+
+        let mut coverage = 0;
+        let mut previous_y = 0;
+
+        for &(y, delta) in events.iter() {
+            if coverage >= 2 {
+                count += (y - previous_y) as usize;
+            }
+
+            coverage += delta;
+            previous_y = y;
+        }
+
+        active.retain(|segment| segment.x_range().1 > x);
+        x += 1;
+    }
+}
+
+/// A point at which two polylines meet together with the number of steps each polyline takes
+/// from its start to get there.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Crossing {
+    pub point: Point,
+    pub steps_one: isize,
+    pub steps_two: isize,
+}
+
+/// A path of connected segments.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Polyline {
+    segments: Vec<Segment>,
+}
+
+impl Polyline {
+    /// Builds the polyline which starts at `start` and follows the relative `moves`. Returns
+    /// `None` if one of the moves cannot be represented by a `Segment`.
+    pub fn from_moves<I: IntoIterator<Item = Point>>(start: Point, moves: I) -> Option<Polyline> {
+        let mut segments = Vec::new();
+        let mut current = start;
+
+        for offset in moves {
+            let segment = Segment::new(current, current + offset)?;
+            current = segment.end();
+            segments.push(segment);
+        }
+
+        Some(Polyline { segments })
+    }
+
+    pub fn from_points(points: &[Point]) -> Option<Polyline> {
+        let segments = points
+            .windows(2)
+            .map(|window| Segment::new(window[0], window[1]))
+            .collect::<Option<Vec<Segment>>>()?;
+
+        Some(Polyline { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Total number of steps along the polyline.
+    pub fn length(&self) -> isize {
+        self.segments.iter().map(Segment::length).sum()
+    }
+
+    /// All lattice points in the order in which they are visited. Points where two segments
+    /// meet are only reported once.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let start = self.segments.first().map(Segment::start);
+
+        start.into_iter().chain(
+            self.segments
+                .iter()
+                .flat_map(|segment| segment.points().skip(1)),
+        )
+    }
+
+    /// Number of steps until `point` is reached for the first time.
+    pub fn steps_to(&self, point: Point) -> Option<isize> {
+        let mut steps = 0;
+
+        for segment in self.segments.iter() {
+            if let Some(additional_steps) = segment.steps_to(point) {
+                return Some(steps + additional_steps);
+            }
+
+            steps += segment.length();
+        }
+
+        None
+    }
+
+    /// Every meeting of both polylines. A point which is passed several times is reported
+    /// once per pair of passes; a point where two segments meet counts as a single pass.
+    pub fn crossings(&self, other: &Polyline) -> Vec<Crossing> {
+        let mut crossings = Vec::new();
+        let mut steps_one = 0;
+
+        for (index_one, segment_one) in self.segments.iter().enumerate() {
+            let mut steps_two = 0;
+
+            for (index_two, segment_two) in other.segments.iter().enumerate() {
+                let points: Vec<Point> = match segment_one.intersection(segment_two) {
+                    Intersection::None => vec![],
+                    Intersection::Point(point) => vec![point],
+                    Intersection::Overlap(overlap) => overlap.points().collect(),
+                };
+                let is_joint = |index: usize, segment: &Segment, point: Point| {
+                    index > 0 && point == segment.start()
+                };
+
+                crossings.extend(
+                    points
+                        .into_iter()
+                        .filter(|&point| {
+                            !is_joint(index_one, segment_one, point)
+                                && !is_joint(index_two, segment_two, point)
+                        })
+                        .map(|point| Crossing {
+                            point,
+                            steps_one: steps_one + segment_one.steps_to(point).unwrap_or(0),
+                            steps_two: steps_two + segment_two.steps_to(point).unwrap_or(0),
+                        }),
+                );
+
+                steps_two += segment_two.length();
+            }
+
+            steps_one += segment_one.length();
+        }
+
+        crossings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: (isize, isize), end: (isize, isize)) -> Segment {
+        Segment::new(Point(start.0, start.1), Point(end.0, end.1)).unwrap()
+    }
+
+    #[test]
+    fn only_axis_aligned_and_diagonal_segments() {
+        assert!(Segment::new(Point(0, 0), Point(3, 3)).is_some());
+        assert!(Segment::new(Point(0, 0), Point(-2, 0)).is_some());
+        assert!(Segment::new(Point(1, 1), Point(1, 1)).is_some());
+        assert!(Segment::new(Point(0, 0), Point(1, 2)).is_none());
+    }
+
+    #[test]
+    fn rasterization() {
+        let points: Vec<Point> = segment((3, 1), (1, 3)).points().collect();
+
+        assert_eq!(points, vec![Point(3, 1), Point(2, 2), Point(1, 3)]);
+        assert_eq!(segment((2, 2), (2, 2)).points().count(), 1);
+        assert_eq!(segment((3, 1), (1, 3)).steps_to(Point(1, 3)), Some(2));
+        assert_eq!(segment((3, 1), (1, 3)).steps_to(Point(2, 3)), None);
+    }
+
+    #[test]
+    fn orthogonal_intersection() {
+        assert_eq!(
+            segment((4, 4), (0, 4)).intersection(&segment((2, 2), (2, 6))),
+            Intersection::Point(Point(2, 4))
+        );
+        assert_eq!(
+            segment((4, 4), (0, 4)).intersection(&segment((5, 2), (5, 6))),
+            Intersection::None
+        );
+    }
+
+    #[test]
+    fn diagonal_intersections() {
+        assert_eq!(
+            segment((0, 0), (4, 4)).intersection(&segment((0, 4), (4, 0))),
+            Intersection::Point(Point(2, 2))
+        );
+        assert_eq!(
+            segment((0, 0), (1, 1)).intersection(&segment((0, 1), (1, 0))),
+            Intersection::None
+        );
+        assert_eq!(
+            segment((0, 0), (4, 4)).intersection(&segment((3, 0), (3, 5))),
+            Intersection::Point(Point(3, 3))
+        );
+    }
+
+    #[test]
+    fn parallel_segments() {
+        assert_eq!(
+            segment((3, 3), (6, 3)).intersection(&segment((4, 2), (7, 2))),
+            Intersection::None
+        );
+        assert_eq!(
+            segment((3, 3), (5, 3)).intersection(&segment((6, 3), (8, 3))),
+            Intersection::None
+        );
+        assert_eq!(
+            segment((3, 3), (5, 3)).intersection(&segment((8, 3), (5, 3))),
+            Intersection::Point(Point(5, 3))
+        );
+        assert_eq!(
+            segment((1, 4), (5, 4)).intersection(&segment((7, 4), (2, 4))),
+            Intersection::Overlap(segment((2, 4), (5, 4)))
+        );
+        assert_eq!(
+            segment((0, 0), (5, 5)).intersection(&segment((6, 6), (2, 2))),
+            Intersection::Overlap(segment((2, 2), (5, 5)))
+        );
+    }
+
+    #[test]
+    fn sweep_line_counts_overlapping_points() {
+        let segments = vec![
+            segment((0, 9), (5, 9)),
+            segment((8, 0), (0, 8)),
+            segment((9, 4), (3, 4)),
+            segment((2, 2), (2, 1)),
+            segment((7, 0), (7, 4)),
+            segment((6, 4), (2, 0)),
+            segment((0, 9), (2, 9)),
+            segment((3, 4), (1, 4)),
+            segment((0, 0), (8, 8)),
+            segment((5, 5), (8, 2)),
+        ];
+        let axis_aligned: Vec<Segment> = segments
+            .iter()
+            .filter(|segment| !segment.is_diagonal())
+            .cloned()
+            .collect();
+
+        assert_eq!(count_overlapping_points(&axis_aligned), 5);
+        assert_eq!(count_overlapping_points(&segments), 12);
+        assert_eq!(count_overlapping_points(&[]), 0);
+    }
+
+    #[test]
+    fn polyline_crossings() {
+        let one = Polyline::from_moves(
+            Point::ORIGIN,
+            vec![Point(8, 0), Point(0, -5), Point(-5, 0), Point(0, 3)],
+        )
+        .unwrap();
+        let two = Polyline::from_moves(
+            Point::ORIGIN,
+            vec![Point(0, -7), Point(6, 0), Point(0, 4), Point(-4, 0)],
+        )
+        .unwrap();
+
+        let mut crossings: Vec<(Point, isize)> = one
+            .crossings(&two)
+            .into_iter()
+            .filter(|crossing| crossing.point != Point::ORIGIN)
+            .map(|crossing| (crossing.point, crossing.steps_one + crossing.steps_two))
+            .collect();
+        crossings.sort_by_key(|&(_, steps)| steps);
+
+        assert_eq!(crossings, vec![(Point(6, -5), 30), (Point(3, -3), 40)]);
+        assert_eq!(one.length(), 21);
+        assert_eq!(one.steps_to(Point(3, -3)), Some(20));
+        assert_eq!(one.points().count(), 22);
+    }
+
+    #[test]
+    fn polyline_crossing_at_corners() {
+        let one = Polyline::from_points(&[Point(0, 0), Point(4, 0), Point(4, 4)]).unwrap();
+        let two = Polyline::from_points(&[Point(4, -2), Point(4, 0), Point(7, 0)]).unwrap();
+
+        let crossings: Vec<(Point, isize, isize)> = one
+            .crossings(&two)
+            .into_iter()
+            .map(|crossing| (crossing.point, crossing.steps_one, crossing.steps_two))
+            .collect();
+
+        assert_eq!(crossings, vec![(Point(4, 0), 4, 2)]);
+    }
+}
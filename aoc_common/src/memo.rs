@@ -0,0 +1,248 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+/// Cache for a recursive function over hashable keys. A bounded cache evicts the least recently
+/// used entry once it is full.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    entries: HashMap<K, (V, u64)>,
+    recently_used: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            entries: HashMap::new(),
+            recently_used: BTreeMap::new(),
+            capacity: None,
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn bounded(capacity: usize) -> Memo<K, V> {
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::new()
+        }
+    }
+
+    /// Returns the value of `f` for `key`, computing it only if it is not cached. `f` receives a
+    /// function through which it can recursively evaluate itself for other keys:
+    ///
+    /// ```
+    /// use aoc_common::memo::Memo;
+    ///
+    /// let fibonacci = Memo::new().compute(80, &|fibonacci, &n: &u64| {
+    ///     if n < 2 {
+    ///         n
+    ///     } else {
+    ///         fibonacci(n - 1) + fibonacci(n - 2)
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(fibonacci, 23_416_728_348_467_685);
+    /// ```
+    pub fn compute<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let value = f(&mut |next| self.compute(next, f), &key);
+        self.insert(key, value.clone());
+
+        value
+    }
+
+    /// Looks up a cached value and counts the lookup in the statistics.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let tick = self.tick();
+
+        match self.entries.get_mut(key) {
+            Some((value, last_used)) => {
+                self.recently_used.remove(last_used);
+                self.recently_used.insert(tick, key.clone());
+                *last_used = tick;
+                self.stats.hits += 1;
+
+                Some(value.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        let tick = self.tick();
+
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (value, tick)) {
+            self.recently_used.remove(&last_used);
+        }
+
+        self.recently_used.insert(tick, key);
+
+        while self.capacity.is_some_and(|capacity| self.entries.len() > capacity) {
+            if let Some((&oldest, _)) = self.recently_used.iter().next() {
+                if let Some(key) = self.recently_used.remove(&oldest) {
+                    self.entries.remove(&key);
+                    self.stats.evictions += 1;
+                }
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Drops all cached values but keeps the statistics.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recently_used.clear();
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+/// Fills a table of `length` entries in ascending order. Every entry is computed from the
+/// entries before it and its index.
+pub fn table<V, F>(length: usize, mut f: F) -> Vec<V>
+where
+    F: FnMut(&[V], usize) -> V,
+{
+    let mut table = Vec::with_capacity(length);
+
+    for index in 0..length {
+        let value = f(&table, index);
+        table.push(value);
+    }
+
+    table
+}
+
+/// Fills a `rows` × `columns` table row by row. Every entry is computed from the completed rows
+/// above, the entries to its left and its position.
+pub fn table_2d<V, F>(rows: usize, columns: usize, mut f: F) -> Vec<Vec<V>>
+where
+    F: FnMut(&[Vec<V>], &[V], usize, usize) -> V,
+{
+    let mut table: Vec<Vec<V>> = Vec::with_capacity(rows);
+
+    for row in 0..rows {
+        let mut current = Vec::with_capacity(columns);
+
+        for column in 0..columns {
+            let value = f(&table, &current, row, column);
+            current.push(value);
+        }
+
+        table.push(current);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binomial(memo: &mut Memo<(u64, u64), u64>, n: u64, k: u64) -> u64 {
+        memo.compute((n, k), &|binomial, &(n, k)| {
+            if k == 0 || k == n {
+                1
+            } else {
+                binomial((n - 1, k - 1)) + binomial((n - 1, k))
+            }
+        })
+    }
+
+    #[test]
+    fn memo_caches_recursive_calls() {
+        let mut memo = Memo::new();
+
+        assert_eq!(binomial(&mut memo, 60, 30), 118_264_581_564_861_424);
+        assert_eq!(memo.stats().misses, memo.len());
+        assert!(memo.len() < 31 * 31);
+
+        let hits = memo.stats().hits;
+        assert_eq!(binomial(&mut memo, 60, 30), 118_264_581_564_861_424);
+        assert_eq!(memo.stats().hits, hits + 1);
+    }
+
+    #[test]
+    fn bounded_memo_evicts_least_recently_used() {
+        let mut memo = Memo::bounded(2);
+
+        memo.insert('a', 1);
+        memo.insert('b', 2);
+        assert_eq!(memo.get(&'a'), Some(1));
+        memo.insert('c', 3);
+
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&'b'), None);
+        assert_eq!(memo.get(&'a'), Some(1));
+        assert_eq!(
+            memo.stats(),
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                evictions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn tables() {
+        let fibonacci = table(10, |previous, index| {
+            if index < 2 {
+                index
+            } else {
+                previous[index - 1] + previous[index - 2]
+            }
+        });
+
+        assert_eq!(fibonacci[9], 34);
+
+        let paths = table_2d(3, 4, |above, left, row, column| {
+            if row == 0 || column == 0 {
+                1
+            } else {
+                above[row - 1][column] + left[column - 1]
+            }
+        });
+
+        assert_eq!(paths[2][3], 10);
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use crate::math::Point;
+use crate::{AocError, AocResult};
+
+/// Letters which are 4 pixels wide and 6 pixels high, separated by one blank column.
+const SMALL_FONT: (&str, &str) = (
+    "ABCEFGHIJKLOPRSUYZ",
+    "
+.##..###...##..####.####..##..#..#..###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#...#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####...#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#...#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#...#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#..###..##..#..#.####..##..#....#..#.###...##....#...####",
+);
+
+/// Letters which are 6 pixels wide and 10 pixels high, separated by two blank columns.
+const LARGE_FONT: (&str, &str) = (
+    "ABCEFGHJKLNPRXZ",
+    "
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######",
+);
+
+/// Reads the letters of a grid rendered in one of the block-letter fonts. Rows may have
+/// different lengths and surrounding blank rows and columns are ignored. The font is chosen by
+/// the height of the lit pixels.
+pub fn recognize(grid: &[Vec<bool>]) -> AocResult<String> {
+    let rows = lit_rows(grid);
+    let font = match rows.len() {
+        0 => return Ok(String::new()),
+        6 => glyph_table(SMALL_FONT),
+        10 => glyph_table(LARGE_FONT),
+        height => {
+            return Err(AocError::invalid_input(&format!(
+                "Letters are {} pixels high but only fonts with 6 and 10 pixels are known.",
+                height
+            )))
+        }
+    };
+    let columns = columns(rows);
+
+    glyphs(&columns)
+        .map(|(x, glyph)| {
+            font.get(glyph).cloned().ok_or_else(|| {
+                AocError::invalid_input(&format!(
+                    "Unknown letter in columns {} to {}.",
+                    x,
+                    x + glyph.len() - 1
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Reads the letters formed by the given lit points.
+pub fn recognize_points<I: IntoIterator<Item = Point>>(points: I) -> AocResult<String> {
+    let points: Vec<Point> = points.into_iter().collect();
+
+    let (min_x, min_y) = match (
+        points.iter().map(|point| point.0).min(),
+        points.iter().map(|point| point.1).min(),
+    ) {
+        (Some(min_x), Some(min_y)) => (min_x, min_y),
+        _ => return Ok(String::new()),
+    };
+
+    let mut grid: Vec<Vec<bool>> = Vec::new();
+
+    for Point(x, y) in points {
+        let (x, y) = ((x - min_x) as usize, (y - min_y) as usize);
+
+        if grid.len() <= y {
+            grid.resize(y + 1, Vec::new());
+        }
+
+        if grid[y].len() <= x {
+            grid[y].resize(x + 1, false);
+        }
+
+        grid[y][x] = true;
+    }
+
+    recognize(&grid)
+}
+
+/// Reads the letters of a rendering in which `lit` marks the lit pixels.
+pub fn recognize_str(rendering: &str, lit: char) -> AocResult<String> {
+    recognize(&to_grid(rendering, lit))
+}
+
+fn to_grid(rendering: &str, lit: char) -> Vec<Vec<bool>> {
+    rendering
+        .lines()
+        .map(|line| line.chars().map(|chr| chr == lit).collect())
+        .collect()
+}
+
+/// The rows from the first to the last one containing a lit pixel.
+fn lit_rows(grid: &[Vec<bool>]) -> &[Vec<bool>] {
+    let top = grid.iter().position(|row| row.contains(&true));
+    let bottom = grid.iter().rposition(|row| row.contains(&true));
+
+    match (top, bottom) {
+        (Some(top), Some(bottom)) => &grid[top..=bottom],
+        _ => &[],
+    }
+}
+
+/// Every column as a bit mask with the top row in bit 0. Supports up to 16 rows.
+fn columns(rows: &[Vec<bool>]) -> Vec<u16> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    (0..width)
+        .map(|x| {
+            rows.iter()
+                .enumerate()
+                .filter(|(_, row)| row.get(x).cloned().unwrap_or(false))
+                .fold(0, |column, (y, _)| column | 1 << y)
+        })
+        .collect()
+}
+
+/// Splits the columns at blank columns and returns every glyph together with its first column.
+fn glyphs(columns: &[u16]) -> impl Iterator<Item = (usize, &[u16])> {
+    let mut x = 0;
+
+    std::iter::from_fn(move || {
+        let start = x + columns[x..].iter().position(|&column| column != 0)?;
+        let end = columns[start..]
+            .iter()
+            .position(|&column| column == 0)
+            .map_or(columns.len(), |length| start + length);
+
+        x = end;
+        Some((start, &columns[start..end]))
+    })
+}
+
+fn glyph_table((letters, rendering): (&str, &str)) -> HashMap<Vec<u16>, char> {
+    let grid = to_grid(rendering, '#');
+    let columns = columns(lit_rows(&grid));
+
+    glyphs(&columns)
+        .map(|(_, glyph)| glyph.to_vec())
+        .zip(letters.chars())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts_contain_every_letter() {
+        for font in &[SMALL_FONT, LARGE_FONT] {
+            let (letters, _) = font;
+
+            assert_eq!(glyph_table(*font).len(), letters.len());
+        }
+    }
+
+    #[test]
+    fn recognize_small_letters() {
+        let rendering = " ###   ##  #  # ###  #    ###  #  # #  #
+ #  # #  # # #  #  # #    #  # #  # # #
+ #  # #    ##   #  # #    #  # #  # ##
+ ###  #    # #  ###  #    ###  #  # # #
+ #    #  # # #  # #  #    #    #  # # #
+ #     ##  #  # #  # #### #     ##  #  #   ";
+
+        assert_eq!(recognize_str(rendering, '#').unwrap(), "PCKRLPUK");
+    }
+
+    #[test]
+    fn recognize_large_letters() {
+        let rendering = "
+#####...#####....####...######..######
+#....#..#....#..#....#.......#..#.....
+#....#..#....#..#............#..#.....
+#....#..#....#..#...........#...#.....
+#####...#####...#..........#....#####.
+#..#....#....#..#.........#.....#.....
+#...#...#....#..#........#......#.....
+#...#...#....#..#.......#.......#.....
+#....#..#....#..#....#..#.......#.....
+#....#..#####....####...######..######
+";
+
+        assert_eq!(recognize_str(rendering, '#').unwrap(), "RBCZE");
+    }
+
+    #[test]
+    fn recognize_points_and_failures() {
+        let points = "..##\n...#\n...#\n...#\n#..#\n.##."
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, chr)| chr == '#')
+                    .map(move |(x, _)| Point(x as isize - 10, y as isize + 3))
+            });
+
+        assert_eq!(recognize_points(points).unwrap(), "J");
+        assert_eq!(recognize_points(Vec::new()).unwrap(), "");
+        assert!(recognize_str("#\n#\n#", '#').is_err());
+        assert!(recognize_str("##\n#\n#\n#\n#\n##", '#').is_err());
+    }
+}
@@ -0,0 +1,589 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Parse error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Position in the input text. Lines and columns are 1-based and columns count characters.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Cursor<'a> {
+        Cursor::at_line(text, 1)
+    }
+
+    fn at_line(text: &'a str, line: usize) -> Cursor<'a> {
+        Cursor {
+            text,
+            offset: 0,
+            line,
+            column: 1,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn remaining(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    pub fn error(&self, message: &str) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        for char in self.text[self.offset..self.offset + bytes].chars() {
+            if char == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+
+        self.offset += bytes;
+    }
+
+    fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+        let remaining = self.remaining();
+        let len = remaining
+            .char_indices()
+            .find(|(_, char)| !predicate(*char))
+            .map(|(idx, _)| idx)
+            .unwrap_or_else(|| remaining.len());
+
+        self.advance(len);
+        &remaining[..len]
+    }
+}
+
+pub trait Parser<T> {
+    fn parse(&self, cursor: &mut Cursor) -> Result<T, ParseError>;
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&mut Cursor) -> Result<T, ParseError>,
+{
+    fn parse(&self, cursor: &mut Cursor) -> Result<T, ParseError> {
+        self(cursor)
+    }
+}
+
+pub fn literal(expected: &str) -> impl Parser<()> + '_ {
+    move |cursor: &mut Cursor| {
+        if cursor.remaining().starts_with(expected) {
+            cursor.advance(expected.len());
+            Ok(())
+        } else {
+            Err(cursor.error(&format!("Expected '{}'.", expected)))
+        }
+    }
+}
+
+/// Skips spaces and tabs, but not line breaks.
+pub fn spaces() -> impl Parser<()> {
+    |cursor: &mut Cursor| {
+        cursor.take_while(|char| char == ' ' || char == '\t');
+        Ok(())
+    }
+}
+
+pub fn take_while1<'a, P>(predicate: P, description: &'a str) -> impl Parser<String> + 'a
+where
+    P: Fn(char) -> bool + 'a,
+{
+    move |cursor: &mut Cursor| {
+        let start = *cursor;
+        let value = cursor.take_while(&predicate);
+
+        if value.is_empty() {
+            Err(start.error(&format!("Expected {}.", description)))
+        } else {
+            Ok(value.to_string())
+        }
+    }
+}
+
+pub fn word() -> impl Parser<String> {
+    take_while1(char::is_alphanumeric, "a word")
+}
+
+pub fn unsigned<T: FromStr>() -> impl Parser<T> {
+    |cursor: &mut Cursor| {
+        let start = *cursor;
+        let digits = cursor.take_while(|char| char.is_ascii_digit());
+
+        if digits.is_empty() {
+            return Err(start.error("Expected a number."));
+        }
+
+        digits
+            .parse()
+            .map_err(|_| start.error(&format!("Number {} is out of range.", digits)))
+    }
+}
+
+pub fn signed<T: FromStr>() -> impl Parser<T> {
+    |cursor: &mut Cursor| {
+        let start = *cursor;
+
+        if let Some('-') | Some('+') = cursor.peek() {
+            cursor.advance(1);
+        }
+
+        let digits = cursor.take_while(|char| char.is_ascii_digit());
+
+        if digits.is_empty() {
+            return Err(start.error("Expected a signed number."));
+        }
+
+        let number = &start.remaining()[..cursor.offset - start.offset];
+
+        number
+            .parse()
+            .map_err(|_| start.error(&format!("Number {} is out of range.", number)))
+    }
+}
+
+pub fn map<T, U, P, F>(parser: P, f: F) -> impl Parser<U>
+where
+    P: Parser<T>,
+    F: Fn(T) -> U,
+{
+    move |cursor: &mut Cursor| parser.parse(cursor).map(&f)
+}
+
+/// Like `map` but the conversion may fail. The error points to the start of the parsed value.
+pub fn try_map<T, U, P, F>(parser: P, f: F) -> impl Parser<U>
+where
+    P: Parser<T>,
+    F: Fn(T) -> Result<U, String>,
+{
+    move |cursor: &mut Cursor| {
+        let start = *cursor;
+        let value = parser.parse(cursor)?;
+
+        f(value).map_err(|message| start.error(&message))
+    }
+}
+
+pub fn pair<A, B, PA, PB>(first: PA, second: PB) -> impl Parser<(A, B)>
+where
+    PA: Parser<A>,
+    PB: Parser<B>,
+{
+    move |cursor: &mut Cursor| {
+        let a = first.parse(cursor)?;
+        let b = second.parse(cursor)?;
+
+        Ok((a, b))
+    }
+}
+
+pub fn preceded<A, B, PA, PB>(prefix: PA, parser: PB) -> impl Parser<B>
+where
+    PA: Parser<A>,
+    PB: Parser<B>,
+{
+    move |cursor: &mut Cursor| {
+        prefix.parse(cursor)?;
+        parser.parse(cursor)
+    }
+}
+
+pub fn terminated<A, B, PA, PB>(parser: PA, suffix: PB) -> impl Parser<A>
+where
+    PA: Parser<A>,
+    PB: Parser<B>,
+{
+    move |cursor: &mut Cursor| {
+        let value = parser.parse(cursor)?;
+        suffix.parse(cursor)?;
+
+        Ok(value)
+    }
+}
+
+pub fn delimited<A, B, C, PA, PB, PC>(open: PA, parser: PB, close: PC) -> impl Parser<B>
+where
+    PA: Parser<A>,
+    PB: Parser<B>,
+    PC: Parser<C>,
+{
+    move |cursor: &mut Cursor| {
+        open.parse(cursor)?;
+        let value = parser.parse(cursor)?;
+        close.parse(cursor)?;
+
+        Ok(value)
+    }
+}
+
+/// Tries the parser and rewinds the cursor if it fails.
+pub fn optional<T, P: Parser<T>>(parser: P) -> impl Parser<Option<T>> {
+    move |cursor: &mut Cursor| {
+        let start = *cursor;
+
+        match parser.parse(cursor) {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => {
+                *cursor = start;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Parses one or more values separated by `separator`.
+pub fn separated_list<T, S, P, PS>(parser: P, separator: PS) -> impl Parser<Vec<T>>
+where
+    P: Parser<T>,
+    PS: Parser<S>,
+{
+    move |cursor: &mut Cursor| {
+        let mut result = vec![parser.parse(cursor)?];
+
+        loop {
+            let before_separator = *cursor;
+
+            if separator.parse(cursor).is_err() {
+                *cursor = before_separator;
+                break;
+            }
+
+            result.push(parser.parse(cursor)?);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Parses signed numbers separated by `separator`, allowing spaces around each number.
+pub fn signed_list<'a, T: FromStr + 'a>(separator: &'a str) -> impl Parser<Vec<T>> + 'a {
+    separated_list(
+        delimited(spaces(), signed(), spaces()),
+        literal(separator),
+    )
+}
+
+pub fn parse_all<T, P: Parser<T>>(parser: P, text: &str) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(text);
+    parse_to_end(&parser, &mut cursor)
+}
+
+fn parse_to_end<T, P: Parser<T>>(parser: &P, cursor: &mut Cursor) -> Result<T, ParseError> {
+    let value = parser.parse(cursor)?;
+
+    if cursor.is_at_end() {
+        Ok(value)
+    } else {
+        Err(cursor.error("Unexpected trailing input."))
+    }
+}
+
+/// Parses the whole `text` with the given parser. `text` starts at `line` and `column` of the
+/// input, so errors point into the input instead of into `text`.
+pub fn parse_at<T, P: Parser<T>>(
+    parser: P,
+    text: &str,
+    line: usize,
+    column: usize,
+) -> Result<T, ParseError> {
+    let mut cursor = Cursor {
+        column,
+        ..Cursor::at_line(text, line)
+    };
+    parse_to_end(&parser, &mut cursor)
+}
+
+/// Splits `line` at whitespace like `str::split_whitespace` and pairs every token with its
+/// 1-based column.
+pub fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace().map(move |token| {
+        let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+
+        (line[..offset].chars().count() + 1, token)
+    })
+}
+
+/// Parses every line with the given parser. Line numbers in errors refer to `lines`.
+pub fn parse_lines<T, P, S>(parser: P, lines: &[S]) -> Result<Vec<T>, ParseError>
+where
+    P: Parser<T>,
+    S: AsRef<str>,
+{
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_to_end(&parser, &mut Cursor::at_line(line.as_ref(), idx + 1)))
+        .collect()
+}
+
+/// Splits lines into groups which are separated by one or more blank lines.
+pub fn split_groups<S: AsRef<str>>(lines: &[S]) -> Vec<&[S]> {
+    lines
+        .split(|line| line.as_ref().trim().is_empty())
+        .filter(|group| !group.is_empty())
+        .collect()
+}
+
+/// Parses every line of every blank-line separated group with the given parser.
+pub fn parse_groups<T, P, S>(parser: P, lines: &[S]) -> Result<Vec<Vec<T>>, ParseError>
+where
+    P: Parser<T>,
+    S: AsRef<str>,
+{
+    let mut result = Vec::new();
+    let mut current_group = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let line = line.as_ref();
+
+        if line.trim().is_empty() {
+            if !current_group.is_empty() {
+                result.push(std::mem::take(&mut current_group));
+            }
+        } else {
+            current_group.push(parse_to_end(&parser, &mut Cursor::at_line(line, idx + 1))?);
+        }
+    }
+
+    if !current_group.is_empty() {
+        result.push(current_group);
+    }
+
+    Ok(result)
+}
+
+/// Parses a rectangular grid where every character is converted by `cell`. Rows are indexed
+/// first, i.e. `grid[y][x]`.
+pub fn parse_grid<T, F, S>(lines: &[S], cell: F) -> Result<Vec<Vec<T>>, ParseError>
+where
+    F: Fn(char) -> Option<T>,
+    S: AsRef<str>,
+{
+    let mut result: Vec<Vec<T>> = Vec::with_capacity(lines.len());
+
+    for (y, line) in lines.iter().enumerate() {
+        let line = line.as_ref();
+        let mut row = Vec::with_capacity(line.len());
+
+        for (x, char) in line.chars().enumerate() {
+            let value = cell(char).ok_or_else(|| {
+                ParseError::new(y + 1, x + 1, &format!("Unexpected character '{}'.", char))
+            })?;
+            row.push(value);
+        }
+
+        if let Some(first_row) = result.first() {
+            if first_row.len() != row.len() {
+                return Err(ParseError::new(
+                    y + 1,
+                    row.len().min(first_row.len()) + 1,
+                    &format!(
+                        "Expected row of width {} but found width {}.",
+                        first_row.len(),
+                        row.len()
+                    ),
+                ));
+            }
+        }
+
+        result.push(row);
+    }
+
+    Ok(result)
+}
+
+pub fn parse_char_grid<S: AsRef<str>>(lines: &[S]) -> Result<Vec<Vec<char>>, ParseError> {
+    parse_grid(lines, Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_unsigned_and_signed_numbers() {
+        assert_eq!(parse_all(unsigned::<u32>(), "1234"), Ok(1234));
+        assert_eq!(parse_all(signed::<i32>(), "-42"), Ok(-42));
+        assert_eq!(parse_all(signed::<i32>(), "+7"), Ok(7));
+    }
+
+    #[test]
+    fn number_errors_point_to_position() {
+        let error = parse_all(preceded(literal("x="), unsigned::<u8>()), "x=300").unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (1, 3));
+
+        let error = parse_all(unsigned::<u8>(), "12a").unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (1, 3));
+    }
+
+    #[test]
+    fn parse_at_offsets_positions() {
+        assert_eq!(parse_at(signed::<i32>(), "-17", 4, 9), Ok(-17));
+
+        let error = parse_at(unsigned::<u32>(), "12x", 4, 9).unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (4, 11));
+    }
+
+    #[test]
+    fn tokens_have_columns() {
+        assert_eq!(
+            tokens("  +1 \t-22 3").collect::<Vec<_>>(),
+            vec![(3, "+1"), (7, "-22"), (11, "3")]
+        );
+    }
+
+    #[test]
+    fn parse_signed_list() {
+        assert_eq!(
+            parse_all(signed_list::<i64>(","), "1, -2,3 , +4"),
+            Ok(vec![1, -2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn parse_delimited_record() {
+        let policy = pair(
+            terminated(
+                pair(terminated(unsigned::<usize>(), literal("-")), unsigned()),
+                spaces(),
+            ),
+            pair(
+                terminated(take_while1(char::is_alphabetic, "a letter"), literal(": ")),
+                word(),
+            ),
+        );
+
+        assert_eq!(
+            parse_all(policy, "1-3 a: abcde"),
+            Ok(((1, 3), ("a".to_string(), "abcde".to_string())))
+        );
+    }
+
+    #[test]
+    fn parse_lines_reports_line_numbers() {
+        let lines = vec!["<x=1, y=2>", "<x=3, y=oops>"];
+        let point = delimited(
+            literal("<x="),
+            pair(signed::<i32>(), preceded(literal(", y="), signed::<i32>())),
+            literal(">"),
+        );
+
+        let error = parse_lines(point, &lines).unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (2, 9));
+    }
+
+    #[test]
+    fn parse_blank_line_separated_groups() {
+        let lines = vec!["abc", "", "a", "b", "c", "", "", "ab"];
+
+        assert_eq!(
+            parse_groups(word(), &lines),
+            Ok(vec![
+                vec!["abc".to_string()],
+                vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                vec!["ab".to_string()],
+            ])
+        );
+        assert_eq!(split_groups(&lines).len(), 3);
+    }
+
+    #[test]
+    fn parse_character_grid() {
+        let lines = vec!["#.#", "..#"];
+        let grid = parse_grid(&lines, |char| match char {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(grid, vec![vec![true, false, true], vec![false, false, true]]);
+        assert_eq!(parse_char_grid(&lines).unwrap()[1][2], '#');
+    }
+
+    #[test]
+    fn grid_errors_point_to_position() {
+        let error = parse_grid(&["..", ".x"], |char| if char == '.' { Some(()) } else { None })
+            .unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (2, 2));
+
+        let error = parse_char_grid(&["...", ".."]).unwrap_err();
+
+        assert_eq!((error.line(), error.column()), (2, 3));
+    }
+
+    #[test]
+    fn trailing_input_is_rejected() {
+        let error = parse_all(unsigned::<u32>(), "12 ").unwrap_err();
+
+        assert_eq!(error.column(), 3);
+    }
+}
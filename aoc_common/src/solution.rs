@@ -0,0 +1,163 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::AocResult;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn all() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A puzzle solution which can be driven by the runner. `parse` turns the lines of the puzzle
+/// input into the representation which both parts share.
+pub trait Solution {
+    type Input;
+
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn parse(&self, lines: &[String]) -> AocResult<Self::Input>;
+
+    fn part_one(&self, input: &Self::Input) -> AocResult<String>;
+
+    fn part_two(&self, input: &Self::Input) -> AocResult<String>;
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: AocResult<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct SolutionRun {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Object safe view of a `Solution` so that solutions with different input types can be
+/// registered side by side.
+pub trait RunnableSolution {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn run(&self, lines: &[String], parts: &[Part]) -> AocResult<SolutionRun>;
+}
+
+impl<S: Solution> RunnableSolution for S {
+    fn year(&self) -> u16 {
+        Solution::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn run(&self, lines: &[String], parts: &[Part]) -> AocResult<SolutionRun> {
+        let start = Instant::now();
+        let input = self.parse(lines)?;
+        let parse_elapsed = start.elapsed();
+
+        let parts = parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::One => self.part_one(&input),
+                    Part::Two => self.part_two(&input),
+                };
+
+                PartRun {
+                    part,
+                    answer,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(SolutionRun {
+            parse_elapsed,
+            parts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{parse_lines, signed};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        fn year(&self) -> u16 {
+            2018
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn parse(&self, lines: &[String]) -> AocResult<Vec<i32>> {
+            Ok(parse_lines(signed(), lines)?)
+        }
+
+        fn part_one(&self, input: &Vec<i32>) -> AocResult<String> {
+            Ok(input.iter().sum::<i32>().to_string())
+        }
+
+        fn part_two(&self, input: &Vec<i32>) -> AocResult<String> {
+            Ok(input.iter().product::<i32>().to_string())
+        }
+    }
+
+    #[test]
+    fn run_selected_parts() {
+        let lines = vec!["2".to_string(), "3".to_string()];
+        let run = Sum.run(&lines, &[Part::Two]).unwrap();
+
+        assert_eq!(run.parts.len(), 1);
+        assert_eq!(run.parts[0].part, Part::Two);
+        assert_eq!(run.parts[0].answer.as_ref().unwrap(), "6");
+    }
+
+    #[test]
+    fn parse_errors_abort_run() {
+        let lines = vec!["x".to_string()];
+
+        assert!(Sum.run(&lines, &Part::all()).is_err());
+    }
+}
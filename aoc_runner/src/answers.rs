@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::solution::Part;
+use aoc_common::{AocError, AocResult};
+
+use crate::registry;
+use crate::DayRun;
+
+/// Accepted answer of a puzzle part together with the hash of the input it was computed for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredAnswer {
+    pub input_hash: u64,
+    pub answer: String,
+}
+
+/// Answers file with one line per puzzle part:
+///
+/// ```text
+/// # year day part input-hash answer
+/// 2019 17 2 5f0e7c1b2a9d3c48 651043
+/// ```
+///
+/// Newlines and backslashes in answers are escaped so that every answer fits on its line.
+#[derive(Debug, Default)]
+pub struct AnswerDb {
+    answers: BTreeMap<(u16, u8, Part), StoredAnswer>,
+}
+
+impl AnswerDb {
+    pub fn new() -> AnswerDb {
+        AnswerDb::default()
+    }
+
+    /// Loads the answers from the given file. A missing file yields an empty database.
+    pub fn load(path: &Path) -> AocResult<AnswerDb> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                AnswerDb::parse(&content).map_err(|err| err.in_file(&path.display().to_string()))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(AnswerDb::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn parse(content: &str) -> AocResult<AnswerDb> {
+        let mut db = AnswerDb::new();
+
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: &str| AocError::parse_at(index + 1, 1, message);
+            let mut fields = line.splitn(5, ' ');
+            let mut field = |name: &str| {
+                fields
+                    .next()
+                    .ok_or_else(|| error(&format!("Missing {}.", name)))
+            };
+
+            let year = field("year")?
+                .parse::<u16>()
+                .map_err(|_| error("Invalid year."))?;
+            let day = field("day")?
+                .parse::<u8>()
+                .map_err(|_| error("Invalid day."))?;
+            let part = field("part")?
+                .parse::<u8>()
+                .ok()
+                .and_then(Part::from_number)
+                .ok_or_else(|| error("Invalid part."))?;
+            let input_hash = u64::from_str_radix(field("input hash")?, 16)
+                .map_err(|_| error("Invalid input hash."))?;
+            let answer = unescape(field("answer")?).ok_or_else(|| error("Invalid escape."))?;
+
+            db.insert(year, day, part, StoredAnswer { input_hash, answer });
+        }
+
+        Ok(db)
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        fs::write(path, self.to_file_content())?;
+
+        Ok(())
+    }
+
+    pub fn to_file_content(&self) -> String {
+        let mut content = String::from("# year day part input-hash answer\n");
+
+        for ((year, day, part), stored) in &self.answers {
+            writeln!(
+                content,
+                "{} {} {} {:016x} {}",
+                year,
+                day,
+                part,
+                stored.input_hash,
+                escape(&stored.answer)
+            )
+            .unwrap();
+        }
+
+        content
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: Part) -> Option<&StoredAnswer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: Part, answer: StoredAnswer) {
+        self.answers.insert((year, day, part), answer);
+    }
+
+    /// Stores every successful answer of the given runs. Returns the number of stored answers.
+    pub fn record(&mut self, runs: &[DayRun]) -> usize {
+        let mut recorded = 0;
+
+        for run in runs {
+            if let (Some(input_hash), Ok(solution_run)) = (run.input_hash, &run.result) {
+                for part_run in &solution_run.parts {
+                    if let Ok(answer) = &part_run.answer {
+                        let answer = StoredAnswer {
+                            input_hash,
+                            answer: answer.clone(),
+                        };
+                        self.insert(run.year, run.day, part_run.part, answer);
+                        recorded += 1;
+                    }
+                }
+            }
+        }
+
+        recorded
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+pub fn default_path() -> PathBuf {
+    registry::repository_root().join("answers.txt")
+}
+
+/// FNV-1a hash of the input lines. Unlike `DefaultHasher` it is stable across Rust releases,
+/// which matters since the hashes are written to disk.
+pub fn input_hash(lines: &[String]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    lines
+        .iter()
+        .flat_map(|line| line.bytes().chain(std::iter::once(b'\n')))
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> Option<String> {
+    let mut result = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+
+    while let Some(chr) = chars.next() {
+        if chr == '\\' {
+            match chars.next()? {
+                'n' => result.push('\n'),
+                '\\' => result.push('\\'),
+                _ => return None,
+            }
+        } else {
+            result.push(chr);
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut db = AnswerDb::new();
+        db.insert(
+            2019,
+            8,
+            Part::Two,
+            StoredAnswer {
+                input_hash: 42,
+                answer: "# #\n\\ #".to_string(),
+            },
+        );
+        db.insert(
+            2018,
+            1,
+            Part::One,
+            StoredAnswer {
+                input_hash: 7,
+                answer: "430".to_string(),
+            },
+        );
+
+        let content = db.to_file_content();
+        let parsed = AnswerDb::parse(&content).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.get(2019, 8, Part::Two), db.get(2019, 8, Part::Two));
+        assert_eq!(
+            content.lines().nth(1),
+            Some("2018 1 1 0000000000000007 430")
+        );
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let err = AnswerDb::parse("# header\n2019 x 1 00 1\n").unwrap_err();
+
+        assert_eq!(err.to_string(), "Parse error at 2:1: Invalid day.");
+    }
+
+    #[test]
+    fn hash_depends_on_line_breaks() {
+        let joined = vec!["ab".to_string()];
+        let split = vec!["a".to_string(), "b".to_string()];
+
+        assert_ne!(input_hash(&joined), input_hash(&split));
+    }
+}
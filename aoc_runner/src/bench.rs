@@ -0,0 +1,300 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc_common::parse::tokens;
+use aoc_common::solution::Part;
+use aoc_common::{AocError, AocResult};
+
+use crate::registry::{self, Entry};
+
+/// Differences below this are treated as noise and never reported as regressions.
+const NOISE_FLOOR: Duration = Duration::from_micros(100);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part(Part::One)),
+            "part2" => Ok(Stage::Part(Part::Two)),
+            _ => Err(AocError::parse(&format!("Unknown stage {}.", s))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub elapsed: Duration,
+}
+
+/// Runs the given parts of the solution `iterations` times on the given input and returns the
+/// median time of parsing and of every part which produced an answer.
+pub fn benchmark(
+    entry: &Entry,
+    input: &Path,
+    parts: &[Part],
+    iterations: usize,
+) -> AocResult<Vec<Measurement>> {
+    let lines = crate::read_input(input)?;
+    let mut samples: Vec<(Stage, Vec<Duration>)> = Vec::new();
+
+    for _ in 0..iterations.max(1) {
+        let run = entry.solution().run(&lines, parts)?;
+        let mut add = |stage: Stage, elapsed: Duration| match samples
+            .iter_mut()
+            .find(|(other, _)| *other == stage)
+        {
+            Some((_, durations)) => durations.push(elapsed),
+            None => samples.push((stage, vec![elapsed])),
+        };
+
+        add(Stage::Parse, run.parse_elapsed);
+
+        for part_run in run.parts.iter().filter(|part_run| part_run.answer.is_ok()) {
+            add(Stage::Part(part_run.part), part_run.elapsed);
+        }
+    }
+
+    Ok(samples
+        .into_iter()
+        .map(|(stage, mut durations)| {
+            durations.sort();
+
+            Measurement {
+                year: entry.year(),
+                day: entry.day(),
+                stage,
+                elapsed: durations[durations.len() / 2],
+            }
+        })
+        .collect())
+}
+
+/// Benchmark results of earlier runs. Every line of the history file holds one measurement:
+///
+/// ```text
+/// <unix timestamp> <year> <day> <stage> <nanoseconds>
+/// ```
+#[derive(Debug, Default)]
+pub struct History {
+    entries: Vec<(u64, Measurement)>,
+}
+
+fn parse_field<T: FromStr>(
+    line: usize,
+    (column, field): (usize, &str),
+    name: &str,
+) -> AocResult<T> {
+    field
+        .parse()
+        .map_err(|_| AocError::parse_at(line, column, &format!("Invalid {}.", name)))
+}
+
+impl History {
+    pub fn load(path: &Path) -> AocResult<History> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                History::parse(&content).map_err(|err| err.in_file(&path.display().to_string()))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn parse(content: &str) -> AocResult<History> {
+        let mut entries = Vec::new();
+
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<(usize, &str)> = tokens(line).collect();
+
+            if fields.len() != 5 {
+                return Err(AocError::parse_at(
+                    index + 1,
+                    1,
+                    "Expected timestamp, year, day, stage and duration.",
+                ));
+            }
+
+            let timestamp = parse_field(index + 1, fields[0], "timestamp")?;
+            let measurement = Measurement {
+                year: parse_field(index + 1, fields[1], "year")?,
+                day: parse_field(index + 1, fields[2], "day")?,
+                stage: fields[3]
+                    .1
+                    .parse()
+                    .map_err(|err: AocError| err.offset_by(index + 1, fields[3].0))?,
+                elapsed: Duration::from_nanos(parse_field(index + 1, fields[4], "duration")?),
+            };
+
+            entries.push((timestamp, measurement));
+        }
+
+        Ok(History { entries })
+    }
+
+    /// Returns the most recent measurement of the given stage.
+    pub fn latest(&self, year: u16, day: u8, stage: Stage) -> Option<&Measurement> {
+        self.entries
+            .iter()
+            .filter(|(_, m)| m.year == year && m.day == day && m.stage == stage)
+            .max_by_key(|(timestamp, _)| *timestamp)
+            .map(|(_, measurement)| measurement)
+    }
+
+    pub fn append(path: &Path, measurements: &[Measurement]) -> AocResult<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        for measurement in measurements {
+            writeln!(
+                file,
+                "{} {} {} {} {}",
+                timestamp,
+                measurement.year,
+                measurement.day,
+                measurement.stage,
+                measurement.elapsed.as_nanos()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn default_history_path() -> PathBuf {
+    registry::repository_root().join("bench_history.txt")
+}
+
+#[derive(Debug)]
+pub struct Comparison {
+    pub measurement: Measurement,
+    pub previous: Option<Duration>,
+}
+
+impl Comparison {
+    /// Relative change compared to the previous run, e.g. `0.1` for 10% slower.
+    pub fn change(&self) -> Option<f64> {
+        self.previous
+            .filter(|previous| !previous.is_zero())
+            .map(|previous| self.measurement.elapsed.as_secs_f64() / previous.as_secs_f64() - 1.0)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        match (self.previous, self.change()) {
+            (Some(previous), Some(change)) => {
+                change > threshold && self.measurement.elapsed > previous + NOISE_FLOOR
+            }
+            _ => false,
+        }
+    }
+}
+
+pub fn compare(measurements: Vec<Measurement>, history: &History) -> Vec<Comparison> {
+    measurements
+        .into_iter()
+        .map(|measurement| {
+            let previous = history
+                .latest(measurement.year, measurement.day, measurement.stage)
+                .map(|previous| previous.elapsed);
+
+            Comparison {
+                measurement,
+                previous,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(stage: Stage, millis: u64) -> Measurement {
+        Measurement {
+            year: 2019,
+            day: 16,
+            stage,
+            elapsed: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    fn latest_measurement_wins() {
+        let history = History::parse(
+            "20 2019 16 part2 3000000\n10 2019 16 part2 1000000\n20 2019 16 parse 5\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            history.latest(2019, 16, Stage::Part(Part::Two)),
+            Some(&measurement(Stage::Part(Part::Two), 3))
+        );
+        assert_eq!(history.latest(2019, 16, Stage::Part(Part::One)), None);
+    }
+
+    #[test]
+    fn invalid_history_line() {
+        let location = |err: AocError| {
+            err.location()
+                .map(|location| (location.line, location.column))
+        };
+
+        assert_eq!(
+            location(History::parse("1 2019 16 part3 5\n").unwrap_err()),
+            Some((1, 11))
+        );
+        assert_eq!(
+            location(History::parse("\n1 2019 x part1 5\n").unwrap_err()),
+            Some((2, 8))
+        );
+    }
+
+    #[test]
+    fn regression_threshold() {
+        let history = History::parse("1 2019 16 part2 10000000\n1 2019 16 parse 10000\n").unwrap();
+        let comparisons = compare(
+            vec![
+                measurement(Stage::Part(Part::Two), 12),
+                measurement(Stage::Parse, 0),
+                measurement(Stage::Part(Part::One), 1),
+            ],
+            &history,
+        );
+
+        assert!(comparisons[0].is_regression(0.1));
+        assert!(!comparisons[0].is_regression(0.5));
+        assert!(!comparisons[1].is_regression(0.1));
+        assert!(!comparisons[2].is_regression(0.1));
+        assert_eq!(comparisons[2].change(), None);
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::solution::{Part, SolutionRun};
+use aoc_common::AocResult;
+
+use crate::registry::Entry;
+
+pub mod answers;
+pub mod bench;
+pub mod registry;
+pub mod report;
+pub mod verify;
+
+pub struct DayRun {
+    pub year: u16,
+    pub day: u8,
+    pub input: PathBuf,
+    pub input_hash: Option<u64>,
+    pub result: AocResult<SolutionRun>,
+}
+
+pub fn read_input(path: &Path) -> AocResult<Vec<String>> {
+    let content = fs::read_to_string(path)?;
+
+    Ok(content.lines().map(String::from).collect())
+}
+
+pub fn run_day(entry: &Entry, input: &Path, parts: &[Part]) -> DayRun {
+    let lines = read_input(input);
+    let input_hash = lines.as_ref().ok().map(|lines| answers::input_hash(lines));
+    let result = lines.and_then(|lines| {
+        entry
+            .solution()
+            .run(&lines, parts)
+            .map_err(|err| err.in_file(&input.display().to_string()))
+    });
+
+    DayRun {
+        year: entry.year(),
+        day: entry.day(),
+        input: input.to_path_buf(),
+        input_hash,
+        result,
+    }
+}
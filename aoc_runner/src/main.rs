@@ -0,0 +1,233 @@
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+use aoc_common::solution::Part;
+use aoc_common::{AocError, AocResult};
+use aoc_runner::answers::{self, AnswerDb};
+use aoc_runner::bench::{self, History};
+use aoc_runner::registry::Entry;
+use aoc_runner::report::{self, Format};
+use aoc_runner::{registry, verify, DayRun};
+
+const USAGE: &str = "Usage: runner [run|verify|record|bench] [YEAR [DAY]] [--part 1|2] \
+                     [--input PATH] [--format text|json] [--answers PATH] [--iterations N] \
+                     [--threshold PERCENT] [--history PATH]";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Command {
+    Run,
+    Verify,
+    Record,
+    Bench,
+}
+
+struct Options {
+    command: Command,
+    year: Option<u16>,
+    day: Option<u8>,
+    parts: Vec<Part>,
+    input: Option<PathBuf>,
+    format: Format,
+    answers: PathBuf,
+    iterations: usize,
+    threshold: f64,
+    history: PathBuf,
+}
+
+impl Options {
+    fn input_for(&self, entry: &Entry) -> PathBuf {
+        self.input.clone().unwrap_or_else(|| entry.default_input())
+    }
+}
+
+fn parse_argument<T: FromStr>(name: &str, value: &str) -> AocResult<T> {
+    value
+        .parse()
+        .map_err(|_| AocError::invalid_input(&format!("Invalid value {} for {}.", value, name)))
+}
+
+fn parse_options(args: &[String]) -> AocResult<Options> {
+    let mut options = Options {
+        command: Command::Run,
+        year: None,
+        day: None,
+        parts: Part::all().to_vec(),
+        input: None,
+        format: Format::Text,
+        answers: answers::default_path(),
+        iterations: 5,
+        threshold: 0.1,
+        history: bench::default_history_path(),
+    };
+    let command = match args.first().map(String::as_str) {
+        Some("run") => Some(Command::Run),
+        Some("verify") => Some(Command::Verify),
+        Some("record") => Some(Command::Record),
+        Some("bench") => Some(Command::Bench),
+        _ => None,
+    };
+    let mut args = args.iter();
+
+    if let Some(command) = command {
+        options.command = command;
+        args.next();
+    }
+
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| AocError::invalid_input(&format!("Missing value for {}.", name)))
+        };
+
+        match arg.as_str() {
+            "--part" => {
+                let part = value("--part")?
+                    .parse::<u8>()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| AocError::invalid_input("The part has to be 1 or 2."))?;
+                options.parts = vec![part];
+            }
+            "--input" => options.input = Some(PathBuf::from(value("--input")?)),
+            "--format" => options.format = value("--format")?.parse()?,
+            "--answers" => options.answers = PathBuf::from(value("--answers")?),
+            "--iterations" => {
+                options.iterations = parse_argument("--iterations", value("--iterations")?)?
+            }
+            "--threshold" => {
+                options.threshold =
+                    parse_argument::<f64>("--threshold", value("--threshold")?)? / 100.0
+            }
+            "--history" => options.history = PathBuf::from(value("--history")?),
+            arg if arg.starts_with("--") => {
+                return Err(AocError::invalid_input(&format!("Unknown option {}.", arg)))
+            }
+            arg => positional.push(arg),
+        }
+    }
+
+    match positional.as_slice() {
+        [] => (),
+        [year] => options.year = Some(parse_argument("year", year)?),
+        [year, day] => {
+            options.year = Some(parse_argument("year", year)?);
+            options.day = Some(parse_argument("day", day)?);
+        }
+        _ => return Err(AocError::invalid_input("Too many arguments.")),
+    }
+
+    if options.input.is_some() && options.day.is_none() {
+        return Err(AocError::invalid_input(
+            "An input file can only be given for a single day.",
+        ));
+    }
+
+    Ok(options)
+}
+
+fn run(options: &Options) -> AocResult<bool> {
+    let entries = registry::select(options.year, options.day);
+
+    if entries.is_empty() {
+        return Err(AocError::invalid_input(
+            "No registered solution matches the selection.",
+        ));
+    }
+
+    match options.command {
+        Command::Run => {
+            let runs = run_solutions(options, &entries);
+            print!("{}", report::render(&runs, options.format));
+
+            Ok(runs.iter().all(|run| match &run.result {
+                Ok(solution_run) => solution_run.parts.iter().all(|part| part.answer.is_ok()),
+                Err(_) => false,
+            }))
+        }
+        Command::Verify => {
+            let runs = run_solutions(options, &entries);
+            let db = AnswerDb::load(&options.answers)?;
+            let verifications = verify::verify(&runs, &options.parts, &db);
+
+            print!(
+                "{}",
+                report::render_verification(&verifications, options.format)
+            );
+
+            Ok(verify::regressed_days(&verifications).is_empty())
+        }
+        Command::Record => {
+            let runs = run_solutions(options, &entries);
+            let mut db = AnswerDb::load(&options.answers)?;
+            let recorded = db.record(&runs);
+            db.save(&options.answers)?;
+
+            println!(
+                "Recorded {} answers in {}.",
+                recorded,
+                options.answers.display()
+            );
+
+            Ok(true)
+        }
+        Command::Bench => run_benchmarks(options, &entries),
+    }
+}
+
+fn run_solutions(options: &Options, entries: &[Entry]) -> Vec<DayRun> {
+    entries
+        .iter()
+        .map(|entry| aoc_runner::run_day(entry, &options.input_for(entry), &options.parts))
+        .collect()
+}
+
+fn run_benchmarks(options: &Options, entries: &[Entry]) -> AocResult<bool> {
+    let history = History::load(&options.history)?;
+    let mut measurements = Vec::new();
+
+    for entry in entries {
+        let input = options.input_for(entry);
+
+        match bench::benchmark(entry, &input, &options.parts, options.iterations) {
+            Ok(day_measurements) => measurements.extend(day_measurements),
+            Err(err) => eprintln!("{} day {}: error: {}", entry.year(), entry.day(), err),
+        }
+    }
+
+    History::append(&options.history, &measurements)?;
+
+    let comparisons = bench::compare(measurements, &history);
+
+    print!(
+        "{}",
+        report::render_benchmark(&comparisons, options.threshold, options.format)
+    );
+
+    Ok(!comparisons
+        .iter()
+        .any(|comparison| comparison.is_regression(options.threshold)))
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::solution::RunnableSolution;
+
+/// A registered solution together with the location of its puzzle input relative to the
+/// repository root.
+pub struct Entry {
+    solution: Box<dyn RunnableSolution>,
+    input: &'static str,
+}
+
+impl Entry {
+    fn new(solution: Box<dyn RunnableSolution>, input: &'static str) -> Entry {
+        Entry { solution, input }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.solution.year()
+    }
+
+    pub fn day(&self) -> u8 {
+        self.solution.day()
+    }
+
+    pub fn solution(&self) -> &dyn RunnableSolution {
+        self.solution.as_ref()
+    }
+
+    pub fn default_input(&self) -> PathBuf {
+        repository_root().join(self.input)
+    }
+}
+
+pub fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn solutions() -> Vec<Entry> {
+    let mut entries = vec![
+        Entry::new(Box::new(aoc1::Puzzle), "aoc1/input.txt"),
+        Entry::new(Box::new(aoc2::Puzzle), "aoc2/input.txt"),
+        Entry::new(Box::new(aoc3::Puzzle), "aoc3/input.txt"),
+        Entry::new(Box::new(aoc4::Puzzle), "aoc4/input.txt"),
+        Entry::new(Box::new(aoc5::Puzzle), "aoc5/input.txt"),
+        Entry::new(Box::new(aoc6::Puzzle), "aoc6/input.txt"),
+        Entry::new(Box::new(aoc7::Puzzle), "aoc7/input.txt"),
+        Entry::new(Box::new(aoc8::Puzzle), "aoc8/input.txt"),
+        Entry::new(Box::new(aoc9::Puzzle), "aoc9/input.txt"),
+        Entry::new(Box::new(aoc10::Puzzle), "aoc10/input.txt"),
+        Entry::new(Box::new(aoc11::Puzzle), "aoc11/input.txt"),
+        Entry::new(Box::new(aoc12::Puzzle), "aoc12/input.txt"),
+        Entry::new(Box::new(aoc13::Puzzle), "aoc13/input.txt"),
+        Entry::new(Box::new(aoc14::Puzzle), "aoc14/input.txt"),
+        Entry::new(Box::new(aoc_2019_1::Puzzle), "aoc_2019_1/input.txt"),
+        Entry::new(Box::new(aoc_2019_2::Puzzle), "aoc_2019_2/input.txt"),
+        Entry::new(
+            Box::new(aoc_2019_2::Day5Puzzle),
+            "aoc_2019_2/input_day_5.txt",
+        ),
+        Entry::new(Box::new(aoc_2019_3::Puzzle), "aoc_2019_3/input.txt"),
+        Entry::new(Box::new(aoc_2019_4::Puzzle), "aoc_2019_4/input.txt"),
+        Entry::new(Box::new(aoc_2019_6::Puzzle), "aoc_2019_6/input.txt"),
+        Entry::new(Box::new(aoc_2019_7::Puzzle), "aoc_2019_7/input_day_7.txt"),
+        Entry::new(Box::new(aoc_2019_8::Puzzle), "aoc_2019_8/input.txt"),
+        Entry::new(Box::new(aoc_2019_9::Puzzle), "aoc_2019_9/input.txt"),
+        Entry::new(Box::new(aoc_2019_10::Puzzle), "aoc_2019_10/input.txt"),
+        Entry::new(Box::new(aoc_2019_11::Puzzle), "aoc_2019_11/input.txt"),
+        Entry::new(Box::new(aoc_2019_12::Puzzle), "aoc_2019_12/input.txt"),
+        Entry::new(Box::new(aoc_2019_13::Puzzle), "aoc_2019_13/input.txt"),
+        Entry::new(Box::new(aoc_2019_14::Puzzle), "aoc_2019_14/input.txt"),
+        Entry::new(Box::new(aoc_2019_15::Puzzle), "aoc_2019_15/input.txt"),
+        Entry::new(Box::new(aoc_2019_16::Puzzle), "aoc_2019_16/input.txt"),
+        Entry::new(Box::new(aoc_2019_17::Puzzle), "aoc_2019_17/input.txt"),
+        Entry::new(Box::new(aoc_2019_18::Puzzle), "aoc_2019_18/input.txt"),
+        Entry::new(Box::new(aoc_2020_1::Puzzle), "aoc_2020_1/input.txt"),
+        Entry::new(Box::new(aoc_2020_2::Puzzle), "aoc_2020_2/input.txt"),
+        Entry::new(Box::new(aoc_2020_3::Puzzle), "aoc_2020_3/input.txt"),
+        Entry::new(Box::new(aoc_2020_4::Puzzle), "aoc_2020_4/input.txt"),
+        Entry::new(Box::new(aoc_2020_5::Puzzle), "aoc_2020_5/input.txt"),
+        Entry::new(Box::new(aoc_2020_6::Puzzle), "aoc_2020_6/input.txt"),
+        Entry::new(Box::new(aoc_2020_7::Puzzle), "aoc_2020_7/input.txt"),
+        Entry::new(Box::new(aoc_2021_1::Puzzle), "aoc_2021_1/input.txt"),
+        Entry::new(Box::new(aoc_2021_2::Puzzle), "aoc_2021_2/input.txt"),
+        Entry::new(Box::new(aoc_2021_3::Puzzle), "aoc_2021_3/input.txt"),
+        Entry::new(Box::new(aoc_2021_4::Puzzle), "aoc_2021_4/input.txt"),
+        Entry::new(Box::new(aoc_2021_5::Puzzle), "aoc_2021_5/input.txt"),
+        Entry::new(Box::new(aoc_2021_6::Puzzle), "aoc_2021_6/input.txt"),
+        Entry::new(Box::new(aoc_2021_7::Puzzle), "aoc_2021_7/input.txt"),
+        Entry::new(Box::new(aoc_2021_8::Puzzle), "aoc_2021_8/input.txt"),
+        Entry::new(Box::new(aoc_2021_9::Puzzle), "aoc_2021_9/input.txt"),
+        Entry::new(Box::new(aoc_2021_10::Puzzle), "aoc_2021_10/input.txt"),
+        Entry::new(Box::new(aoc_2021_11::Puzzle), "aoc_2021_11/input.txt"),
+        Entry::new(Box::new(aoc_2021_12::Puzzle), "aoc_2021_12/input.txt"),
+        Entry::new(Box::new(aoc_2021_13::Puzzle), "aoc_2021_13/input.txt"),
+        Entry::new(Box::new(aoc_2021_14::Puzzle), "aoc_2021_14/input.txt"),
+    ];
+
+    entries.sort_by_key(|entry| (entry.year(), entry.day()));
+    entries
+}
+
+/// Returns the registered solutions matching the given year and day. A missing filter matches
+/// everything.
+pub fn select(year: Option<u16>, day: Option<u8>) -> Vec<Entry> {
+    solutions()
+        .into_iter()
+        .filter(|entry| year.is_none_or(|year| entry.year() == year))
+        .filter(|entry| day.is_none_or(|day| entry.day() == day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_registered_once() {
+        let entries = solutions();
+        let mut keys: Vec<(u16, u8)> = entries.iter().map(|e| (e.year(), e.day())).collect();
+        keys.dedup();
+
+        assert_eq!(keys.len(), entries.len());
+    }
+
+    #[test]
+    fn select_single_day() {
+        let entries = select(Some(2019), Some(5));
+
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0]
+            .default_input()
+            .ends_with("aoc_2019_2/input_day_5.txt"));
+    }
+}
@@ -0,0 +1,405 @@
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::AocError;
+
+use crate::bench::Comparison;
+use crate::verify::{self, Verdict, Verification};
+use crate::DayRun;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(AocError::invalid_input(&format!(
+                "Unknown output format: {}",
+                s
+            ))),
+        }
+    }
+}
+
+pub fn render(runs: &[DayRun], format: Format) -> String {
+    match format {
+        Format::Text => render_text(runs),
+        Format::Json => render_json(runs),
+    }
+}
+
+fn render_text(runs: &[DayRun]) -> String {
+    let mut output = String::new();
+
+    for run in runs {
+        match &run.result {
+            Ok(solution_run) => {
+                writeln!(
+                    output,
+                    "{} day {} (parse {})",
+                    run.year,
+                    run.day,
+                    format_duration(solution_run.parse_elapsed)
+                )
+                .unwrap();
+
+                for part_run in &solution_run.parts {
+                    let answer = match &part_run.answer {
+                        Ok(answer) if answer.contains('\n') => answer
+                            .lines()
+                            .map(|line| format!("\n    {}", line))
+                            .collect(),
+                        Ok(answer) => format!(" {}", answer),
+                        Err(err) => format!(" error: {}", err),
+                    };
+
+                    writeln!(
+                        output,
+                        "  part {} ({}):{}",
+                        part_run.part,
+                        format_duration(part_run.elapsed),
+                        answer
+                    )
+                    .unwrap();
+                }
+            }
+            Err(err) => {
+                writeln!(output, "{} day {}: error: {}", run.year, run.day, err).unwrap();
+            }
+        }
+    }
+
+    output
+}
+
+fn render_json(runs: &[DayRun]) -> String {
+    let days: Vec<String> = runs
+        .iter()
+        .map(|run| {
+            let mut fields = vec![
+                format!("\"year\":{}", run.year),
+                format!("\"day\":{}", run.day),
+                format!(
+                    "\"input\":{}",
+                    json_string(&run.input.display().to_string())
+                ),
+            ];
+
+            match &run.result {
+                Ok(solution_run) => {
+                    let parts: Vec<String> = solution_run
+                        .parts
+                        .iter()
+                        .map(|part_run| {
+                            let outcome = match &part_run.answer {
+                                Ok(answer) => format!("\"answer\":{}", json_string(answer)),
+                                Err(err) => format!("\"error\":{}", json_string(&err.to_string())),
+                            };
+
+                            format!(
+                                "{{\"part\":{},{},\"elapsed_ms\":{}}}",
+                                part_run.part,
+                                outcome,
+                                milliseconds(part_run.elapsed)
+                            )
+                        })
+                        .collect();
+
+                    fields.push(format!(
+                        "\"parse_ms\":{}",
+                        milliseconds(solution_run.parse_elapsed)
+                    ));
+                    fields.push(format!("\"parts\":[{}]", parts.join(",")));
+                }
+                Err(err) => fields.push(format!("\"error\":{}", json_string(&err.to_string()))),
+            }
+
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+
+    format!("[{}]\n", days.join(",\n"))
+}
+
+pub fn render_verification(verifications: &[Verification], format: Format) -> String {
+    match format {
+        Format::Text => render_verification_text(verifications),
+        Format::Json => render_verification_json(verifications),
+    }
+}
+
+fn render_verification_text(verifications: &[Verification]) -> String {
+    let mut output = String::new();
+    let mut counts = [0; 5];
+
+    for verification in verifications {
+        let index = match verification.verdict {
+            Verdict::Correct => 0,
+            Verdict::Regressed { .. } => 1,
+            Verdict::Failed(_) => 2,
+            Verdict::InputChanged => 3,
+            Verdict::Unknown => 4,
+        };
+        counts[index] += 1;
+
+        if verification.verdict == Verdict::Correct {
+            continue;
+        }
+
+        write!(
+            output,
+            "{} day {} part {}: {}",
+            verification.year,
+            verification.day,
+            verification.part,
+            verification.verdict.name()
+        )
+        .unwrap();
+
+        match &verification.verdict {
+            Verdict::Regressed { expected, actual } => writeln!(
+                output,
+                "\n    expected: {}\n    actual:   {}",
+                expected.replace('\n', "\n              "),
+                actual.replace('\n', "\n              ")
+            )
+            .unwrap(),
+            Verdict::Failed(err) => writeln!(output, " ({})", err).unwrap(),
+            _ => writeln!(output).unwrap(),
+        }
+    }
+
+    writeln!(
+        output,
+        "{} correct, {} regressed, {} failed, {} input changed, {} unknown",
+        counts[0], counts[1], counts[2], counts[3], counts[4]
+    )
+    .unwrap();
+
+    let regressed_days = verify::regressed_days(verifications);
+
+    if !regressed_days.is_empty() {
+        let days: Vec<String> = regressed_days
+            .iter()
+            .map(|(year, day)| format!("{}/{}", year, day))
+            .collect();
+        writeln!(output, "Regressed days: {}", days.join(", ")).unwrap();
+    }
+
+    output
+}
+
+fn render_verification_json(verifications: &[Verification]) -> String {
+    let entries: Vec<String> = verifications
+        .iter()
+        .map(|verification| {
+            let mut fields = vec![
+                format!("\"year\":{}", verification.year),
+                format!("\"day\":{}", verification.day),
+                format!("\"part\":{}", verification.part),
+                format!("\"status\":{}", json_string(verification.verdict.name())),
+            ];
+
+            match &verification.verdict {
+                Verdict::Regressed { expected, actual } => {
+                    fields.push(format!("\"expected\":{}", json_string(expected)));
+                    fields.push(format!("\"actual\":{}", json_string(actual)));
+                }
+                Verdict::Failed(err) => fields.push(format!("\"error\":{}", json_string(err))),
+                _ => (),
+            }
+
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+
+    format!("[{}]\n", entries.join(",\n"))
+}
+
+pub fn render_benchmark(comparisons: &[Comparison], threshold: f64, format: Format) -> String {
+    match format {
+        Format::Text => render_benchmark_text(comparisons, threshold),
+        Format::Json => render_benchmark_json(comparisons, threshold),
+    }
+}
+
+fn render_benchmark_text(comparisons: &[Comparison], threshold: f64) -> String {
+    let mut output = String::new();
+
+    for comparison in comparisons {
+        let measurement = &comparison.measurement;
+
+        write!(
+            output,
+            "{} day {:>2} {:<6} {:>14}",
+            measurement.year,
+            measurement.day,
+            measurement.stage.to_string(),
+            format_duration(measurement.elapsed)
+        )
+        .unwrap();
+
+        if let Some(change) = comparison.change() {
+            write!(output, " ({:+.1}%)", change * 100.0).unwrap();
+        }
+
+        if comparison.is_regression(threshold) {
+            write!(output, " REGRESSION").unwrap();
+        }
+
+        writeln!(output).unwrap();
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|comparison| comparison.is_regression(threshold))
+        .count();
+    writeln!(
+        output,
+        "{} regressions above {:.0}%",
+        regressions,
+        threshold * 100.0
+    )
+    .unwrap();
+
+    output
+}
+
+fn render_benchmark_json(comparisons: &[Comparison], threshold: f64) -> String {
+    let entries: Vec<String> = comparisons
+        .iter()
+        .map(|comparison| {
+            let measurement = &comparison.measurement;
+            let previous = comparison
+                .previous
+                .map_or_else(|| "null".to_string(), milliseconds);
+
+            format!(
+                "{{\"year\":{},\"day\":{},\"stage\":\"{}\",\"elapsed_ms\":{},\"previous_ms\":{},\"regression\":{}}}",
+                measurement.year,
+                measurement.day,
+                measurement.stage,
+                milliseconds(measurement.elapsed),
+                previous,
+                comparison.is_regression(threshold)
+            )
+        })
+        .collect();
+
+    format!("[{}]\n", entries.join(",\n"))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{} ms", milliseconds(duration))
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+
+    for chr in value.chars() {
+        match chr {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            chr if (chr as u32) < 0x20 => write!(result, "\\u{:04x}", chr as u32).unwrap(),
+            chr => result.push(chr),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use aoc_common::solution::{Part, PartRun, SolutionRun};
+
+    use super::*;
+
+    fn day_run() -> DayRun {
+        DayRun {
+            year: 2019,
+            day: 17,
+            input: PathBuf::from("input.txt"),
+            input_hash: None,
+            result: Ok(SolutionRun {
+                parse_elapsed: Duration::from_micros(1500),
+                parts: vec![
+                    PartRun {
+                        part: Part::One,
+                        answer: Ok("a\"b\nc".to_string()),
+                        elapsed: Duration::from_millis(2),
+                    },
+                    PartRun {
+                        part: Part::Two,
+                        answer: Err(AocError::no_solution("none")),
+                        elapsed: Duration::from_millis(0),
+                    },
+                ],
+            }),
+        }
+    }
+
+    #[test]
+    fn render_json_output() {
+        assert_eq!(
+            render(&[day_run()], Format::Json),
+            "[{\"year\":2019,\"day\":17,\"input\":\"input.txt\",\"parse_ms\":1.500,\"parts\":[\
+             {\"part\":1,\"answer\":\"a\\\"b\\nc\",\"elapsed_ms\":2.000},\
+             {\"part\":2,\"error\":\"No solution found: none\",\"elapsed_ms\":0.000}]}]\n"
+        );
+    }
+
+    #[test]
+    fn render_text_output() {
+        assert_eq!(
+            render(&[day_run()], Format::Text),
+            "2019 day 17 (parse 1.500 ms)\n  part 1 (2.000 ms):\n    a\"b\n    c\n  part 2 (0.000 ms): error: No solution found: none\n"
+        );
+    }
+
+    #[test]
+    fn render_verification_summary() {
+        let verifications = vec![
+            Verification {
+                year: 2019,
+                day: 2,
+                part: Part::One,
+                verdict: Verdict::Correct,
+            },
+            Verification {
+                year: 2019,
+                day: 5,
+                part: Part::Two,
+                verdict: Verdict::Regressed {
+                    expected: "1".to_string(),
+                    actual: "2".to_string(),
+                },
+            },
+        ];
+
+        assert_eq!(
+            render_verification(&verifications, Format::Text),
+            "2019 day 5 part 2: regressed\n    expected: 1\n    actual:   2\n\
+             1 correct, 1 regressed, 0 failed, 0 input changed, 0 unknown\n\
+             Regressed days: 2019/5\n"
+        );
+    }
+}
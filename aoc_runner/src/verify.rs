@@ -0,0 +1,188 @@
+use aoc_common::solution::Part;
+
+use crate::answers::AnswerDb;
+use crate::DayRun;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Regressed { expected: String, actual: String },
+    Failed(String),
+    InputChanged,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Verdict::Regressed { .. } | Verdict::Failed(_))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Regressed { .. } => "regressed",
+            Verdict::Failed(_) => "failed",
+            Verdict::InputChanged => "input changed",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Verification {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub verdict: Verdict,
+}
+
+/// Compares the answers of the given runs with the stored answers. Answers which were computed
+/// for a different input than the stored one cannot be compared and are reported as such, while
+/// errors, including unreadable inputs, always fail.
+pub fn verify(runs: &[DayRun], parts: &[Part], db: &AnswerDb) -> Vec<Verification> {
+    let mut verifications = Vec::new();
+
+    for run in runs {
+        for &part in parts {
+            let stored = db.get(run.year, run.day, part);
+            let answer = match &run.result {
+                Ok(solution_run) => solution_run
+                    .parts
+                    .iter()
+                    .find(|part_run| part_run.part == part)
+                    .map(|part_run| part_run.answer.as_ref().map_err(|err| err.to_string())),
+                Err(err) => Some(Err(err.to_string())),
+            };
+
+            let verdict = match (stored, answer) {
+                (None, _) | (_, None) => Verdict::Unknown,
+                // also covers inputs which could not be read and therefore have no hash
+                (Some(_), Some(Err(err))) => Verdict::Failed(err),
+                (Some(stored), _) if run.input_hash != Some(stored.input_hash) => {
+                    Verdict::InputChanged
+                }
+                (Some(stored), Some(Ok(answer))) if *answer == stored.answer => Verdict::Correct,
+                (Some(stored), Some(Ok(answer))) => Verdict::Regressed {
+                    expected: stored.answer.clone(),
+                    actual: answer.clone(),
+                },
+            };
+
+            verifications.push(Verification {
+                year: run.year,
+                day: run.day,
+                part,
+                verdict,
+            });
+        }
+    }
+
+    verifications
+}
+
+/// Returns the days with at least one regressed or failing part.
+pub fn regressed_days(verifications: &[Verification]) -> Vec<(u16, u8)> {
+    let mut days: Vec<(u16, u8)> = verifications
+        .iter()
+        .filter(|verification| verification.verdict.is_regression())
+        .map(|verification| (verification.year, verification.day))
+        .collect();
+    days.dedup();
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use aoc_common::solution::{PartRun, SolutionRun};
+    use aoc_common::AocError;
+
+    use super::*;
+    use crate::answers::StoredAnswer;
+
+    fn day_run(input_hash: u64, part_one: &str) -> DayRun {
+        DayRun {
+            year: 2021,
+            day: 1,
+            input: PathBuf::from("input.txt"),
+            input_hash: Some(input_hash),
+            result: Ok(SolutionRun {
+                parse_elapsed: Duration::from_millis(0),
+                parts: vec![
+                    PartRun {
+                        part: Part::One,
+                        answer: Ok(part_one.to_string()),
+                        elapsed: Duration::from_millis(0),
+                    },
+                    PartRun {
+                        part: Part::Two,
+                        answer: Err(AocError::no_solution("none")),
+                        elapsed: Duration::from_millis(0),
+                    },
+                ],
+            }),
+        }
+    }
+
+    fn db() -> AnswerDb {
+        let mut db = AnswerDb::new();
+        for part in Part::all().iter() {
+            db.insert(
+                2021,
+                1,
+                *part,
+                StoredAnswer {
+                    input_hash: 1,
+                    answer: "7".to_string(),
+                },
+            );
+        }
+        db
+    }
+
+    #[test]
+    fn detect_regressions() {
+        let verifications = verify(&[day_run(1, "8")], &Part::all(), &db());
+
+        assert_eq!(
+            verifications[0].verdict,
+            Verdict::Regressed {
+                expected: "7".to_string(),
+                actual: "8".to_string()
+            }
+        );
+        assert_eq!(verifications[1].verdict.name(), "failed");
+        assert_eq!(regressed_days(&verifications), vec![(2021, 1)]);
+    }
+
+    #[test]
+    fn changed_input_is_not_compared() {
+        let verifications = verify(&[day_run(2, "8")], &[Part::One], &db());
+
+        assert_eq!(verifications[0].verdict, Verdict::InputChanged);
+        assert!(regressed_days(&verifications).is_empty());
+    }
+
+    #[test]
+    fn missing_input_fails() {
+        let entry = crate::registry::select(Some(2021), Some(1)).remove(0);
+        let run = crate::run_day(&entry, &PathBuf::from("missing/input.txt"), &[Part::One]);
+        let verifications = verify(&[run], &[Part::One], &db());
+
+        assert_eq!(verifications[0].verdict.name(), "failed");
+        assert_eq!(regressed_days(&verifications), vec![(2021, 1)]);
+    }
+
+    #[test]
+    fn matching_answer_is_correct() {
+        let verifications = verify(&[day_run(1, "7")], &[Part::One], &AnswerDb::new());
+
+        assert_eq!(verifications[0].verdict, Verdict::Unknown);
+
+        let verifications = verify(&[day_run(1, "7")], &[Part::One], &db());
+
+        assert_eq!(verifications[0].verdict, Verdict::Correct);
+    }
+}
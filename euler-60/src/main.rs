@@ -2,75 +2,10 @@ use std::collections::HashMap;
 use std::collections::BinaryHeap;
 use std::cmp::Ordering;
 
-#[derive(Debug)]
-struct PrimeNumbers {
-    ascending: Vec<usize>,
-    set: HashMap<usize, usize>,
-}
-
-impl PrimeNumbers {
-    fn new(numbers: usize) -> PrimeNumbers {
-        let mut sieve = vec![true; numbers];
-
-        sieve[0] = false;
-        sieve[1] = false;
-
-        for i in 2..sieve.len() {
-            if sieve[i] {
-                for j in ((2 * i)..sieve.len()).step_by(i) {
-                    sieve[j] = false;
-                }
-            }
-        }
-
-        let mut counter = 0;
-
-        for i in 0..sieve.len() {
-            if sieve[i] {
-                counter += 1;
-            }
-        }
-
-        let mut ascending = vec![0; counter];
-        let mut set = HashMap::with_capacity(counter);
-
-        counter = 0;
-
-        for i in 0..sieve.len() {
-            if sieve[i] {
-                ascending[counter] = i;
-                set.insert(i, counter);
-
-                counter += 1
-            }
-        }
-
-        PrimeNumbers {
-            ascending,
-            set
-        }
-    }
-
-    fn is_prime(&self, prime_candidate: usize) -> Result<bool, ()> {
-
-        if prime_candidate <= self.largest_prime() {
-            Ok(self.set.contains_key(&prime_candidate))
-        } else {
-            Err(())
-        }
-    }
-
-    fn largest_prime(&self) -> usize {
-        self.ascending[self.ascending.len() - 1]
-    }
-
-    fn get(&self, idx: usize) -> usize {
-        self.ascending[idx]
-    }
-}
+use aoc_common::math::number_theory::PrimeSieve;
 
 struct Solution<'a> {
-    prime_numbers: &'a PrimeNumbers,
+    prime_numbers: &'a PrimeSieve,
     prime_pairs: usize
 }
 
@@ -92,8 +27,8 @@ impl SolutionCandidate {
         self.indices.len()
     }
 
-    fn generate_primes(&self, prime_numbers: &PrimeNumbers) -> Vec<usize> {
-        self.indices.iter().map(|&idx| prime_numbers.ascending[idx]).collect()
+    fn generate_primes(&self, prime_numbers: &PrimeSieve) -> Vec<usize> {
+        self.indices.iter().map(|&idx| prime_numbers.primes()[idx] as usize).collect()
     }
 }
 
@@ -115,18 +50,26 @@ impl Ord for SolutionCandidate {
 }
 
 impl<'a> Solution<'a> {
-    fn new(prime_numbers: &'a PrimeNumbers, prime_pairs: usize) -> Solution<'a> {
+    fn new(prime_numbers: &'a PrimeSieve, prime_pairs: usize) -> Solution<'a> {
         Solution{
             prime_numbers,
             prime_pairs
         }
     }
 
+    fn prime(&self, index: usize) -> usize {
+        self.prime_numbers.primes()[index] as usize
+    }
+
+    fn is_prime(&self, prime_candidate: usize) -> Result<bool, ()> {
+        self.prime_numbers.is_prime(prime_candidate as u64).ok_or(())
+    }
+
     fn find_prime_pairs(&self) -> Option<Vec<usize>> {
         println!("Calculate solution");
         let mut heap = BinaryHeap::new();
 
-        heap.push(SolutionCandidate::new(vec![1], vec![self.prime_numbers.get(1)]));
+        heap.push(SolutionCandidate::new(vec![1], vec![self.prime(1)]));
 
         let mut counter = 0;
 
@@ -161,7 +104,7 @@ impl<'a> Solution<'a> {
                 let mut new_indices = solution_candidate.indices.clone();
                 let mut new_primes = solution_candidate.primes.clone();
                 new_indices.push(candidate);
-                new_primes.push(self.prime_numbers.get(candidate));
+                new_primes.push(self.prime(candidate));
 
                 Some(SolutionCandidate {
                     indices: new_indices,
@@ -181,7 +124,7 @@ impl<'a> Solution<'a> {
         match new_candidate {
             Ok(candidate) => {
                 solution_candidate.indices[last_index] = candidate;
-                solution_candidate.primes[last_index] = self.prime_numbers.get(candidate);
+                solution_candidate.primes[last_index] = self.prime(candidate);
 
                 Some(solution_candidate)
             }
@@ -203,7 +146,7 @@ impl<'a> Solution<'a> {
 
     fn is_new_pair(&self, others: &[usize], new_candidate: usize) -> Result<bool, ()> {
         for &other in others {
-            if !self.is_valid_prime_pair(self.prime_numbers.get(other), self.prime_numbers.get(new_candidate))? {
+            if !self.is_valid_prime_pair(self.prime(other), self.prime(new_candidate))? {
                 return Ok(false);
             }
         }
@@ -214,7 +157,7 @@ impl<'a> Solution<'a> {
     fn validate_prime_pairs(&self, pairs: &[usize]) -> Result<bool, ()> {
         for i in 0..(pairs.len() - 1) {
             for j in (i + 1)..pairs.len() {
-                if !self.is_valid_prime_pair(self.prime_numbers.ascending[pairs[i]], self.prime_numbers.ascending[pairs[j]])? {
+                if !self.is_valid_prime_pair(self.prime(pairs[i]), self.prime(pairs[j]))? {
                     return Ok(false);
                 }
             }
@@ -228,10 +171,10 @@ impl<'a> Solution<'a> {
             return Err(());
         }
 
-        let x = self.prime_numbers.is_prime(common::concatenate(a, b))?;
+        let x = self.is_prime(common::concatenate(a, b))?;
 
         if x {
-            Ok(self.prime_numbers.is_prime(common::concatenate(b, a))?)
+            Ok(self.is_prime(common::concatenate(b, a))?)
         } else {
             Ok(false)
         }
@@ -241,18 +184,6 @@ impl<'a> Solution<'a> {
 mod common {
     const DIGITS:[usize; 11] = [10, 100, 1000, 10000, 100000, 1000000, 10000000, 100000000, 1000000000, 10000000000, 100000000000];
 
-    pub fn is_prime(number: usize) -> bool {
-        let sqrt = (number as f64).sqrt() as usize + 1;
-
-        for i in 2..sqrt {
-            if number % i == 0 {
-                return false;
-            }
-        }
-
-        true
-    }
-
     pub fn concatenate(a: usize, b: usize) -> usize {
         let factor = calculate_factor(b);
 
@@ -271,7 +202,7 @@ mod common {
 }
 
 fn main() {
-    let primes = PrimeNumbers::new(100000000);
+    let primes = PrimeSieve::new(100000000);
     let solution = Solution::new(&primes, 5);
     let prime_pairs = solution.find_prime_pairs().unwrap();
 
@@ -283,25 +214,24 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::ops::Index;
 
     #[test]
     fn test_wrong_prime_pairs() {
-        let primes = PrimeNumbers::new(10000);
+        let primes = PrimeSieve::new(10000);
         let solution = Solution::new(&primes, 3);
         assert!(!solution.validate_prime_pairs(&vec![0, 1, 4]).unwrap());
     }
 
     #[test]
     fn test_prime_pairs() {
-        let primes = PrimeNumbers::new(700000);
+        let primes = PrimeSieve::new(700000);
         let solution = Solution::new(&primes, 4);
-        assert!(solution.validate_prime_pairs(&vec![*primes.set.index(&3), *primes.set.index(&7), *primes.set.index(&109), *primes.set.index(&673)]).unwrap());
+        assert!(solution.validate_prime_pairs(&vec![primes.index_of(3).unwrap(), primes.index_of(7).unwrap(), primes.index_of(109).unwrap(), primes.index_of(673).unwrap()]).unwrap());
     }
 
     #[test]
     fn test_wrong_prime_pair() {
-        let primes = PrimeNumbers::new(10000);
+        let primes = PrimeSieve::new(10000);
         let solution = Solution::new(&primes, 2);
         assert!(!solution.is_valid_prime_pair(2, 11).unwrap());
     }
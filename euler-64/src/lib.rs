@@ -1,4 +1,4 @@
-use std::slice::Iter;
+use aoc_common::math::number_theory::gcd;
 
 #[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
 pub struct Fraction(Times, Const);
@@ -46,17 +46,20 @@ pub fn fraction(a: i32, b: i32) -> Fraction {
     Fraction(Times(Const(a), one_addition()), Const(b))
 }
 
-pub fn cancel(primes: &Primes, fraction: Fraction) -> Fraction {
+pub fn cancel(fraction: Fraction) -> Fraction {
     match fraction {
         Fraction(Times(Const(a), b), Const(c)) => {
-            let gcd = primes.gcd(a, c);
-            Fraction(Times(Const(a / gcd as i32), b), Const(c / gcd as i32))
+            let gcd = gcd(a, c);
+            if gcd == 0 {
+                return fraction;
+            }
+            Fraction(Times(Const(a / gcd), b), Const(c / gcd))
         }
     }
 }
 
-pub fn next_fraction_pair(primes: &Primes, fraction: Fraction) -> (i32, Fraction) {
-    let fraction = cancel(primes, fraction);
+pub fn next_fraction_pair(fraction: Fraction) -> (i32, Fraction) {
+    let fraction = cancel(fraction);
 
     match fraction {
         Fraction(Times(Const(a), Addition(s, Const(b))), Const(c)) => {
@@ -77,28 +80,26 @@ fn next_fraction(fraction: Fraction) -> Fraction {
     Fraction(Times(c, Addition(s, Const(-b))), Const(a * (sv as i32 - b*b)))
 }
 
-pub struct SqrtSequence<'a> {
+pub struct SqrtSequence {
     fraction: Fraction,
-    primes: &'a Primes,
 }
 
-impl SqrtSequence<'_> {
-    pub fn new(v: u32, primes: &'_ Primes) -> SqrtSequence<'_> {
+impl SqrtSequence {
+    pub fn new(v: u32) -> SqrtSequence {
         SqrtSequence {
             fraction: sqrt(v),
-            primes
         }
     }
 }
 
-impl Iterator for SqrtSequence<'_> {
+impl Iterator for SqrtSequence {
     type Item = (i32, Fraction);
 
     fn next(&mut self) -> Option<Self::Item> {
 
         if not_zero(self.fraction) {
-            let (value, remainder) = next_fraction_pair(self.primes, self.fraction);
-            let next_fraction = cancel(self.primes, next_fraction(remainder));
+            let (value, remainder) = next_fraction_pair(self.fraction);
+            let next_fraction = cancel(next_fraction(remainder));
 
             self.fraction = next_fraction;
 
@@ -109,84 +110,16 @@ impl Iterator for SqrtSequence<'_> {
     }
 }
 
-pub struct Primes {
-    primes: Vec<usize>
-}
-
-impl Primes {
-    pub fn new(number_primes: usize) -> Primes {
-        let mut sieve = vec![true; number_primes];
-
-        sieve[0] = false;
-        sieve[1] = false;
-
-        let mut counter = 0;
-
-        for i in 2..(number_primes as f64).sqrt().ceil() as usize {
-            if sieve[i] {
-                counter += 1;
-                for j in ((2 * i)..number_primes).step_by(i) {
-                    sieve[j] = false;
-                }
-            }
-        }
-
-        let mut primes = Vec::with_capacity(counter);
-
-        for i in 0..number_primes {
-            if sieve[i] {
-                primes.push(i);
-            }
-        }
-        Primes{primes}
-    }
-
-    fn iter(&self) -> Iter<usize> {
-        self.primes.iter()
-    }
-
-    pub fn gcd(&self, a: i32, b: i32) -> u32 {
-        let mut a = a.abs() as u32;
-        let mut b = b.abs() as u32;
-        let mut gcd = 1;
-        let mut prime_iterator = self.iter();
-
-        while a > 1 && b > 1 {
-            let prime = *prime_iterator.next().unwrap() as u32;
-
-            let mut counter_a = 0;
-
-            while a % prime == 0 {
-                a /= prime;
-                counter_a += 1;
-            }
-
-            let mut counter_b = 0;
-
-            while b % prime == 0 {
-                b /= prime;
-                counter_b += 1;
-            }
-
-            for _ in 0..u32::min(counter_a, counter_b) {
-                gcd *= prime;
-            }
-        }
-
-        gcd
-    }
-}
-
 fn not_zero(fraction: Fraction) -> bool {
     let Fraction(_, Const(v)) = fraction;
 
     v != 0
 }
 
-pub fn calculate_sequence_length(v: u32, primes: &Primes) -> usize {
+pub fn calculate_sequence_length(v: u32) -> usize {
     let mut fractions = std::collections::HashSet::new();
 
-    let mut sqrt_seq = SqrtSequence::new(v, primes);
+    let mut sqrt_seq = SqrtSequence::new(v);
 
     sqrt_seq.take_while(|(_, fraction)| not_zero(*fraction) && fractions.insert(*fraction)).count()
 }
@@ -197,25 +130,21 @@ mod tests {
 
     #[test]
     fn test_calculate_sequence_length9() {
-        let primes = Primes::new(100);
-        assert_eq!(calculate_sequence_length(9, &primes), 0)
+        assert_eq!(calculate_sequence_length(9), 0)
     }
 
     #[test]
     fn test_calculate_sequence_length23() {
-        let primes = Primes::new(100);
-        assert_eq!(calculate_sequence_length(23, &primes), 4)
+        assert_eq!(calculate_sequence_length(23), 4)
     }
 
     #[test]
     fn test_calculate_sequence_length7() {
-        let primes = Primes::new(100);
-        assert_eq!(calculate_sequence_length(7, &primes), 4)
+        assert_eq!(calculate_sequence_length(7), 4)
     }
 
     fn test_sqrt_sequence(v: u32, t: usize) -> Vec<i32> {
-        let primes = Primes::new(100);
-        let sqrt_seq = SqrtSequence::new(v, &primes);
+        let sqrt_seq = SqrtSequence::new(v);
 
         sqrt_seq.map(|(v, _)| v).take(t).collect()
     }
@@ -249,45 +178,45 @@ mod tests {
 
     #[test]
     fn test_next_number() {
-        let primes = Primes::new(100);
         let fraction = Fraction(Times(Const(1), Addition(Sqrt::new(23), Const(4))), Const(7));
 
-        let (value, new_fraction) = next_fraction_pair(&primes, fraction);
+        let (value, new_fraction) = next_fraction_pair(fraction);
 
         assert_eq!(value, 1);
         assert_eq!(new_fraction, Fraction(Times(Const(1), Addition(Sqrt::new(23), Const(-3))), Const(7)));
     }
 
     #[test]
-    fn test_primes() {
-        let primes = Primes::new(10);
+    fn test_cancel() {
+        let fraction = Fraction(Times(Const(2), Addition(Sqrt::new(23), Const(4))), Const(6));
+
+        assert_eq!(cancel(fraction), Fraction(Times(Const(1), Addition(Sqrt::new(23), Const(4))), Const(3)));
+    }
 
-        let result:Vec<usize> = primes.primes.into_iter().collect();
+    #[test]
+    fn test_cancel_zero() {
+        let fraction = Fraction(Times(Const(0), Addition(Sqrt::new(23), Const(4))), Const(0));
 
-        assert_eq!(result, vec![2, 3, 5, 7]);
+        assert_eq!(cancel(fraction), fraction);
     }
 
     #[test]
     fn test_gcd_aa() {
-        let primes = Primes::new(10);
-        assert_eq!(primes.gcd(1, 1), 1)
+        assert_eq!(gcd(1, 1), 1)
     }
 
     #[test]
     fn test_gcd_ab() {
-        let primes = Primes::new(10);
-        assert_eq!(primes.gcd(4, 2), 2)
+        assert_eq!(gcd(4, 2), 2)
     }
 
     #[test]
     fn test_gcd_ba() {
-        let primes = Primes::new(10);
-        assert_eq!(primes.gcd(2, 4), 2)
+        assert_eq!(gcd(2, 4), 2)
     }
 
     #[test]
     fn test_gcd_ac() {
-        let primes = Primes::new(10);
-        assert_eq!(primes.gcd(7, 5), 1)
+        assert_eq!(gcd(7, 5), 1)
     }
 }
\ No newline at end of file
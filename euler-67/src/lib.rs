@@ -1,3 +1,4 @@
+use aoc_common::memo::Memo;
 use std::fs::File;
 use std::io::{BufReader, BufRead};
 use std::io::Error;
@@ -9,19 +10,19 @@ pub fn find_maximum_path(filename: &str) -> Result<u64> {
 
     let result: Vec<String> = buffered.lines().map(|line| line.unwrap()).collect();
 
-    let rows: Vec<Vec<u64>> = result.iter().map(|line| line.split_whitespace().map(|v| v.parse::<u64>().unwrap()).collect()).rev().collect();
+    let rows: Vec<Vec<u64>> = result.iter().map(|line| line.split_whitespace().map(|v| v.parse::<u64>().unwrap()).collect()).collect();
 
-    let maximum_path = rows.into_iter().fold(Vec::new(), |acc, row| {
-        if acc.is_empty() {
-            row
-        } else {
-            assert!(acc.len() == row.len() + 1);
+    let maximum_path = Memo::new().compute((0, 0), &|maximum_path, &(row, column): &(usize, usize)| {
+        let value = rows[row][column];
 
-            row.iter().enumerate().map(|(index, value)| value + u64::max(acc[index], acc[index + 1])).collect()
+        if row + 1 == rows.len() {
+            value
+        } else {
+            value + u64::max(maximum_path((row + 1, column)), maximum_path((row + 1, column + 1)))
         }
     });
 
-    Ok(*maximum_path.get(0).unwrap())
+    Ok(maximum_path)
 }
 
 #[cfg(test)]